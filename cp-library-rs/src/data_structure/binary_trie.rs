@@ -0,0 +1,283 @@
+//! ## 二分トライ木
+//!
+//! `BITS` ビットの非負整数の多重集合を管理する．
+//! 全体に xor を作用させる操作を遅延して扱う．
+//!
+//! - 各操作の時間計算量: $`O(\mathrm{BITS})`$
+//!
+//! ```
+//! use cp_library_rs::data_structure::binary_trie::*;
+//!
+//! let mut trie = BinaryTrie::<4>::new();
+//! trie.insert(3);
+//! trie.insert(5);
+//! trie.insert_count(9, 2);
+//!
+//! assert_eq!(trie.len(), 4);
+//! assert_eq!(trie.min_xor(6), Some(3)); // 5 ^ 6
+//! assert_eq!(trie.max_xor(6), Some(15)); // 9 ^ 6
+//! assert_eq!(trie.kth_smallest(2), Some(9));
+//! assert_eq!(trie.count_less(9), 2);
+//!
+//! // 全体に xor
+//! trie.xor_all(1);
+//! // {2, 4, 8, 8}
+//! assert_eq!(trie.kth_smallest(0), Some(2));
+//! assert_eq!(trie.count(8), 2);
+//!
+//! assert_eq!(trie.erase_count(8, 5), 2);
+//! assert_eq!(trie.max_xor(0), Some(4));
+//! ```
+
+use std::fmt::Debug;
+
+/// 二分トライ木のノード
+#[derive(Debug, Clone)]
+struct Node {
+    /// 子ノードの番号（存在しない場合は `0`）
+    children: [usize; 2],
+    /// 部分木に含まれる要素数
+    count: usize,
+}
+
+/// 二分トライ木
+#[derive(Debug, Clone)]
+pub struct BinaryTrie<const BITS: usize> {
+    /// ノード（`nodes[0]` が根）
+    nodes: Vec<Node>,
+    /// 全体に作用させる xor
+    mask: u64,
+}
+
+impl<const BITS: usize> Default for BinaryTrie<BITS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const BITS: usize> BinaryTrie<BITS> {
+    const NODE: Node = Node {
+        children: [0; 2],
+        count: 0,
+    };
+
+    /// 空の二分トライ木を生成する
+    pub fn new() -> Self {
+        assert!(BITS <= 64, "BITS must be at most 64");
+        Self {
+            nodes: vec![Self::NODE],
+            mask: 0,
+        }
+    }
+
+    /// 要素数
+    pub fn len(&self) -> usize {
+        self.nodes[0].count
+    }
+
+    /// 空であるか
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// `x` の上から `d` 番目のビット
+    fn bit(x: u64, d: usize) -> usize {
+        (x >> (BITS - 1 - d) & 1) as usize
+    }
+
+    /// 値 `x` が `BITS` ビットで表せるか確認する
+    fn check(x: u64) {
+        assert!(
+            BITS == 64 || x >> BITS == 0,
+            "{x} does not fit in {BITS} bits"
+        );
+    }
+
+    /// 値 `x` に対応する葉のノード番号（存在しない場合は `None`）
+    fn find(&self, x: u64) -> Option<usize> {
+        Self::check(x);
+        let x = x ^ self.mask;
+        let mut node = 0;
+        for d in 0..BITS {
+            node = self.nodes[node].children[Self::bit(x, d)];
+            if node == 0 {
+                return None;
+            }
+        }
+        Some(node)
+    }
+
+    /// `x` を1個追加する
+    pub fn insert(&mut self, x: u64) {
+        self.insert_count(x, 1);
+    }
+
+    /// `x` を `cnt` 個追加する
+    pub fn insert_count(&mut self, x: u64, cnt: usize) {
+        Self::check(x);
+        if cnt == 0 {
+            return;
+        }
+        let x = x ^ self.mask;
+        let mut node = 0;
+        self.nodes[node].count += cnt;
+        for d in 0..BITS {
+            let b = Self::bit(x, d);
+            if self.nodes[node].children[b] == 0 {
+                self.nodes[node].children[b] = self.nodes.len();
+                self.nodes.push(Self::NODE);
+            }
+            node = self.nodes[node].children[b];
+            self.nodes[node].count += cnt;
+        }
+    }
+
+    /// `x` を1個削除する
+    ///
+    /// **戻り値**
+    /// - `x` が存在した場合 `true`
+    pub fn erase(&mut self, x: u64) -> bool {
+        self.erase_count(x, 1) == 1
+    }
+
+    /// `x` を最大 `cnt` 個削除する
+    ///
+    /// **戻り値**
+    /// - 実際に削除した個数
+    pub fn erase_count(&mut self, x: u64, cnt: usize) -> usize {
+        let cnt = cnt.min(self.count(x));
+        if cnt == 0 {
+            return 0;
+        }
+        let x = x ^ self.mask;
+        let mut node = 0;
+        self.nodes[node].count -= cnt;
+        for d in 0..BITS {
+            node = self.nodes[node].children[Self::bit(x, d)];
+            self.nodes[node].count -= cnt;
+        }
+        cnt
+    }
+
+    /// `x` の個数
+    pub fn count(&self, x: u64) -> usize {
+        self.find(x).map_or(0, |node| self.nodes[node].count)
+    }
+
+    /// 全要素に `x` を xor する
+    pub fn xor_all(&mut self, x: u64) {
+        Self::check(x);
+        self.mask ^= x;
+    }
+
+    /// 各ビットで `prefer(d)` 側の子を優先して葉まで辿り，経路のビット列を返す
+    fn walk(&self, prefer: u64) -> Option<u64> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut node = 0;
+        let mut res = 0;
+        for d in 0..BITS {
+            let mut b = Self::bit(prefer, d);
+            let child = self.nodes[node].children[b];
+            if child == 0 || self.nodes[child].count == 0 {
+                b ^= 1;
+            }
+            node = self.nodes[node].children[b];
+            res = res << 1 | b as u64;
+        }
+        Some(res)
+    }
+
+    /// 要素 `a` に対する `a ^ x` の最小値
+    ///
+    /// **戻り値**
+    /// - 空の場合 `None`
+    pub fn min_xor(&self, x: u64) -> Option<u64> {
+        Self::check(x);
+        let y = x ^ self.mask;
+        self.walk(y).map(|a| a ^ y)
+    }
+
+    /// 要素 `a` に対する `a ^ x` の最大値
+    ///
+    /// **戻り値**
+    /// - 空の場合 `None`
+    pub fn max_xor(&self, x: u64) -> Option<u64> {
+        Self::check(x);
+        let y = x ^ self.mask;
+        let full = if BITS == 64 { !0 } else { (1 << BITS) - 1 };
+        self.walk(!y & full).map(|a| a ^ y)
+    }
+
+    /// 最小値
+    pub fn min(&self) -> Option<u64> {
+        self.min_xor(0)
+    }
+
+    /// 最大値
+    pub fn max(&self) -> Option<u64> {
+        self.max_xor(0)
+    }
+
+    /// 小さい方から `k` 番目（0-indexed）の要素
+    ///
+    /// **戻り値**
+    /// - 要素数が `k` 以下の場合 `None`
+    pub fn kth_smallest(&self, mut k: usize) -> Option<u64> {
+        if k >= self.len() {
+            return None;
+        }
+        let mut node = 0;
+        let mut res = 0;
+        for d in 0..BITS {
+            // 作用後のビットが 0 になる子
+            let b = Self::bit(self.mask, d);
+            let left = self.nodes[node].children[b];
+            let left_count = if left == 0 { 0 } else { self.nodes[left].count };
+            if k < left_count {
+                node = left;
+                res <<= 1;
+            } else {
+                k -= left_count;
+                node = self.nodes[node].children[b ^ 1];
+                res = res << 1 | 1;
+            }
+        }
+        Some(res)
+    }
+
+    /// 大きい方から `k` 番目（0-indexed）の要素
+    pub fn kth_largest(&self, k: usize) -> Option<u64> {
+        let n = self.len();
+        if k >= n {
+            return None;
+        }
+        self.kth_smallest(n - 1 - k)
+    }
+
+    /// `x` 未満の要素の個数
+    pub fn count_less(&self, x: u64) -> usize {
+        if BITS < 64 && x >> BITS != 0 {
+            return self.len();
+        }
+        let mut node = 0;
+        let mut res = 0;
+        for d in 0..BITS {
+            let m = Self::bit(self.mask, d);
+            let b = Self::bit(x, d);
+            if b == 1 {
+                // 作用後のビットが 0 になる子はすべて x 未満
+                let left = self.nodes[node].children[m];
+                if left != 0 {
+                    res += self.nodes[left].count;
+                }
+            }
+            node = self.nodes[node].children[b ^ m];
+            if node == 0 {
+                break;
+            }
+        }
+        res
+    }
+}
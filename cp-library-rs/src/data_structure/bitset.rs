@@ -2,11 +2,14 @@
 
 use std::{
     fmt::Debug,
-    ops::{Deref, DerefMut, Index},
+    ops::{
+        BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Bound::*, Deref, DerefMut,
+        Index, Not, RangeBounds, Shl, ShlAssign, Shr, ShrAssign,
+    },
 };
 
 /// ビット列を高速に処理する
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct BitSet<const SIZE: usize> {
     bits: Vec<u64>,
 }
@@ -58,8 +61,13 @@ impl<const SIZE: usize> BitSet<SIZE> {
         }
     }
 
-    /// すべてのbitが0になっているかを判定する
+    /// 1であるbitが存在するかを判定する
     pub fn any(&self) -> bool {
+        !self.none()
+    }
+
+    /// すべてのbitが0になっているかを判定する
+    pub fn none(&self) -> bool {
         self.bits.iter().all(|&b64| b64 == 0)
     }
 
@@ -84,6 +92,40 @@ impl<const SIZE: usize> BitSet<SIZE> {
         }
     }
 
+    /// 範囲 `range` のbitを1に設定
+    pub fn set_range<R: RangeBounds<usize> + Debug>(&mut self, range: R) {
+        let (l, r) = parse_range(SIZE, range);
+        fill_range(&mut self.bits, l, r, true);
+    }
+
+    /// 範囲 `range` のbitを0に設定
+    pub fn unset_range<R: RangeBounds<usize> + Debug>(&mut self, range: R) {
+        let (l, r) = parse_range(SIZE, range);
+        fill_range(&mut self.bits, l, r, false);
+    }
+
+    /// 1である最初のbitのインデックス
+    pub fn find_first(&self) -> Option<usize> {
+        find_from(&self.bits, 0)
+    }
+
+    /// `index` より後で1である最初のbitのインデックス
+    pub fn find_next(&self, index: usize) -> Option<usize> {
+        find_from(&self.bits, index.checked_add(1)?)
+    }
+
+    /// 1であるbitのインデックスを昇順に列挙する
+    pub fn iter_ones(&self) -> Ones<'_> {
+        Ones::new(&self.bits)
+    }
+
+    /// あまりのbitを0にする
+    fn mask(&mut self) {
+        if Self::REM_BIT != 0 {
+            self.bits[Self::ARRAY_SIZE - 1] &= (1 << Self::REM_BIT) - 1;
+        }
+    }
+
     /// 1であるビットの数を求める
     pub fn count_ones(&self) -> usize {
         self.bits
@@ -160,3 +202,217 @@ impl<const SIZE: usize> Debug for BitSet<SIZE> {
         write!(f, "BitSet {{ {:?} }}", bit_str)
     }
 }
+
+impl<const SIZE: usize> ShlAssign<usize> for BitSet<SIZE> {
+    fn shl_assign(&mut self, rhs: usize) {
+        shl_words(&mut self.bits, rhs);
+        self.mask();
+    }
+}
+
+impl<const SIZE: usize> ShrAssign<usize> for BitSet<SIZE> {
+    fn shr_assign(&mut self, rhs: usize) {
+        shr_words(&mut self.bits, rhs);
+    }
+}
+
+impl<const SIZE: usize> Not for BitSet<SIZE> {
+    type Output = Self;
+    fn not(mut self) -> Self::Output {
+        self.bits.iter_mut().for_each(|b64| *b64 = !*b64);
+        self.mask();
+        self
+    }
+}
+
+impl<const SIZE: usize> Not for &BitSet<SIZE> {
+    type Output = BitSet<SIZE>;
+    fn not(self) -> Self::Output {
+        !self.clone()
+    }
+}
+
+/// シフト演算を実装する
+macro_rules! impl_shift {
+    ($trait:ident, $fn:ident, $assign_fn:ident) => {
+        impl<const SIZE: usize> $trait<usize> for BitSet<SIZE> {
+            type Output = Self;
+            fn $fn(mut self, rhs: usize) -> Self::Output {
+                self.$assign_fn(rhs);
+                self
+            }
+        }
+
+        impl<const SIZE: usize> $trait<usize> for &BitSet<SIZE> {
+            type Output = BitSet<SIZE>;
+            fn $fn(self, rhs: usize) -> Self::Output {
+                self.clone().$fn(rhs)
+            }
+        }
+    };
+}
+
+impl_shift!(Shl, shl, shl_assign);
+impl_shift!(Shr, shr, shr_assign);
+
+/// ビットごとの演算を実装する
+macro_rules! impl_bitop {
+    ($trait:ident, $fn:ident, $assign_trait:ident, $assign_fn:ident, $op:tt) => {
+        impl<const SIZE: usize> $assign_trait<&BitSet<SIZE>> for BitSet<SIZE> {
+            fn $assign_fn(&mut self, rhs: &BitSet<SIZE>) {
+                for (a, b) in self.bits.iter_mut().zip(&rhs.bits) {
+                    *a = *a $op *b;
+                }
+            }
+        }
+
+        impl<const SIZE: usize> $assign_trait for BitSet<SIZE> {
+            fn $assign_fn(&mut self, rhs: BitSet<SIZE>) {
+                self.$assign_fn(&rhs);
+            }
+        }
+
+        impl<const SIZE: usize> $trait<&BitSet<SIZE>> for BitSet<SIZE> {
+            type Output = Self;
+            fn $fn(mut self, rhs: &BitSet<SIZE>) -> Self::Output {
+                self.$assign_fn(rhs);
+                self
+            }
+        }
+
+        impl<const SIZE: usize> $trait for BitSet<SIZE> {
+            type Output = Self;
+            fn $fn(mut self, rhs: BitSet<SIZE>) -> Self::Output {
+                self.$assign_fn(&rhs);
+                self
+            }
+        }
+
+        impl<const SIZE: usize> $trait for &BitSet<SIZE> {
+            type Output = BitSet<SIZE>;
+            fn $fn(self, rhs: &BitSet<SIZE>) -> Self::Output {
+                self.clone().$fn(rhs)
+            }
+        }
+    };
+}
+
+impl_bitop!(BitAnd, bitand, BitAndAssign, bitand_assign, &);
+impl_bitop!(BitOr, bitor, BitOrAssign, bitor_assign, |);
+impl_bitop!(BitXor, bitxor, BitXorAssign, bitxor_assign, ^);
+
+/// 区間を `[l, r)` の形に変換する
+pub(crate) fn parse_range<R: RangeBounds<usize> + Debug>(n: usize, range: R) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Unbounded => 0,
+        Excluded(&v) => v + 1,
+        Included(&v) => v,
+    };
+    let end = match range.end_bound() {
+        Unbounded => n,
+        Excluded(&v) => v,
+        Included(&v) => v + 1,
+    };
+    if start > end || end > n {
+        panic!("The given range is wrong: {:?}", range);
+    }
+    (start, end)
+}
+
+/// ビット列全体を `k` ビット上位にずらす（あふれたビットは捨てる）
+pub(crate) fn shl_words(bits: &mut [u64], k: usize) {
+    let (q, r) = (k / 64, k % 64);
+    for i in (0..bits.len()).rev() {
+        bits[i] = if i < q {
+            0
+        } else if r == 0 || i == q {
+            bits[i - q] << r
+        } else {
+            bits[i - q] << r | bits[i - q - 1] >> (64 - r)
+        };
+    }
+}
+
+/// ビット列全体を `k` ビット下位にずらす
+pub(crate) fn shr_words(bits: &mut [u64], k: usize) {
+    let (q, r) = (k / 64, k % 64);
+    let n = bits.len();
+    for i in 0..n {
+        bits[i] = if i + q >= n {
+            0
+        } else if r == 0 || i + q + 1 == n {
+            bits[i + q] >> r
+        } else {
+            bits[i + q] >> r | bits[i + q + 1] << (64 - r)
+        };
+    }
+}
+
+/// `[l, r)` のビットを `val` に設定する
+pub(crate) fn fill_range(bits: &mut [u64], l: usize, r: usize, val: bool) {
+    let mut i = l;
+    while i < r {
+        let (idx, lo) = (i / 64, i % 64);
+        let hi = (r - idx * 64).min(64);
+        let mask = if hi - lo == 64 {
+            !0
+        } else {
+            ((1 << (hi - lo)) - 1) << lo
+        };
+        if val {
+            bits[idx] |= mask;
+        } else {
+            bits[idx] &= !mask;
+        }
+        i = (idx + 1) * 64;
+    }
+}
+
+/// `index` 以降で1である最初のビット
+pub(crate) fn find_from(bits: &[u64], index: usize) -> Option<usize> {
+    let mut idx = index / 64;
+    if idx >= bits.len() {
+        return None;
+    }
+    let mut b64 = bits[idx] & (!0 << (index % 64));
+    loop {
+        if b64 != 0 {
+            return Some(idx * 64 + b64.trailing_zeros() as usize);
+        }
+        idx += 1;
+        b64 = *bits.get(idx)?;
+    }
+}
+
+/// 1であるビットを列挙するイテレータ
+pub struct Ones<'a> {
+    bits: &'a [u64],
+    /// 現在のワードの番号
+    idx: usize,
+    /// 現在のワードのうち未列挙のビット
+    cur: u64,
+}
+
+impl<'a> Ones<'a> {
+    pub(crate) fn new(bits: &'a [u64]) -> Self {
+        Self {
+            bits,
+            idx: 0,
+            cur: bits.first().copied().unwrap_or(0),
+        }
+    }
+}
+
+impl Iterator for Ones<'_> {
+    type Item = usize;
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.cur == 0 {
+            self.idx += 1;
+            self.cur = *self.bits.get(self.idx)?;
+        }
+        let res = self.idx * 64 + self.cur.trailing_zeros() as usize;
+        // 最下位の1を消す
+        self.cur &= self.cur - 1;
+        Some(res)
+    }
+}
@@ -0,0 +1,261 @@
+//! ## 実行時にサイズを決めるビット列
+//!
+//! 64bitごとにまとめて演算を行う．
+//!
+//! - シフト・ビット演算の時間計算量: $`O(N / 64)`$
+//!
+//! ```
+//! use cp_library_rs::data_structure::dynamic_bitset::*;
+//!
+//! // 部分和問題: {3, 5, 7} の部分和として作れる 10 以下の値
+//! let mut dp = DynamicBitSet::new(11);
+//! dp.set(0);
+//! for a in [3, 5, 7] {
+//!     dp |= &(&dp << a);
+//! }
+//! assert_eq!(dp.iter_ones().collect::<Vec<_>>(), vec![0, 3, 5, 7, 8, 10]);
+//! ```
+
+use std::{
+    fmt::Debug,
+    ops::{
+        BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Index, Not, RangeBounds,
+        Shl, ShlAssign, Shr, ShrAssign,
+    },
+};
+
+use crate::data_structure::bitset::{
+    fill_range, find_from, parse_range, shl_words, shr_words, Ones,
+};
+
+/// 実行時にサイズを決めるビット列
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct DynamicBitSet {
+    /// ビット数
+    size: usize,
+    /// ビット列（`size` 以上のビットは常に0）
+    bits: Vec<u64>,
+}
+
+impl DynamicBitSet {
+    /// 一時的な値
+    const TMP_BOOL: [bool; 2] = [false, true];
+
+    /// すべて0の長さ `size` のビット列を生成する
+    pub fn new(size: usize) -> Self {
+        Self {
+            size,
+            bits: vec![0; size.div_ceil(64)],
+        }
+    }
+
+    /// ビット数
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    /// ビット数が0であるか
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// `index` bit目を取得
+    pub fn get(&self, index: usize) -> bool {
+        assert!(index < self.size, "index out of range: {index}");
+        self.bits[index / 64] >> (index % 64) & 1 == 1
+    }
+
+    /// `index` bit目を1に設定
+    pub fn set(&mut self, index: usize) {
+        assert!(index < self.size, "index out of range: {index}");
+        self.bits[index / 64] |= 1 << (index % 64);
+    }
+
+    /// `index` bit目を0に設定
+    pub fn unset(&mut self, index: usize) {
+        assert!(index < self.size, "index out of range: {index}");
+        self.bits[index / 64] &= !(1 << (index % 64));
+    }
+
+    /// `index` bit目を反転
+    pub fn flip(&mut self, index: usize) {
+        assert!(index < self.size, "index out of range: {index}");
+        self.bits[index / 64] ^= 1 << (index % 64);
+    }
+
+    /// 範囲 `range` のbitを1に設定
+    pub fn set_range<R: RangeBounds<usize> + Debug>(&mut self, range: R) {
+        let (l, r) = parse_range(self.size, range);
+        fill_range(&mut self.bits, l, r, true);
+    }
+
+    /// 範囲 `range` のbitを0に設定
+    pub fn unset_range<R: RangeBounds<usize> + Debug>(&mut self, range: R) {
+        let (l, r) = parse_range(self.size, range);
+        fill_range(&mut self.bits, l, r, false);
+    }
+
+    /// 1であるビットの数を求める
+    pub fn count_ones(&self) -> usize {
+        self.bits.iter().map(|b64| b64.count_ones() as usize).sum()
+    }
+
+    /// 1であるbitが存在するかを判定する
+    pub fn any(&self) -> bool {
+        self.bits.iter().any(|&b64| b64 != 0)
+    }
+
+    /// すべてのbitが0であるかを判定する
+    pub fn none(&self) -> bool {
+        !self.any()
+    }
+
+    /// すべてのbitが1であるかを判定する
+    pub fn all(&self) -> bool {
+        self.count_ones() == self.size
+    }
+
+    /// 1である最初のbitのインデックス
+    pub fn find_first(&self) -> Option<usize> {
+        find_from(&self.bits, 0)
+    }
+
+    /// `index` より後で1である最初のbitのインデックス
+    pub fn find_next(&self, index: usize) -> Option<usize> {
+        find_from(&self.bits, index.checked_add(1)?)
+    }
+
+    /// 1であるbitのインデックスを昇順に列挙する
+    pub fn iter_ones(&self) -> Ones<'_> {
+        Ones::new(&self.bits)
+    }
+
+    /// 64bitごとのブロック（下位から）
+    pub fn as_words(&self) -> &[u64] {
+        &self.bits
+    }
+
+    /// あまりのbitを0にする
+    fn mask(&mut self) {
+        if self.size % 64 != 0 {
+            *self.bits.last_mut().unwrap() &= (1 << (self.size % 64)) - 1;
+        }
+    }
+}
+
+impl Index<usize> for DynamicBitSet {
+    type Output = bool;
+    fn index(&self, index: usize) -> &Self::Output {
+        &Self::TMP_BOOL[self.get(index) as usize]
+    }
+}
+
+impl Debug for DynamicBitSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // 上位のbitから順に表示する
+        let bit_str: String = (0..self.size)
+            .rev()
+            .map(|i| if self.get(i) { '1' } else { '0' })
+            .collect();
+        write!(f, "DynamicBitSet {{ {:?} }}", bit_str)
+    }
+}
+
+impl ShlAssign<usize> for DynamicBitSet {
+    fn shl_assign(&mut self, rhs: usize) {
+        shl_words(&mut self.bits, rhs);
+        self.mask();
+    }
+}
+
+impl ShrAssign<usize> for DynamicBitSet {
+    fn shr_assign(&mut self, rhs: usize) {
+        shr_words(&mut self.bits, rhs);
+    }
+}
+
+impl Not for DynamicBitSet {
+    type Output = Self;
+    fn not(mut self) -> Self::Output {
+        self.bits.iter_mut().for_each(|b64| *b64 = !*b64);
+        self.mask();
+        self
+    }
+}
+
+impl Not for &DynamicBitSet {
+    type Output = DynamicBitSet;
+    fn not(self) -> Self::Output {
+        !self.clone()
+    }
+}
+
+/// シフト演算を実装する
+macro_rules! impl_shift {
+    ($trait:ident, $fn:ident, $assign_fn:ident) => {
+        impl $trait<usize> for DynamicBitSet {
+            type Output = Self;
+            fn $fn(mut self, rhs: usize) -> Self::Output {
+                self.$assign_fn(rhs);
+                self
+            }
+        }
+
+        impl $trait<usize> for &DynamicBitSet {
+            type Output = DynamicBitSet;
+            fn $fn(self, rhs: usize) -> Self::Output {
+                self.clone().$fn(rhs)
+            }
+        }
+    };
+}
+
+impl_shift!(Shl, shl, shl_assign);
+impl_shift!(Shr, shr, shr_assign);
+
+/// ビットごとの演算を実装する
+macro_rules! impl_bitop {
+    ($trait:ident, $fn:ident, $assign_trait:ident, $assign_fn:ident, $op:tt) => {
+        impl $assign_trait<&DynamicBitSet> for DynamicBitSet {
+            fn $assign_fn(&mut self, rhs: &DynamicBitSet) {
+                assert_eq!(self.size, rhs.size, "sizes of bitsets differ");
+                for (a, b) in self.bits.iter_mut().zip(&rhs.bits) {
+                    *a = *a $op *b;
+                }
+            }
+        }
+
+        impl $assign_trait for DynamicBitSet {
+            fn $assign_fn(&mut self, rhs: DynamicBitSet) {
+                self.$assign_fn(&rhs);
+            }
+        }
+
+        impl $trait<&DynamicBitSet> for DynamicBitSet {
+            type Output = Self;
+            fn $fn(mut self, rhs: &DynamicBitSet) -> Self::Output {
+                self.$assign_fn(rhs);
+                self
+            }
+        }
+
+        impl $trait for DynamicBitSet {
+            type Output = Self;
+            fn $fn(mut self, rhs: DynamicBitSet) -> Self::Output {
+                self.$assign_fn(&rhs);
+                self
+            }
+        }
+
+        impl $trait for &DynamicBitSet {
+            type Output = DynamicBitSet;
+            fn $fn(self, rhs: &DynamicBitSet) -> Self::Output {
+                self.clone().$fn(rhs)
+            }
+        }
+    };
+}
+
+impl_bitop!(BitAnd, bitand, BitAndAssign, bitand_assign, &);
+impl_bitop!(BitOr, bitor, BitOrAssign, bitor_assign, |);
+impl_bitop!(BitXor, bitxor, BitXorAssign, bitxor_assign, ^);
@@ -0,0 +1,186 @@
+//! ## 区間を管理するデータ構造（ODT）
+//!
+//! 互いに交わらない半開区間 $`[l, r)`$ ごとに値を持つ．
+//! 区間代入を行うと，値が等しく隣接する区間は併合される．
+//!
+//! - `assign` の時間計算量: 償却 $`O(\log N)`$（削除される区間の個数を除く）
+//!
+//! ```
+//! use cp_library_rs::data_structure::interval_map::*;
+//!
+//! let mut map = IntervalMap::new();
+//! // 値の総和を管理する
+//! let mut sum = 0;
+//!
+//! for (range, v) in [(0..10, 1), (3..6, 2), (5..8, 1)] {
+//!     sum += (range.end - range.start) * v;
+//!     map.assign_with(range, v, |l, r, v| sum -= (r - l) * v);
+//! }
+//!
+//! // [0, 3): 1, [3, 5): 2, [5, 10): 1
+//! assert_eq!(map.len(), 3);
+//! assert_eq!(map.get(&4), Some(&2));
+//! assert_eq!(map.get(&10), None);
+//! assert_eq!(sum, 12);
+//!
+//! let pieces: Vec<_> = map.range(4..6).collect();
+//! assert_eq!(pieces, vec![(&3, &5, &2), (&5, &10, &1)]);
+//! ```
+
+use std::{collections::BTreeMap, ops::Range};
+
+use num_traits::PrimInt;
+
+/// 区間ごとに値を持つ連想配列
+#[derive(Debug, Clone)]
+pub struct IntervalMap<K, V> {
+    /// `l` → `(r, 値)`
+    map: BTreeMap<K, (K, V)>,
+}
+
+/// 区間の集合
+pub type IntervalSet<K> = IntervalMap<K, ()>;
+
+impl<K: Ord + Clone, V: Eq + Clone> Default for IntervalMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord + Clone, V: Eq + Clone> IntervalMap<K, V> {
+    /// 空の `IntervalMap` を生成する
+    pub fn new() -> Self {
+        Self {
+            map: BTreeMap::new(),
+        }
+    }
+
+    /// 区間の個数
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// 空であるか
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// `k` を含む区間 `(l, r, 値)`
+    pub fn get_piece(&self, k: &K) -> Option<(&K, &K, &V)> {
+        self.map
+            .range(..=k)
+            .next_back()
+            .filter(|(_, (r, _))| k < r)
+            .map(|(l, (r, v))| (l, r, v))
+    }
+
+    /// `k` における値
+    pub fn get(&self, k: &K) -> Option<&V> {
+        self.get_piece(k).map(|(_, _, v)| v)
+    }
+
+    /// `k` を含む区間を `k` の前後で分割する
+    fn split(&mut self, k: &K) {
+        let Some((_, (r, v))) = self.map.range_mut(..k).next_back() else {
+            return;
+        };
+        if k < r {
+            let right = (r.clone(), v.clone());
+            *r = k.clone();
+            self.map.insert(k.clone(), right);
+        }
+    }
+
+    /// 区間 `[l, r)` を削除し，削除した区間 `(l, r, 値)` に対して `f` を呼ぶ
+    fn remove_range<F: FnMut(K, K, V)>(&mut self, l: &K, r: &K, mut f: F) {
+        self.split(l);
+        self.split(r);
+        let keys: Vec<K> = self.map.range(l..r).map(|(k, _)| k.clone()).collect();
+        for k in keys {
+            let (r, v) = self.map.remove(&k).unwrap();
+            f(k, r, v);
+        }
+    }
+
+    /// 区間 `range` の値を `v` にする
+    pub fn assign(&mut self, range: Range<K>, v: V) {
+        self.assign_with(range, v, |_, _, _| {});
+    }
+
+    /// 区間 `range` の値を `v` にする
+    ///
+    /// - 上書きされる各区間 `(l, r, 値)` に対して `f` を呼ぶ
+    pub fn assign_with<F: FnMut(K, K, V)>(&mut self, range: Range<K>, v: V, f: F) {
+        let Range {
+            start: mut l,
+            end: mut r,
+        } = range;
+        if l >= r {
+            return;
+        }
+        self.remove_range(&l, &r, f);
+
+        // 左側と併合
+        if let Some((pl, (pr, pv))) = self.map.range(..&l).next_back() {
+            if *pr == l && *pv == v {
+                l = pl.clone();
+                self.map.remove(&l);
+            }
+        }
+        // 右側と併合
+        if let Some((nr, nv)) = self.map.get(&r) {
+            if *nv == v {
+                let nr = nr.clone();
+                self.map.remove(&r);
+                r = nr;
+            }
+        }
+        self.map.insert(l, (r, v));
+    }
+
+    /// 区間 `range` の値を削除する
+    pub fn erase(&mut self, range: Range<K>) {
+        self.erase_with(range, |_, _, _| {});
+    }
+
+    /// 区間 `range` の値を削除する
+    ///
+    /// - 削除される各区間 `(l, r, 値)` に対して `f` を呼ぶ
+    pub fn erase_with<F: FnMut(K, K, V)>(&mut self, range: Range<K>, f: F) {
+        if range.start < range.end {
+            self.remove_range(&range.start, &range.end, f);
+        }
+    }
+
+    /// すべての区間 `(l, r, 値)` を昇順に列挙する
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &K, &V)> {
+        self.map.iter().map(|(l, (r, v))| (l, r, v))
+    }
+
+    /// 区間 `range` と交わる区間 `(l, r, 値)` を昇順に列挙する
+    ///
+    /// - 区間は `range` で切り取らずに返す
+    pub fn range(&self, range: Range<K>) -> impl Iterator<Item = (&K, &K, &V)> {
+        let Range { start: l, end: r } = range;
+        let r = r.max(l.clone());
+        let first = self
+            .map
+            .range(..&l)
+            .next_back()
+            .filter(|(_, (pr, _))| l < *pr && l < r);
+        first
+            .into_iter()
+            .chain(self.map.range(l..r))
+            .map(|(l, (r, v))| (l, r, v))
+    }
+}
+
+impl<K: PrimInt> IntervalMap<K, ()> {
+    /// 区間 `range` のうち，いずれかの区間に含まれる部分の長さ
+    pub fn covered_len(&self, range: Range<K>) -> K {
+        let (l, r) = (range.start, range.end);
+        self.range(range)
+            .map(|(&pl, &pr, _)| pr.min(r) - pl.max(l))
+            .fold(K::zero(), |acc, x| acc + x)
+    }
+}
@@ -0,0 +1,536 @@
+//! ## Li Chao Tree
+//!
+//! 直線 $`y = ax + b`$（線分）の追加と，点 $`x`$ での最小値（最大値）の取得を $`O(\log N)`$ で行う．
+//!
+//! - [`LiChaoTree`] ： クエリの $`x`$ 座標を先に与える
+//! - [`DynamicLiChaoTree`] ： 整数の $`x`$ 座標の範囲だけを与え，必要なノードだけ作る
+//! - [`MonotoneCHT`] ： 傾きが単調な場合の Convex Hull Trick
+//!
+//! 最小値か最大値かは [`Min`](crate::algebraic_structure::operation::Min)，
+//! [`Max`](crate::algebraic_structure::operation::Max) で指定する．
+//! 直線がない場合の値は [`Monoid::e`] になる．
+
+use std::{
+    collections::VecDeque,
+    fmt::Debug,
+    ops::{
+        Add,
+        Bound::{Excluded, Included, Unbounded},
+        Mul, RangeBounds, Sub,
+    },
+};
+
+use num_traits::PrimInt;
+
+use crate::{
+    algebraic_structure::monoid::Monoid,
+    tree::arena::{Arena, ArenaNode, Ptr},
+    utils::ord_float::OrdF64,
+};
+
+/// 直線の係数・値として使える型
+pub trait LineValue:
+    Copy + Ord + Debug + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    /// 整数の座標を変換する
+    fn from_key<I: PrimInt>(x: I) -> Self;
+}
+
+impl LineValue for i64 {
+    fn from_key<I: PrimInt>(x: I) -> Self {
+        x.to_i64().unwrap()
+    }
+}
+
+impl LineValue for i128 {
+    fn from_key<I: PrimInt>(x: I) -> Self {
+        x.to_i128().unwrap()
+    }
+}
+
+impl LineValue for OrdF64 {
+    fn from_key<I: PrimInt>(x: I) -> Self {
+        OrdF64(x.to_f64().unwrap())
+    }
+}
+
+/// 直線 $`y = ax + b`$ を `(a, b)` で表す
+pub type Line<T> = (T, T);
+
+#[inline]
+fn eval<T: LineValue>(&(a, b): &Line<T>, x: T) -> T {
+    a * x + b
+}
+
+/// `x` が `y` より真に良い（`S::op` で選ばれる）か
+#[inline]
+fn better<S: Monoid>(x: &S::Val, y: &S::Val) -> bool
+where
+    S::Val: Eq,
+{
+    x != y && S::op(x, y) == *x
+}
+
+// ========== static ==========
+
+/// Li Chao Tree（クエリの座標を先に与える）
+///
+/// ```
+/// use cp_library_rs::{algebraic_structure::operation::Min, data_structure::li_chao_tree::*};
+///
+/// let mut lct = LiChaoTree::<Min<i64>>::new(vec![-2, 0, 1, 3]);
+/// lct.add_line((1, 0));
+/// lct.add_line((-1, 1));
+/// assert_eq!(lct.query(-2), -2);
+/// assert_eq!(lct.query(3), -2);
+///
+/// // x ∈ [0, 1] のみ
+/// lct.add_segment((0, -5), 0..=1);
+/// assert_eq!(lct.query(1), -5);
+/// assert_eq!(lct.query(3), -2);
+/// ```
+#[derive(Debug)]
+pub struct LiChaoTree<S: Monoid>
+where
+    S::Val: LineValue,
+{
+    /// クエリの座標（昇順）
+    xs: Vec<S::Val>,
+    /// 葉の数（2べき）
+    size: usize,
+    lines: Vec<Option<Line<S::Val>>>,
+}
+
+impl<S: Monoid> LiChaoTree<S>
+where
+    S::Val: LineValue,
+{
+    /// クエリの座標 `xs` から構築する
+    pub fn new(mut xs: Vec<S::Val>) -> Self {
+        xs.sort();
+        xs.dedup();
+        let size = xs.len().next_power_of_two();
+        Self {
+            xs,
+            size,
+            lines: vec![None; 2 * size],
+        }
+    }
+
+    /// `i` 番目の座標（範囲外の葉は末尾の座標とみなす）
+    #[inline]
+    fn x(&self, i: usize) -> S::Val {
+        self.xs[i.min(self.xs.len() - 1)]
+    }
+
+    /// ノード `k`（座標の添字 `l..r` を担当）以下に直線を追加する
+    fn insert(&mut self, mut k: usize, mut l: usize, mut r: usize, mut line: Line<S::Val>) {
+        loop {
+            let Some(cur) = self.lines[k] else {
+                self.lines[k] = Some(line);
+                return;
+            };
+            let mid = (l + r) / 2;
+            let (xl, xm, xr) = (self.x(l), self.x(mid), self.x(r - 1));
+            // 中央で良い方をノードに残す
+            let mut cur = cur;
+            if better::<S>(&eval(&line, xm), &eval(&cur, xm)) {
+                self.lines[k] = Some(line);
+                (line, cur) = (cur, line);
+            }
+            if r - l == 1 {
+                return;
+            }
+            if better::<S>(&eval(&line, xl), &eval(&cur, xl)) {
+                (k, r) = (2 * k, mid);
+            } else if better::<S>(&eval(&line, xr), &eval(&cur, xr)) {
+                (k, l) = (2 * k + 1, mid);
+            } else {
+                return;
+            }
+        }
+    }
+
+    /// 直線 `line = (a, b)` を追加する
+    /// - 時間計算量: $`O(\log N)`$
+    pub fn add_line(&mut self, line: Line<S::Val>) {
+        if self.xs.is_empty() {
+            return;
+        }
+        self.insert(1, 0, self.size, line);
+    }
+
+    /// `range` の範囲でのみ有効な線分 `line = (a, b)` を追加する
+    /// - 時間計算量: $`O(\log^2 N)`$
+    pub fn add_segment<R: RangeBounds<S::Val>>(&mut self, line: Line<S::Val>, range: R) {
+        let l = match range.start_bound() {
+            Unbounded => 0,
+            Included(v) => self.xs.partition_point(|x| x < v),
+            Excluded(v) => self.xs.partition_point(|x| x <= v),
+        };
+        let r = match range.end_bound() {
+            Unbounded => self.xs.len(),
+            Included(v) => self.xs.partition_point(|x| x <= v),
+            Excluded(v) => self.xs.partition_point(|x| x < v),
+        };
+        if l < r {
+            self.insert_range(1, 0, self.size, l, r, line);
+        }
+    }
+
+    fn insert_range(
+        &mut self,
+        k: usize,
+        nl: usize,
+        nr: usize,
+        l: usize,
+        r: usize,
+        line: Line<S::Val>,
+    ) {
+        if r <= nl || nr <= l {
+            return;
+        }
+        if l <= nl && nr <= r {
+            self.insert(k, nl, nr, line);
+            return;
+        }
+        let mid = (nl + nr) / 2;
+        self.insert_range(2 * k, nl, mid, l, r, line);
+        self.insert_range(2 * k + 1, mid, nr, l, r, line);
+    }
+
+    /// 座標 `x` での最小値（最大値）
+    /// - 時間計算量: $`O(\log N)`$
+    ///
+    /// `x` が構築時に与えた座標にない場合は panic する
+    pub fn query(&self, x: S::Val) -> S::Val {
+        let i = self
+            .xs
+            .binary_search(&x)
+            .unwrap_or_else(|_| panic!("{:?} is not a query point", x));
+        let mut k = i + self.size;
+        let mut res = S::e();
+        while k > 0 {
+            if let Some(line) = &self.lines[k] {
+                res = S::op(&res, &eval(line, x));
+            }
+            k >>= 1;
+        }
+        res
+    }
+}
+
+// ========== dynamic ==========
+
+struct Node<T> {
+    line: Option<Line<T>>,
+    left: Option<Ptr>,
+    right: Option<Ptr>,
+}
+
+impl<T> ArenaNode for Node<T> {}
+
+impl<T> Node<T> {
+    fn new() -> Self {
+        Self {
+            line: None,
+            left: None,
+            right: None,
+        }
+    }
+}
+
+/// Li Chao Tree（整数座標 `[min, max)` 上，必要なノードだけ作る）
+///
+/// ```
+/// use cp_library_rs::{algebraic_structure::operation::Max, data_structure::li_chao_tree::*};
+///
+/// let mut lct = DynamicLiChaoTree::<i32, Max<i64>>::new(-1_000_000_000, 1_000_000_000);
+/// assert_eq!(lct.query(0), i64::MIN);
+/// lct.add_line((2, 0));
+/// lct.add_segment((0, 10), 0..5);
+/// assert_eq!(lct.query(4), 10);
+/// assert_eq!(lct.query(5), 10);
+/// assert_eq!(lct.query(6), 12);
+/// ```
+pub struct DynamicLiChaoTree<I: PrimInt, S: Monoid>
+where
+    S::Val: LineValue,
+{
+    min_index: I,
+    max_index: I,
+    arena: Arena<Node<S::Val>>,
+    root: Ptr,
+}
+
+impl<I: PrimInt + Debug, S: Monoid> DynamicLiChaoTree<I, S>
+where
+    S::Val: LineValue,
+{
+    /// 座標の範囲 `[min, max)` で初期化する
+    pub fn new(min: I, max: I) -> Self {
+        assert!(min < max);
+        let mut arena = Arena::new();
+        let root = arena.alloc(Node::new());
+        Self {
+            min_index: min,
+            max_index: max,
+            arena,
+            root,
+        }
+    }
+
+    /// $`\lfloor (l + r) / 2 \rfloor`$（`l + r` や `r - l` を経由せず，オーバーフローしない）
+    #[inline]
+    fn mid(l: I, r: I) -> I {
+        (l >> 1) + (r >> 1) + (l & r & I::one())
+    }
+
+    /// 子ノード（なければ作る）
+    fn child(&mut self, ptr: Ptr, is_left: bool) -> Ptr {
+        let node = self.arena.get(ptr);
+        let child = if is_left { node.left } else { node.right };
+        if let Some(c) = child {
+            return c;
+        }
+        let c = self.arena.alloc(Node::new());
+        let node = self.arena.get_mut(ptr);
+        if is_left {
+            node.left = Some(c);
+        } else {
+            node.right = Some(c);
+        }
+        c
+    }
+
+    fn insert(&mut self, mut ptr: Ptr, mut l: I, mut r: I, mut line: Line<S::Val>) {
+        loop {
+            let Some(cur) = self.arena.get(ptr).line else {
+                self.arena.get_mut(ptr).line = Some(line);
+                return;
+            };
+            let mid = Self::mid(l, r);
+            let (xl, xm, xr) = (
+                S::Val::from_key(l),
+                S::Val::from_key(mid),
+                S::Val::from_key(r - I::one()),
+            );
+            let mut cur = cur;
+            if better::<S>(&eval(&line, xm), &eval(&cur, xm)) {
+                self.arena.get_mut(ptr).line = Some(line);
+                (line, cur) = (cur, line);
+            }
+            if l + I::one() == r {
+                return;
+            }
+            if better::<S>(&eval(&line, xl), &eval(&cur, xl)) {
+                (ptr, r) = (self.child(ptr, true), mid);
+            } else if better::<S>(&eval(&line, xr), &eval(&cur, xr)) {
+                (ptr, l) = (self.child(ptr, false), mid);
+            } else {
+                return;
+            }
+        }
+    }
+
+    /// 直線 `line = (a, b)` を追加する
+    /// - 時間計算量: $`O(\log (\max - \min))`$
+    pub fn add_line(&mut self, line: Line<S::Val>) {
+        self.insert(self.root, self.min_index, self.max_index, line);
+    }
+
+    /// `range` の範囲でのみ有効な線分 `line = (a, b)` を追加する
+    /// - 時間計算量: $`O(\log^2 (\max - \min))`$
+    pub fn add_segment<R: RangeBounds<I> + Debug>(&mut self, line: Line<S::Val>, range: R) {
+        let l = match range.start_bound() {
+            Unbounded => self.min_index,
+            Included(&v) => v,
+            Excluded(&v) => v + I::one(),
+        };
+        let r = match range.end_bound() {
+            Unbounded => self.max_index,
+            Included(&v) => v + I::one(),
+            Excluded(&v) => v,
+        };
+        assert!(
+            self.min_index <= l && r <= self.max_index,
+            "The given range is wrong: {:?}",
+            range
+        );
+        if l < r {
+            self.insert_range(self.root, self.min_index, self.max_index, l, r, line);
+        }
+    }
+
+    fn insert_range(&mut self, ptr: Ptr, nl: I, nr: I, l: I, r: I, line: Line<S::Val>) {
+        if r <= nl || nr <= l {
+            return;
+        }
+        if l <= nl && nr <= r {
+            self.insert(ptr, nl, nr, line);
+            return;
+        }
+        let mid = Self::mid(nl, nr);
+        if l < mid {
+            let left = self.child(ptr, true);
+            self.insert_range(left, nl, mid, l, r, line);
+        }
+        if mid < r {
+            let right = self.child(ptr, false);
+            self.insert_range(right, mid, nr, l, r, line);
+        }
+    }
+
+    /// 座標 `x` での最小値（最大値）
+    /// - 時間計算量: $`O(\log (\max - \min))`$
+    pub fn query(&self, x: I) -> S::Val {
+        assert!(
+            self.min_index <= x && x < self.max_index,
+            "index out of range: {:?}",
+            x
+        );
+        let xv = S::Val::from_key(x);
+        let (mut l, mut r) = (self.min_index, self.max_index);
+        let mut ptr = Some(self.root);
+        let mut res = S::e();
+        while let Some(p) = ptr {
+            let node = self.arena.get(p);
+            if let Some(line) = &node.line {
+                res = S::op(&res, &eval(line, xv));
+            }
+            let mid = Self::mid(l, r);
+            if x < mid {
+                (ptr, r) = (node.left, mid);
+            } else {
+                (ptr, l) = (node.right, mid);
+            }
+        }
+        res
+    }
+}
+
+// ========== convex hull trick ==========
+
+/// 傾きが単調な場合の Convex Hull Trick
+///
+/// - 直線は，最小値なら傾きの降順，最大値なら傾きの昇順に追加する
+/// - 直線の追加は償却 $`O(1)`$，クエリは $`O(\log N)`$（`x` が昇順なら [`MonotoneCHT::query_monotone`] で償却 $`O(1)`$）
+///
+/// ```
+/// use cp_library_rs::{algebraic_structure::operation::Min, data_structure::li_chao_tree::*};
+///
+/// let mut cht = MonotoneCHT::<Min<i64>>::new();
+/// cht.add_line((2, 0));
+/// cht.add_line((0, 1));
+/// cht.add_line((-1, 5));
+/// assert_eq!(cht.query(-1), -2);
+/// assert_eq!(cht.query(1), 1);
+/// assert_eq!(cht.query(10), -5);
+/// ```
+#[derive(Debug)]
+pub struct MonotoneCHT<S: Monoid>
+where
+    S::Val: LineValue,
+{
+    lines: VecDeque<Line<S::Val>>,
+}
+
+impl<S: Monoid> Default for MonotoneCHT<S>
+where
+    S::Val: LineValue,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Monoid> MonotoneCHT<S>
+where
+    S::Val: LineValue,
+{
+    pub fn new() -> Self {
+        Self {
+            lines: VecDeque::new(),
+        }
+    }
+
+    /// `l2` が `l1` と `l3` の間で不要か
+    fn is_unnecessary(
+        &(a1, b1): &Line<S::Val>,
+        &(a2, b2): &Line<S::Val>,
+        &(a3, b3): &Line<S::Val>,
+    ) -> bool {
+        // l1, l2 の交点が l2, l3 の交点以上
+        (b3 - b2) * (a1 - a2) <= (b2 - b1) * (a2 - a3)
+    }
+
+    /// 直線 `line = (a, b)` を追加する
+    ///
+    /// 傾きが単調でない場合は panic する
+    pub fn add_line(&mut self, line: Line<S::Val>) {
+        let (a, b) = line;
+        if let Some(&(la, lb)) = self.lines.back() {
+            assert!(S::op(&a, &la) == a, "slopes must be monotone");
+            if a == la {
+                if !better::<S>(&b, &lb) {
+                    return;
+                }
+                self.lines.pop_back();
+            }
+        }
+        while self.lines.len() >= 2 {
+            let n = self.lines.len();
+            if Self::is_unnecessary(&self.lines[n - 2], &self.lines[n - 1], &line) {
+                self.lines.pop_back();
+            } else {
+                break;
+            }
+        }
+        self.lines.push_back(line);
+    }
+
+    /// 座標 `x` での最小値（最大値）
+    /// - 時間計算量: $`O(\log N)`$
+    pub fn query(&self, x: S::Val) -> S::Val {
+        if self.lines.is_empty() {
+            return S::e();
+        }
+        // 最適な直線までは，次の直線の方が良い
+        let (mut ok, mut ng) = (0, self.lines.len());
+        while ng - ok > 1 {
+            let mid = (ok + ng) / 2;
+            let (prev, cur) = (eval(&self.lines[mid - 1], x), eval(&self.lines[mid], x));
+            if S::op(&cur, &prev) == cur {
+                ok = mid;
+            } else {
+                ng = mid;
+            }
+        }
+        eval(&self.lines[ok], x)
+    }
+
+    /// 座標 `x` での最小値（最大値）
+    /// - 時間計算量: 償却 $`O(1)`$
+    ///
+    /// `x` は昇順に与える（それより前の座標でしか最適でない直線は削除される）
+    pub fn query_monotone(&mut self, x: S::Val) -> S::Val {
+        while self.lines.len() >= 2 {
+            let (cur, next) = (eval(&self.lines[0], x), eval(&self.lines[1], x));
+            if S::op(&next, &cur) == next {
+                self.lines.pop_front();
+            } else {
+                break;
+            }
+        }
+        self.lines.front().map_or_else(S::e, |line| eval(line, x))
+    }
+
+    /// 直線の数
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+}
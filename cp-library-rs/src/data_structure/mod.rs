@@ -5,25 +5,32 @@ pub mod acc2d_cyclic;
 pub mod acc3d;
 pub mod bbt_aa;
 pub mod bbt_treap;
+pub mod binary_trie;
 pub mod bit;
 pub mod bit_2d;
 pub mod bitset;
 pub mod cartesian_tree;
 pub mod dual_segment_tree;
+pub mod dynamic_bitset;
 pub mod dynamic_segment_tree;
 pub mod dynamic_segment_tree_2d;
 pub mod implicit_treap;
 pub mod indexedset;
+pub mod interval_map;
 pub mod lazy_segment_tree;
+pub mod li_chao_tree;
 pub mod mex_set;
 pub mod multiset;
 pub mod multiset_splay_tree;
+pub mod persistent_segment_tree;
 pub mod rollback_unionfind;
 pub mod segment_tree;
 pub mod segment_tree_2d;
+pub mod segment_tree_beats;
 pub mod segment_tree_ctx;
 pub mod segmented_sieve;
 pub mod sparse_table;
 pub mod trie;
 pub mod union_find;
+pub mod wavelet_matrix;
 pub mod weighted_union_find;
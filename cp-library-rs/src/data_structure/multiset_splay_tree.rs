@@ -1,5 +1,6 @@
 //! スプレー木の多重集合
 
+pub use crate::tree::splay_tree::pointer::NodeOps;
 pub use multiset_splay_tree_::MultiSet;
 
 mod multiset_splay_tree_ {
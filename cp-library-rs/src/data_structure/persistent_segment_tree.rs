@@ -0,0 +1,411 @@
+//! ## 永続セグメント木
+//!
+//! モノイド（[`Monoid`]）の列 $`A`$ に対し，
+//!
+//! - 要素の更新 ： 更新後の列を新しいバージョンとして作る
+//! - 区間積の取得 ： 任意のバージョンの $`A[l] \circ \cdots \circ A[r-1]`$
+//!
+//! をそれぞれ $`O(\log N)`$ で行う．バージョンは根のポインタ（[`Ptr`]）で表す．
+
+use std::{
+    fmt::Debug,
+    ops::{
+        Bound::{Excluded, Included, Unbounded},
+        RangeBounds,
+    },
+};
+
+use crate::{
+    algebraic_structure::{monoid::Monoid, operation::Add},
+    tree::arena::{Arena, ArenaNode, Ptr},
+};
+
+/// ノード
+struct Node<M: Monoid> {
+    val: M::Val,
+    left: Ptr,
+    right: Ptr,
+}
+
+impl<M: Monoid> ArenaNode for Node<M> {}
+
+/// すべての要素が単位元である木（子は自分自身を指す）
+const EMPTY: Ptr = Ptr(0);
+
+/// 永続セグメント木
+///
+/// ```
+/// use cp_library_rs::{
+///     algebraic_structure::operation::Add,
+///     data_structure::persistent_segment_tree::PersistentSegmentTree,
+/// };
+///
+/// let mut seg = PersistentSegmentTree::<Add<i64>>::from_vec(vec![1, 2, 3]);
+/// let v0 = seg.initial();
+/// let v1 = seg.update(v0, 0, 10);
+///
+/// assert_eq!(seg.get_range(v0, ..), 6);
+/// assert_eq!(seg.get_range(v1, ..), 15);
+/// ```
+pub struct PersistentSegmentTree<M: Monoid> {
+    /// 要素数
+    pub N: usize,
+    /// 葉の数（2べき）
+    size: usize,
+    arena: Arena<Node<M>>,
+    /// 初期状態のバージョン
+    initial: Ptr,
+}
+
+impl<M: Monoid> PersistentSegmentTree<M> {
+    /// すべての要素が単位元の永続セグメント木を生成する
+    /// - 時間計算量: $`O(1)`$
+    pub fn new(N: usize) -> Self {
+        let mut arena = Arena::new();
+        arena.alloc(Node {
+            val: M::e(),
+            left: EMPTY,
+            right: EMPTY,
+        });
+        Self {
+            N,
+            size: N.next_power_of_two(),
+            arena,
+            initial: EMPTY,
+        }
+    }
+
+    /// 配列から初期化する
+    /// - 時間計算量: $`O(N)`$
+    pub fn from_vec(src: Vec<M::Val>) -> Self {
+        let mut seg = Self::new(src.len());
+        seg.initial = seg.build(&src, 0, seg.size);
+        seg
+    }
+
+    fn build(&mut self, src: &[M::Val], l: usize, r: usize) -> Ptr {
+        if l >= src.len() {
+            return EMPTY;
+        }
+        if r - l == 1 {
+            return self.alloc_leaf(src[l].clone());
+        }
+        let mid = (l + r) / 2;
+        let left = self.build(src, l, mid);
+        let right = self.build(src, mid, r);
+        self.alloc_node(left, right)
+    }
+
+    fn alloc_leaf(&mut self, val: M::Val) -> Ptr {
+        self.arena.alloc(Node {
+            val,
+            left: EMPTY,
+            right: EMPTY,
+        })
+    }
+
+    fn alloc_node(&mut self, left: Ptr, right: Ptr) -> Ptr {
+        let val = M::op(&self.arena.get(left).val, &self.arena.get(right).val);
+        self.arena.alloc(Node { val, left, right })
+    }
+
+    #[inline]
+    fn parse_range<R: RangeBounds<usize>>(&self, range: &R) -> Option<(usize, usize)> {
+        let start = match range.start_bound() {
+            Unbounded => 0,
+            Excluded(&v) => v + 1,
+            Included(&v) => v,
+        };
+        let end = match range.end_bound() {
+            Unbounded => self.N,
+            Excluded(&v) => v,
+            Included(&v) => v + 1,
+        };
+        if start <= end && end <= self.N {
+            Some((start, end))
+        } else {
+            None
+        }
+    }
+
+    /// 初期状態のバージョン
+    pub fn initial(&self) -> Ptr {
+        self.initial
+    }
+
+    /// バージョン `version` の `index` 番目の要素を `value` に更新したバージョンを返す
+    /// - 時間計算量: $`O(\log N)`$（ノードを $`O(\log N)`$ 個確保する）
+    pub fn update(&mut self, version: Ptr, index: usize, value: M::Val) -> Ptr {
+        assert!(index < self.N, "index out of range: {index}");
+        self.update_inner(version, 0, self.size, index, value)
+    }
+
+    fn update_inner(&mut self, ptr: Ptr, l: usize, r: usize, index: usize, value: M::Val) -> Ptr {
+        if r - l == 1 {
+            return self.alloc_leaf(value);
+        }
+        let mid = (l + r) / 2;
+        let Node { left, right, .. } = *self.arena.get(ptr);
+        if index < mid {
+            let left = self.update_inner(left, l, mid, index, value);
+            self.alloc_node(left, right)
+        } else {
+            let right = self.update_inner(right, mid, r, index, value);
+            self.alloc_node(left, right)
+        }
+    }
+
+    /// バージョン `version` の `index` 番目の要素
+    /// - 時間計算量: $`O(\log N)`$
+    pub fn get(&self, version: Ptr, index: usize) -> &M::Val {
+        assert!(index < self.N, "index out of range: {index}");
+        let (mut ptr, mut l, mut r) = (version, 0, self.size);
+        while r - l > 1 {
+            let mid = (l + r) / 2;
+            let node = self.arena.get(ptr);
+            if index < mid {
+                (ptr, r) = (node.left, mid);
+            } else {
+                (ptr, l) = (node.right, mid);
+            }
+        }
+        &self.arena.get(ptr).val
+    }
+
+    /// バージョン `version` の区間 `range` の集約を行う
+    /// - 時間計算量: $`O(\log N)`$
+    pub fn get_range<R: RangeBounds<usize> + Debug>(&self, version: Ptr, range: R) -> M::Val {
+        let (start, end) = match self.parse_range(&range) {
+            Some(r) => r,
+            None => panic!("The given range is wrong: {:?}", range),
+        };
+        self.get_range_inner(version, 0, self.size, start, end)
+    }
+
+    fn get_range_inner(&self, ptr: Ptr, l: usize, r: usize, start: usize, end: usize) -> M::Val {
+        if end <= l || r <= start || ptr == EMPTY {
+            return M::e();
+        }
+        let node = self.arena.get(ptr);
+        if start <= l && r <= end {
+            return node.val.clone();
+        }
+        let mid = (l + r) / 2;
+        M::op(
+            &self.get_range_inner(node.left, l, mid, start, end),
+            &self.get_range_inner(node.right, mid, r, start, end),
+        )
+    }
+}
+
+// セグ木上の2分探索
+impl<M: Monoid> PersistentSegmentTree<M> {
+    /// 左端を固定した2分探索（[`SegmentTree::max_right`](crate::data_structure::segment_tree::SegmentTree::max_right) のバージョン指定版）
+    /// - 引数`l`と関数`f`に対して，
+    ///     - `f( seg.get_range(version, l..x) ) = true`
+    ///     - `f( seg.get_range(version, l..x+1) ) = false`
+    ///
+    ///   \
+    ///   を満たす`x`を返す
+    pub fn max_right<F>(&self, version: Ptr, l: usize, f: F) -> (M::Val, usize)
+    where
+        F: Fn(M::Val) -> bool,
+    {
+        assert!(f(M::e()));
+
+        let mut sum = M::e();
+        if l >= self.N {
+            return (sum, self.N);
+        }
+        let x = self.max_right_inner(version, 0, self.size, l, &f, &mut sum);
+        (sum, x.unwrap_or(self.N).min(self.N))
+    }
+
+    fn max_right_inner<F>(
+        &self,
+        ptr: Ptr,
+        l: usize,
+        r: usize,
+        start: usize,
+        f: &F,
+        sum: &mut M::Val,
+    ) -> Option<usize>
+    where
+        F: Fn(M::Val) -> bool,
+    {
+        if r <= start {
+            return None;
+        }
+        let node = self.arena.get(ptr);
+        if start <= l {
+            let tmp = M::op(sum, &node.val);
+            if f(tmp.clone()) {
+                *sum = tmp;
+                return None;
+            }
+            if r - l == 1 {
+                return Some(l);
+            }
+        }
+        let mid = (l + r) / 2;
+        self.max_right_inner(node.left, l, mid, start, f, sum)
+            .or_else(|| self.max_right_inner(node.right, mid, r, start, f, sum))
+    }
+
+    /// 右端を固定した2分探索（[`SegmentTree::min_left`](crate::data_structure::segment_tree::SegmentTree::min_left) のバージョン指定版）
+    /// - 引数`r`と関数`f`に対して，
+    ///    - `f( seg.get_range(version, x..r) ) = true`
+    ///    - `f( seg.get_range(version, x-1..r) ) = false`
+    ///
+    ///   \
+    ///   となるような`x`を返す
+    pub fn min_left<F>(&self, version: Ptr, r: usize, f: F) -> (M::Val, usize)
+    where
+        F: Fn(M::Val) -> bool,
+    {
+        assert!(f(M::e()));
+        assert!(r <= self.N, "index out of range: {r}");
+
+        let mut sum = M::e();
+        if r == 0 {
+            return (sum, 0);
+        }
+        let x = self.min_left_inner(version, 0, self.size, r, &f, &mut sum);
+        (sum, x.unwrap_or(0))
+    }
+
+    fn min_left_inner<F>(
+        &self,
+        ptr: Ptr,
+        l: usize,
+        r: usize,
+        end: usize,
+        f: &F,
+        sum: &mut M::Val,
+    ) -> Option<usize>
+    where
+        F: Fn(M::Val) -> bool,
+    {
+        if end <= l {
+            return None;
+        }
+        let node = self.arena.get(ptr);
+        if r <= end {
+            let tmp = M::op(&node.val, sum);
+            if f(tmp.clone()) {
+                *sum = tmp;
+                return None;
+            }
+            if r - l == 1 {
+                return Some(r);
+            }
+        }
+        let mid = (l + r) / 2;
+        self.min_left_inner(node.right, mid, r, end, f, sum)
+            .or_else(|| self.min_left_inner(node.left, l, mid, end, f, sum))
+    }
+}
+
+/// 区間内の $`k`$ 番目に小さい値を求める
+///
+/// 値を座標圧縮し，先頭 $`i`$ 要素の出現回数を $`i`$ 番目のバージョンとして持つ
+///
+/// ```
+/// use cp_library_rs::data_structure::persistent_segment_tree::RangeKth;
+///
+/// let kth = RangeKth::new(&[5, 1, 4, 1, 3]);
+/// assert_eq!(kth.kth_smallest(1..4, 0), Some(&1));
+/// assert_eq!(kth.kth_smallest(1..4, 2), Some(&4));
+/// assert_eq!(kth.kth_smallest(1..4, 3), None);
+/// assert_eq!(kth.count_less(.., &4), 3);
+/// ```
+pub struct RangeKth<T: Ord + Clone> {
+    /// 座標圧縮後の値
+    sorted: Vec<T>,
+    seg: PersistentSegmentTree<Add<usize>>,
+    /// `roots[i]`：先頭 `i` 要素の出現回数
+    roots: Vec<Ptr>,
+}
+
+impl<T: Ord + Clone> RangeKth<T> {
+    /// 配列から構築する
+    /// - 時間計算量: $`O(N \log N)`$
+    pub fn new(src: &[T]) -> Self {
+        let mut sorted = src.to_vec();
+        sorted.sort();
+        sorted.dedup();
+
+        let mut seg = PersistentSegmentTree::<Add<usize>>::new(sorted.len());
+        let mut roots = vec![seg.initial()];
+        for x in src {
+            let i = sorted.binary_search(x).unwrap();
+            let prev = *roots.last().unwrap();
+            let cnt = seg.get(prev, i) + 1;
+            roots.push(seg.update(prev, i, cnt));
+        }
+
+        Self { sorted, seg, roots }
+    }
+
+    #[inline]
+    fn parse_range<R: RangeBounds<usize>>(&self, range: &R) -> (usize, usize) {
+        let n = self.roots.len() - 1;
+        let start = match range.start_bound() {
+            Unbounded => 0,
+            Excluded(&v) => v + 1,
+            Included(&v) => v,
+        };
+        let end = match range.end_bound() {
+            Unbounded => n,
+            Excluded(&v) => v,
+            Included(&v) => v + 1,
+        };
+        assert!(start <= end && end <= n, "The given range is wrong");
+        (start, end)
+    }
+
+    /// 区間 `range` の中で `k` 番目（0-indexed）に小さい値
+    /// - 時間計算量: $`O(\log N)`$
+    ///
+    /// **戻り値**
+    /// - 区間の要素数が `k` 以下の場合 `None`
+    pub fn kth_smallest<R: RangeBounds<usize>>(&self, range: R, mut k: usize) -> Option<&T> {
+        let (start, end) = self.parse_range(&range);
+        if end - start <= k {
+            return None;
+        }
+
+        let arena = &self.seg.arena;
+        let (mut hi, mut lo) = (self.roots[end], self.roots[start]);
+        let (mut l, mut r) = (0, self.seg.size);
+        while r - l > 1 {
+            let (h, w) = (arena.get(hi), arena.get(lo));
+            let left = arena.get(h.left).val - arena.get(w.left).val;
+            let mid = (l + r) / 2;
+            if k < left {
+                (hi, lo, r) = (h.left, w.left, mid);
+            } else {
+                k -= left;
+                (hi, lo, l) = (h.right, w.right, mid);
+            }
+        }
+        Some(&self.sorted[l])
+    }
+
+    /// 区間 `range` の中で `k` 番目（0-indexed）に大きい値
+    pub fn kth_largest<R: RangeBounds<usize>>(&self, range: R, k: usize) -> Option<&T> {
+        let (start, end) = self.parse_range(&range);
+        if end - start <= k {
+            return None;
+        }
+        self.kth_smallest(start..end, end - start - 1 - k)
+    }
+
+    /// 区間 `range` の中で `x` 未満の値の個数
+    /// - 時間計算量: $`O(\log N)`$
+    pub fn count_less<R: RangeBounds<usize>>(&self, range: R, x: &T) -> usize {
+        let (start, end) = self.parse_range(&range);
+        let i = self.sorted.partition_point(|v| v < x);
+        self.seg.get_range(self.roots[end], ..i) - self.seg.get_range(self.roots[start], ..i)
+    }
+}
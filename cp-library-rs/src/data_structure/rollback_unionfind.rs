@@ -1,4 +1,125 @@
 //! undo処理，rollback処理をもたせたUnionFind木
 
+use std::{collections::HashMap, fmt::Debug};
+
+use crate::utils::consts::NEG1;
+
 /// ロールバック可能UnionFind
-pub struct RollbackUnionFind {}
+///
+/// - サイズによる併合のみを行い，経路圧縮は行わない
+/// - 各操作の時間計算量: $`O(\log N)`$
+pub struct RollbackUnionFind {
+    /// 要素数
+    n: usize,
+    /// 親の番号を格納する配列（根の場合は要素数の符号反転）
+    parent: Vec<usize>,
+    /// 連結成分の個数
+    count: usize,
+    /// `unite` の履歴 `(親, 子, 子の併合前の値)`（併合しなかった場合は `None`）
+    history: Vec<Option<(usize, usize, usize)>>,
+}
+
+impl RollbackUnionFind {
+    /// 新しいUnionFind木を生成する
+    pub fn new(n: usize) -> Self {
+        Self {
+            n,
+            parent: vec![NEG1; n],
+            count: n,
+            history: vec![],
+        }
+    }
+
+    /// 根を求める
+    pub fn root(&self, mut x: usize) -> usize {
+        while self.parent[x] < self.n {
+            x = self.parent[x];
+        }
+        x
+    }
+
+    /// 同一の集合に所属するか判定
+    pub fn is_same(&self, x: usize, y: usize) -> bool {
+        self.root(x) == self.root(y)
+    }
+
+    /// 集合`x,y`を併合する．
+    ///
+    /// **戻り値**
+    /// - すでに併合済みだった場合`None`，そうでない場合親となった要素の番号を返す
+    pub fn unite(&mut self, x: usize, y: usize) -> Option<usize> {
+        let mut parent = self.root(x);
+        let mut child = self.root(y);
+
+        if parent == child {
+            self.history.push(None);
+            return None;
+        }
+
+        // 要素数が大きい方を親にする
+        if self.parent[parent] > self.parent[child] {
+            (parent, child) = (child, parent);
+        }
+
+        self.history.push(Some((parent, child, self.parent[child])));
+        self.parent[parent] = self.parent[parent].wrapping_add(self.parent[child]);
+        self.parent[child] = parent;
+        self.count -= 1;
+
+        Some(parent)
+    }
+
+    /// 直前の `unite` を取り消す
+    ///
+    /// **戻り値**
+    /// - 取り消す操作がなかった場合 `false`
+    pub fn undo(&mut self) -> bool {
+        let Some(last) = self.history.pop() else {
+            return false;
+        };
+        if let Some((parent, child, child_value)) = last {
+            self.parent[parent] = self.parent[parent].wrapping_sub(child_value);
+            self.parent[child] = child_value;
+            self.count += 1;
+        }
+        true
+    }
+
+    /// 現在の状態を表す値（[`RollbackUnionFind::rollback`] に渡す）
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+
+    /// `snapshot` の時点の状態に戻す
+    pub fn rollback(&mut self, snapshot: usize) {
+        while self.history.len() > snapshot {
+            self.undo();
+        }
+    }
+
+    /// 連結成分の大きさを求める
+    pub fn size(&self, x: usize) -> usize {
+        self.parent[self.root(x)].wrapping_neg()
+    }
+
+    /// 連結成分の数を返す
+    pub fn group_count(&self) -> usize {
+        self.count
+    }
+
+    /// {代表元: 集合} のマップを返す
+    ///
+    /// - 時間計算量: $`O(N \log N)`$
+    pub fn enum_groups(&self) -> HashMap<usize, Vec<usize>> {
+        (0..self.n).fold(HashMap::default(), |mut map, i| {
+            map.entry(self.root(i)).or_default().push(i);
+            map
+        })
+    }
+}
+
+impl Debug for RollbackUnionFind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.enum_groups()).finish()
+    }
+}
@@ -0,0 +1,376 @@
+//! ## Segment Tree Beats
+//!
+//! 整数列 $`A`$ に対し，
+//!
+//! - 区間 chmin / chmax ： $`A_i \leftarrow \min(A_i, x)`$ / $`A_i \leftarrow \max(A_i, x)`$
+//! - 区間加算・区間代入
+//! - 区間和・区間最小値・区間最大値の取得
+//!
+//! を償却 $`O(\log^2 N)`$ で行う．各ノードに最大値・2番目の最大値・最大値の個数（最小値も同様）を持たせる．
+//! - 参考: <https://codeforces.com/blog/entry/57319>
+
+use std::{
+    fmt::Debug,
+    ops::{
+        Bound::{Excluded, Included, Unbounded},
+        RangeBounds,
+    },
+};
+
+use num_traits::PrimInt;
+
+/// ノード
+#[derive(Clone, Debug)]
+struct Node<T> {
+    sum: T,
+    max: T,
+    /// 2番目に大きい値（存在しない場合は `None`）
+    max2: Option<T>,
+    max_cnt: usize,
+    min: T,
+    /// 2番目に小さい値（存在しない場合は `None`）
+    min2: Option<T>,
+    min_cnt: usize,
+    /// 区間の要素数
+    len: usize,
+    /// 子に伝播していない加算
+    add: T,
+}
+
+impl<T: PrimInt> Node<T> {
+    /// 要素を持たないノード
+    fn empty() -> Self {
+        Self {
+            sum: T::zero(),
+            max: T::min_value(),
+            max2: None,
+            max_cnt: 0,
+            min: T::max_value(),
+            min2: None,
+            min_cnt: 0,
+            len: 0,
+            add: T::zero(),
+        }
+    }
+
+    /// 葉
+    fn leaf(x: T) -> Self {
+        Self {
+            sum: x,
+            max: x,
+            max2: None,
+            max_cnt: 1,
+            min: x,
+            min2: None,
+            min_cnt: 1,
+            len: 1,
+            add: T::zero(),
+        }
+    }
+}
+
+/// `x` を `n` 個足した値
+#[inline]
+fn times<T: PrimInt>(x: T, n: usize) -> T {
+    x * T::from(n).unwrap()
+}
+
+/// Segment Tree Beats
+///
+/// ```
+/// use cp_library_rs::data_structure::segment_tree_beats::SegmentTreeBeats;
+///
+/// let mut seg = SegmentTreeBeats::from_vec(vec![5, 1, 4, 1, 3]);
+/// seg.chmin(.., 3);
+/// assert_eq!(seg.sum(..), 3 + 1 + 3 + 1 + 3);
+/// seg.add(1..3, 10);
+/// assert_eq!(seg.max(..), 13);
+/// seg.chmax(.., 4);
+/// assert_eq!(seg.min(..), 4);
+/// ```
+#[derive(Debug)]
+pub struct SegmentTreeBeats<T: PrimInt> {
+    /// 要素数
+    pub size: usize,
+    offset: usize,
+    nodes: Vec<Node<T>>,
+}
+
+impl<T: PrimInt + Debug> SegmentTreeBeats<T> {
+    #[inline]
+    fn parse_range<R: RangeBounds<usize>>(&self, range: &R) -> Option<(usize, usize)> {
+        let start = match range.start_bound() {
+            Unbounded => 0,
+            Excluded(&v) => v + 1,
+            Included(&v) => v,
+        };
+        let end = match range.end_bound() {
+            Unbounded => self.size,
+            Excluded(&v) => v,
+            Included(&v) => v + 1,
+        };
+        if start <= end && end <= self.size {
+            Some((start, end))
+        } else {
+            None
+        }
+    }
+
+    fn range_or_panic<R: RangeBounds<usize> + Debug>(&self, range: &R) -> (usize, usize) {
+        match self.parse_range(range) {
+            Some(r) => r,
+            None => panic!("The given range is wrong: {:?}", range),
+        }
+    }
+
+    /// すべての要素が `0` の列で初期化する
+    pub fn new(n: usize) -> Self {
+        Self::from_vec(vec![T::zero(); n])
+    }
+
+    /// 配列から構築する
+    /// - 時間計算量: $`O(N)`$
+    pub fn from_vec(src: Vec<T>) -> Self {
+        let size = src.len();
+        let offset = size.next_power_of_two();
+        let mut nodes = vec![Node::empty(); offset << 1];
+        for (i, x) in src.into_iter().enumerate() {
+            nodes[offset + i] = Node::leaf(x);
+        }
+        let mut seg = Self {
+            size,
+            offset,
+            nodes,
+        };
+        for k in (1..offset).rev() {
+            seg.pull(k);
+        }
+        seg
+    }
+
+    /// 子の情報から更新する
+    fn pull(&mut self, k: usize) {
+        let (l, r) = (&self.nodes[k << 1], &self.nodes[k << 1 | 1]);
+        // 要素を持たない子は無視する
+        if l.len == 0 || r.len == 0 {
+            let mut node = if l.len == 0 { r.clone() } else { l.clone() };
+            node.add = T::zero();
+            self.nodes[k] = node;
+            return;
+        }
+        let sum = l.sum + r.sum;
+        let len = l.len + r.len;
+
+        // `None < Some(_)` なので，2番目の最大値は `Option::max` で求まる．最小値側は `None` を除いて比較する
+        let min_opt = |a: Option<T>, b: Option<T>| match (a, b) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        let (max, max2, max_cnt) = match l.max.cmp(&r.max) {
+            std::cmp::Ordering::Equal => (l.max, l.max2.max(r.max2), l.max_cnt + r.max_cnt),
+            std::cmp::Ordering::Greater => (l.max, l.max2.max(Some(r.max)), l.max_cnt),
+            std::cmp::Ordering::Less => (r.max, r.max2.max(Some(l.max)), r.max_cnt),
+        };
+        let (min, min2, min_cnt) = match l.min.cmp(&r.min) {
+            std::cmp::Ordering::Equal => (l.min, min_opt(l.min2, r.min2), l.min_cnt + r.min_cnt),
+            std::cmp::Ordering::Less => (l.min, min_opt(l.min2, Some(r.min)), l.min_cnt),
+            std::cmp::Ordering::Greater => (r.min, min_opt(r.min2, Some(l.min)), r.min_cnt),
+        };
+
+        let node = &mut self.nodes[k];
+        node.sum = sum;
+        node.len = len;
+        (node.max, node.max2, node.max_cnt) = (max, max2, max_cnt);
+        (node.min, node.min2, node.min_cnt) = (min, min2, min_cnt);
+    }
+
+    /// 最大値を `x` に下げる（`max2 < x < max` のとき）
+    fn apply_chmin(&mut self, k: usize, x: T) {
+        let node = &mut self.nodes[k];
+        if node.len == 0 || node.max <= x {
+            return;
+        }
+        node.sum = node.sum - times(node.max - x, node.max_cnt);
+        if node.max == node.min {
+            node.min = x;
+        } else if node.min2 == Some(node.max) {
+            node.min2 = Some(x);
+        }
+        node.max = x;
+    }
+
+    /// 最小値を `x` に上げる（`min < x < min2` のとき）
+    fn apply_chmax(&mut self, k: usize, x: T) {
+        let node = &mut self.nodes[k];
+        if node.len == 0 || node.min >= x {
+            return;
+        }
+        node.sum = node.sum + times(x - node.min, node.min_cnt);
+        if node.min == node.max {
+            node.max = x;
+        } else if node.max2 == Some(node.min) {
+            node.max2 = Some(x);
+        }
+        node.min = x;
+    }
+
+    /// 区間全体に `x` を加える
+    fn apply_add(&mut self, k: usize, x: T) {
+        let node = &mut self.nodes[k];
+        if node.len == 0 {
+            return;
+        }
+        node.sum = node.sum + times(x, node.len);
+        node.max = node.max + x;
+        node.max2 = node.max2.map(|v| v + x);
+        node.min = node.min + x;
+        node.min2 = node.min2.map(|v| v + x);
+        node.add = node.add + x;
+    }
+
+    /// 子へ伝播する
+    fn push(&mut self, k: usize) {
+        let add = self.nodes[k].add;
+        if add != T::zero() {
+            self.apply_add(k << 1, add);
+            self.apply_add(k << 1 | 1, add);
+            self.nodes[k].add = T::zero();
+        }
+        let (max, min) = (self.nodes[k].max, self.nodes[k].min);
+        for c in [k << 1, k << 1 | 1] {
+            self.apply_chmin(c, max);
+            self.apply_chmax(c, min);
+        }
+    }
+
+    /// 区間 `range` の各要素を `min(A_i, x)` に更新する
+    /// - 時間計算量: 償却 $`O(\log^2 N)`$
+    pub fn chmin<R: RangeBounds<usize> + Debug>(&mut self, range: R, x: T) {
+        let (l, r) = self.range_or_panic(&range);
+        self.chmin_inner(1, 0, self.offset, l, r, x);
+    }
+
+    fn chmin_inner(&mut self, k: usize, nl: usize, nr: usize, l: usize, r: usize, x: T) {
+        if r <= nl || nr <= l || self.nodes[k].max <= x {
+            return;
+        }
+        if l <= nl && nr <= r && self.nodes[k].max2.is_none_or(|v| v < x) {
+            self.apply_chmin(k, x);
+            return;
+        }
+        self.push(k);
+        let mid = (nl + nr) / 2;
+        self.chmin_inner(k << 1, nl, mid, l, r, x);
+        self.chmin_inner(k << 1 | 1, mid, nr, l, r, x);
+        self.pull(k);
+    }
+
+    /// 区間 `range` の各要素を `max(A_i, x)` に更新する
+    /// - 時間計算量: 償却 $`O(\log^2 N)`$
+    pub fn chmax<R: RangeBounds<usize> + Debug>(&mut self, range: R, x: T) {
+        let (l, r) = self.range_or_panic(&range);
+        self.chmax_inner(1, 0, self.offset, l, r, x);
+    }
+
+    fn chmax_inner(&mut self, k: usize, nl: usize, nr: usize, l: usize, r: usize, x: T) {
+        if r <= nl || nr <= l || self.nodes[k].min >= x {
+            return;
+        }
+        if l <= nl && nr <= r && self.nodes[k].min2.is_none_or(|v| v > x) {
+            self.apply_chmax(k, x);
+            return;
+        }
+        self.push(k);
+        let mid = (nl + nr) / 2;
+        self.chmax_inner(k << 1, nl, mid, l, r, x);
+        self.chmax_inner(k << 1 | 1, mid, nr, l, r, x);
+        self.pull(k);
+    }
+
+    /// 区間 `range` の各要素に `x` を加える
+    /// - 時間計算量: $`O(\log N)`$
+    pub fn add<R: RangeBounds<usize> + Debug>(&mut self, range: R, x: T) {
+        let (l, r) = self.range_or_panic(&range);
+        self.add_inner(1, 0, self.offset, l, r, x);
+    }
+
+    fn add_inner(&mut self, k: usize, nl: usize, nr: usize, l: usize, r: usize, x: T) {
+        if r <= nl || nr <= l {
+            return;
+        }
+        if l <= nl && nr <= r {
+            self.apply_add(k, x);
+            return;
+        }
+        self.push(k);
+        let mid = (nl + nr) / 2;
+        self.add_inner(k << 1, nl, mid, l, r, x);
+        self.add_inner(k << 1 | 1, mid, nr, l, r, x);
+        self.pull(k);
+    }
+
+    /// 区間 `range` の各要素を `x` に更新する
+    /// - 時間計算量: 償却 $`O(\log^2 N)`$
+    pub fn assign<R: RangeBounds<usize> + Debug>(&mut self, range: R, x: T) {
+        let (l, r) = self.range_or_panic(&range);
+        self.chmin_inner(1, 0, self.offset, l, r, x);
+        self.chmax_inner(1, 0, self.offset, l, r, x);
+    }
+
+    /// 区間 `range` の総和
+    /// - 時間計算量: $`O(\log N)`$
+    pub fn sum<R: RangeBounds<usize> + Debug>(&mut self, range: R) -> T {
+        let (l, r) = self.range_or_panic(&range);
+        self.query(1, 0, self.offset, l, r, &|node| node.sum, &|a, b| a + b)
+            .unwrap_or(T::zero())
+    }
+
+    /// 区間 `range` の最小値（空の区間では `T::max_value()`）
+    /// - 時間計算量: $`O(\log N)`$
+    pub fn min<R: RangeBounds<usize> + Debug>(&mut self, range: R) -> T {
+        let (l, r) = self.range_or_panic(&range);
+        self.query(1, 0, self.offset, l, r, &|node| node.min, &T::min)
+            .unwrap_or(T::max_value())
+    }
+
+    /// 区間 `range` の最大値（空の区間では `T::min_value()`）
+    /// - 時間計算量: $`O(\log N)`$
+    pub fn max<R: RangeBounds<usize> + Debug>(&mut self, range: R) -> T {
+        let (l, r) = self.range_or_panic(&range);
+        self.query(1, 0, self.offset, l, r, &|node| node.max, &T::max)
+            .unwrap_or(T::min_value())
+    }
+
+    /// `i` 番目の要素
+    pub fn get(&mut self, i: usize) -> T {
+        assert!(i < self.size, "index out of range: {i}");
+        self.sum(i..=i)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn query(
+        &mut self,
+        k: usize,
+        nl: usize,
+        nr: usize,
+        l: usize,
+        r: usize,
+        get: &impl Fn(&Node<T>) -> T,
+        op: &impl Fn(T, T) -> T,
+    ) -> Option<T> {
+        if r <= nl || nr <= l {
+            return None;
+        }
+        if l <= nl && nr <= r {
+            return Some(get(&self.nodes[k]));
+        }
+        self.push(k);
+        let mid = (nl + nr) / 2;
+        let left = self.query(k << 1, nl, mid, l, r, get, op);
+        let right = self.query(k << 1 | 1, mid, nr, l, r, get, op);
+        match (left, right) {
+            (Some(a), Some(b)) => Some(op(a, b)),
+            (a, b) => a.or(b),
+        }
+    }
+}
@@ -1,143 +1,391 @@
 //! トライ木
+//!
+//! - 任意の全順序な記号列を扱うトライ木 [`GenericTrie`]
+//! - バイト列を扱うトライ木 [`ByteTrie`]
+//! - 文字列を扱うトライ木 [`Trie`]
+//!
+//! ノードは配列上に確保し，各ノードの子は記号の昇順に並べて保持する．
+//! 各ノードでは，そのノードを通過する単語の個数と，そのノードで終わる単語の個数を管理する．
+//!
+//! ```
+//! use cp_library_rs::data_structure::trie::*;
+//!
+//! let mut trie = ByteTrie::<()>::new();
+//! trie.insert(b"abc");
+//! trie.insert(b"abd");
+//! trie.insert(b"abd");
+//! trie.insert(b"b");
+//!
+//! assert_eq!(trie.len(), 4);
+//! assert_eq!(trie.count(b"abd"), 2);
+//! assert_eq!(trie.count_prefix(b"ab"), 3);
+//! assert_eq!(trie.longest_common_prefix_with(b"abz"), 2);
+//! assert_eq!(trie.kth(2), Some(b"abd".to_vec()));
+//!
+//! assert!(trie.remove(b"abc"));
+//! assert_eq!(trie.kth(0), Some(b"abd".to_vec()));
+//! ```
 
-use std::fmt::Debug;
+use std::{
+    fmt::Debug,
+    ops::{Deref, DerefMut},
+};
 
-// 定数
-const ORIGIN: char = 'a'; // 基準となる文字
-const ORIGIN_ID: usize = ORIGIN as usize; // 基準となる文字のID
-const KINDS: usize = 26; // 文字の種類数
-type NodePointer<T> = Option<Box<TrieNode<T>>>;
-
-/// 何番目の文字かを判定する
-fn ord(c: char) -> usize {
-    let num = c as usize;
-    num - ORIGIN_ID
+/// # TrieNode
+/// - トライ木のノード
+#[derive(Debug, Clone)]
+struct TrieNode<C, T> {
+    /// 値
+    data: Option<T>,
+    /// `(記号, 子のノード番号)`（記号の昇順）
+    children: Vec<(C, usize)>,
+    /// このノードを通過する単語の個数
+    pass: usize,
+    /// このノードで終わる単語の個数
+    end: usize,
 }
 
-/// i番目の文字を返す
-fn chr(i: usize) -> char {
-    (ORIGIN_ID + i) as u8 as char
+impl<C, T> TrieNode<C, T> {
+    fn new() -> Self {
+        Self {
+            data: None,
+            children: vec![],
+            pass: 0,
+            end: 0,
+        }
+    }
 }
 
-/// # TrieNode
-/// - トライ木のノード
+/// # GenericTrie
+/// - 記号 `C` の列を単語とするトライ木
+/// - 単語の多重集合と，単語ごとの値 `T` を管理する
+/// - 長さ $`L`$ の単語に対する操作の時間計算量: $`O(L \log \sigma)`$（$`\sigma`$ は子の個数）
 #[derive(Debug, Clone)]
-struct TrieNode<T> {
-    data: Option<T>,
-    children: Vec<NodePointer<T>>,
+pub struct GenericTrie<C, T> {
+    /// ノード（`nodes[0]` が根）
+    nodes: Vec<TrieNode<C, T>>,
 }
 
-impl<T> TrieNode<T>
-where
-    T: Clone,
-{
-    pub fn new(data: Option<T>) -> Self {
+/// バイト列を扱うトライ木
+pub type ByteTrie<T> = GenericTrie<u8, T>;
+
+impl<C: Ord + Clone, T> Default for GenericTrie<C, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Ord + Clone, T> GenericTrie<C, T> {
+    /// 空のトライ木を生成する
+    pub fn new() -> Self {
         Self {
-            data,
-            children: vec![NodePointer::None; KINDS],
+            nodes: vec![TrieNode::new()],
         }
     }
+
+    /// 単語の個数（重複を含む）
+    pub fn len(&self) -> usize {
+        self.nodes[0].pass
+    }
+
+    /// 空であるか
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// ノード `node` の記号 `c` に対応する子
+    fn child(&self, node: usize, c: &C) -> Option<usize> {
+        let children = &self.nodes[node].children;
+        children
+            .binary_search_by(|(d, _)| d.cmp(c))
+            .ok()
+            .map(|i| children[i].1)
+    }
+
+    /// 単語 `word` に対応するノード（存在しない場合は `None`）
+    fn find(&self, word: &[C]) -> Option<usize> {
+        word.iter().try_fold(0, |node, c| self.child(node, c))
+    }
+
+    /// 単語 `word` に対応するノード（存在しない場合は作成する）
+    fn find_or_create(&mut self, word: &[C]) -> usize {
+        let mut node = 0;
+        for c in word {
+            node = match self.nodes[node]
+                .children
+                .binary_search_by(|(d, _)| d.cmp(c))
+            {
+                Ok(i) => self.nodes[node].children[i].1,
+                Err(i) => {
+                    let new = self.nodes.len();
+                    self.nodes.push(TrieNode::new());
+                    self.nodes[node].children.insert(i, (c.clone(), new));
+                    new
+                }
+            };
+        }
+        node
+    }
+
+    /// 単語 `word` を1個追加する
+    pub fn insert(&mut self, word: &[C]) {
+        self.insert_count(word, 1);
+    }
+
+    /// 単語 `word` を `cnt` 個追加する
+    pub fn insert_count(&mut self, word: &[C], cnt: usize) {
+        self.find_or_create(word);
+        let mut node = 0;
+        self.nodes[node].pass += cnt;
+        for c in word {
+            node = self.child(node, c).unwrap();
+            self.nodes[node].pass += cnt;
+        }
+        self.nodes[node].end += cnt;
+    }
+
+    /// 単語 `word` を1個削除する
+    ///
+    /// **戻り値**
+    /// - `word` が存在した場合 `true`
+    pub fn remove(&mut self, word: &[C]) -> bool {
+        self.remove_count(word, 1) == 1
+    }
+
+    /// 単語 `word` を最大 `cnt` 個削除する
+    ///
+    /// - 個数が 0 になった場合は値も削除する
+    ///
+    /// **戻り値**
+    /// - 実際に削除した個数
+    pub fn remove_count(&mut self, word: &[C], cnt: usize) -> usize {
+        let cnt = cnt.min(self.count(word));
+        if cnt == 0 {
+            return 0;
+        }
+        let mut node = 0;
+        self.nodes[node].pass -= cnt;
+        for c in word {
+            node = self.child(node, c).unwrap();
+            self.nodes[node].pass -= cnt;
+        }
+        self.nodes[node].end -= cnt;
+        if self.nodes[node].end == 0 {
+            self.nodes[node].data = None;
+        }
+        cnt
+    }
+
+    /// 単語 `word` の個数
+    pub fn count(&self, word: &[C]) -> usize {
+        self.find(word).map_or(0, |node| self.nodes[node].end)
+    }
+
+    /// `prefix` を接頭辞にもつ単語の個数
+    pub fn count_prefix(&self, prefix: &[C]) -> usize {
+        self.find(prefix).map_or(0, |node| self.nodes[node].pass)
+    }
+
+    /// `word` の接頭辞のうち，いずれかの単語の接頭辞でもあるものの最大の長さ
+    pub fn longest_common_prefix_with(&self, word: &[C]) -> usize {
+        let mut node = 0;
+        for (i, c) in word.iter().enumerate() {
+            match self.child(node, c) {
+                Some(next) if self.nodes[next].pass > 0 => node = next,
+                _ => return i,
+            }
+        }
+        word.len()
+    }
+
+    /// 辞書順で `k` 番目（0-indexed，重複を含む）の単語
+    ///
+    /// **戻り値**
+    /// - 単語の個数が `k` 以下の場合 `None`
+    pub fn kth(&self, mut k: usize) -> Option<Vec<C>> {
+        if k >= self.len() {
+            return None;
+        }
+        let mut node = 0;
+        let mut res = vec![];
+        while k >= self.nodes[node].end {
+            k -= self.nodes[node].end;
+            for (c, next) in &self.nodes[node].children {
+                let pass = self.nodes[*next].pass;
+                if k < pass {
+                    res.push(c.clone());
+                    node = *next;
+                    break;
+                }
+                k -= pass;
+            }
+        }
+        Some(res)
+    }
+
+    /// 単語 `word` の値
+    pub fn get(&self, word: &[C]) -> Option<&T> {
+        self.nodes[self.find(word)?].data.as_ref()
+    }
+
+    /// 単語 `word` の値（可変参照）
+    pub fn get_mut(&mut self, word: &[C]) -> Option<&mut T> {
+        let node = self.find(word)?;
+        self.nodes[node].data.as_mut()
+    }
+
+    /// 単語 `word` の値を格納する場所
+    ///
+    /// - 単語の個数は変化しない（`len`，`count_prefix`，`kth` には含まれない）
+    /// - 値を設定した単語は，個数が 0 でも `get`，`traverse` に含まれる
+    pub fn get_or_insert_mut(&mut self, word: &[C]) -> &mut Option<T> {
+        let node = self.find_or_create(word);
+        &mut self.nodes[node].data
+    }
+
+    /// 値をもつ単語とその値を辞書順に列挙する
+    pub fn traverse(&self) -> Vec<(Vec<C>, &T)> {
+        let mut res = vec![];
+        let mut path = vec![];
+        // (ノード, 親の深さ, 親からの記号)
+        let mut stack = vec![(0, 0, None)];
+        while let Some((node, depth, c)) = stack.pop() {
+            path.truncate(depth);
+            if let Some(c) = c {
+                path.push(c);
+            }
+            if let Some(data) = self.nodes[node].data.as_ref() {
+                res.push((path.clone(), data));
+            }
+            for (c, next) in self.nodes[node].children.iter().rev() {
+                stack.push((*next, path.len(), Some(c.clone())));
+            }
+        }
+        res
+    }
 }
 
 /// # Trie
-/// - トライ木の実装
-#[derive(Debug)]
+/// - 文字列を扱うトライ木
+/// - 文字列ごとに値 `T` をもつ
+/// - 値が `Some` である文字列だけを1個として数える
+#[derive(Debug, Clone)]
 pub struct Trie<T> {
-    size: usize,
-    root: NodePointer<T>,
+    trie: GenericTrie<char, T>,
+}
+
+impl<T> Default for Trie<T> {
+    fn default() -> Self {
+        Trie {
+            trie: GenericTrie::new(),
+        }
+    }
 }
 
 impl<T> Trie<T>
 where
     T: Clone + Debug,
 {
+    /// 値をもつ文字列の個数
     pub fn len(&self) -> usize {
-        self.size
+        self.trie.len()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.size == 0
+        self.trie.is_empty()
     }
 
     pub fn insert(&mut self, key: &str, data: T) -> Option<T> {
-        let res = self.get_or_insert_mut(key).replace(data);
-        if res.is_none() {
-            self.size += 1;
-        }
+        self.get_or_insert_mut(key).replace(data)
+    }
+
+    /// 文字列 `key` とその値を削除する
+    pub fn remove(&mut self, key: &str) -> Option<T> {
+        let key: Vec<char> = key.chars().collect();
+        let res = self
+            .trie
+            .find(&key)
+            .and_then(|node| self.trie.nodes[node].data.take());
+        self.trie.remove(&key);
         res
     }
 
     pub fn get(&self, key: &str) -> Option<&T> {
-        let mut node = &self.root;
-        for c in key.chars().map(ord) {
-            node = &node.as_ref()?.children[c];
-        }
-        node.as_deref()?.data.as_ref()
+        self.trie.get(&key.chars().collect::<Vec<_>>())
     }
 
     pub fn get_mut(&mut self, key: &str) -> Option<&mut T> {
-        let mut node = &mut self.root;
-        for c in key.chars().map(ord) {
-            node = node.as_mut()?.children.get_mut(c).unwrap();
-        }
-        node.as_deref_mut()?.data.as_mut()
+        self.trie.get_mut(&key.chars().collect::<Vec<_>>())
     }
 
-    pub fn get_or_insert_mut(&mut self, key: &str) -> &mut Option<T> {
-        let mut node = &mut self.root;
-        for c in key.chars().map(ord).chain(KINDS..=KINDS) {
-            // データの挿入
-            if c == KINDS {
-                if node.as_ref().is_none() {
-                    *node = Some(Box::new(TrieNode::new(None)));
-                }
-                break;
-            }
-            if node.as_ref().is_none() {
-                *node = Some(Box::new(TrieNode::new(None)));
-            }
-            node = node.as_mut().unwrap().children.get_mut(c).unwrap();
+    /// 文字列 `key` の値を格納する場所
+    ///
+    /// - 戻り値を drop した時点で，値が `Some` なら1個，`None` なら0個として数え直す
+    pub fn get_or_insert_mut(&mut self, key: &str) -> TrieEntry<'_, T> {
+        let key: Vec<char> = key.chars().collect();
+        let node = self.trie.find_or_create(&key);
+        TrieEntry {
+            trie: &mut self.trie,
+            key,
+            node,
         }
-        &mut node.as_deref_mut().unwrap().data
+    }
+
+    /// `prefix` を接頭辞にもつ文字列の個数
+    pub fn count_prefix(&self, prefix: &str) -> usize {
+        self.trie.count_prefix(&prefix.chars().collect::<Vec<_>>())
+    }
+
+    /// `key` の接頭辞のうち，いずれかの文字列の接頭辞でもあるものの最大の長さ（文字数）
+    pub fn longest_common_prefix_with(&self, key: &str) -> usize {
+        self.trie
+            .longest_common_prefix_with(&key.chars().collect::<Vec<_>>())
+    }
+
+    /// 辞書順で `k` 番目（0-indexed）の文字列
+    pub fn kth(&self, k: usize) -> Option<String> {
+        self.trie.kth(k).map(|s| s.into_iter().collect())
     }
 
     pub fn traverse(&self) -> Vec<(String, &T)> {
-        let mut res = vec![];
-        let mut cur = String::new();
-        traverse_inner(&self.root, &mut cur, &mut res);
-        res
+        self.trie
+            .traverse()
+            .into_iter()
+            .map(|(key, value)| (key.into_iter().collect(), value))
+            .collect()
     }
 }
 
-impl<T: Clone> Default for Trie<T> {
-    fn default() -> Self {
-        Trie {
-            size: 0,
-            root: Some(Box::new(TrieNode {
-                data: None,
-                children: vec![NodePointer::None; KINDS],
-            })),
-        }
+/// [`Trie::get_or_insert_mut`] の戻り値
+///
+/// - `Option<T>` として読み書きでき，drop 時に文字列の個数を値に合わせる
+pub struct TrieEntry<'a, T> {
+    trie: &'a mut GenericTrie<char, T>,
+    key: Vec<char>,
+    node: usize,
+}
+
+impl<T> Deref for TrieEntry<'_, T> {
+    type Target = Option<T>;
+    fn deref(&self) -> &Self::Target {
+        &self.trie.nodes[self.node].data
     }
 }
 
-/// trieを順に探索する
-fn traverse_inner<'a, T>(
-    node: &'a NodePointer<T>,
-    cur: &mut String,
-    list: &mut Vec<(String, &'a T)>,
-) {
-    if let Some(value) = node.as_ref().unwrap().data.as_ref() {
-        let key = cur.clone();
-        list.push((key, value));
-    }
-    if let Some(node) = node.as_deref() {
-        for (i, child) in node.children.iter().enumerate() {
-            if child.as_ref().is_some() {
-                cur.push(chr(i));
-                traverse_inner(child, cur, list);
-                cur.pop();
-            }
+impl<T> DerefMut for TrieEntry<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.trie.nodes[self.node].data
+    }
+}
+
+impl<T> Drop for TrieEntry<'_, T> {
+    fn drop(&mut self) {
+        let has_value = self.trie.nodes[self.node].data.is_some();
+        let counted = self.trie.nodes[self.node].end > 0;
+        if has_value && !counted {
+            self.trie.insert(&self.key);
+        } else if !has_value && counted {
+            self.trie.remove(&self.key);
         }
     }
 }
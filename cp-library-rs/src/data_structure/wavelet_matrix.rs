@@ -0,0 +1,543 @@
+//! ## ウェーブレット行列
+//!
+//! 非負整数列 $`A`$ に対し，区間の $`k`$ 番目に小さい値・値の範囲に含まれる要素数などを
+//! $`O(\log \max A)`$ で求める．
+//!
+//! - [`WaveletMatrix`] ： 基本の構造
+//! - [`WaveletMatrixSum`] ： 区間の小さい方から $`k`$ 個の和も求める
+//! - [`CompressedWaveletMatrix`] ： 座標圧縮した値で構築する
+
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    fmt::Debug,
+    ops::{
+        Bound::{Excluded, Included, Unbounded},
+        RangeBounds,
+    },
+};
+
+use crate::utils::coordinate_compression::Compression;
+
+/// 完備辞書（rank / select を持つビット列）
+#[derive(Debug, Clone)]
+pub struct BitVector {
+    len: usize,
+    blocks: Vec<u64>,
+    /// `ranks[i]`：ブロック `i` より前の `1` の個数
+    ranks: Vec<usize>,
+}
+
+impl BitVector {
+    /// ビット列から構築する
+    pub fn new(bits: &[bool]) -> Self {
+        let len = bits.len();
+        let mut blocks = vec![0_u64; len / 64 + 1];
+        for (i, &b) in bits.iter().enumerate() {
+            if b {
+                blocks[i / 64] |= 1 << (i % 64);
+            }
+        }
+        let mut ranks = vec![0; blocks.len() + 1];
+        for (i, b) in blocks.iter().enumerate() {
+            ranks[i + 1] = ranks[i] + b.count_ones() as usize;
+        }
+        Self { len, blocks, ranks }
+    }
+
+    /// `i` 番目のビット
+    pub fn get(&self, i: usize) -> bool {
+        self.blocks[i / 64] >> (i % 64) & 1 == 1
+    }
+
+    /// `[0, i)` の `1` の個数
+    pub fn rank1(&self, i: usize) -> usize {
+        let mask = (1_u64 << (i % 64)) - 1;
+        self.ranks[i / 64] + (self.blocks[i / 64] & mask).count_ones() as usize
+    }
+
+    /// `[0, i)` の `0` の個数
+    pub fn rank0(&self, i: usize) -> usize {
+        i - self.rank1(i)
+    }
+
+    /// `[0, i)` の `b` の個数
+    pub fn rank(&self, b: bool, i: usize) -> usize {
+        if b {
+            self.rank1(i)
+        } else {
+            self.rank0(i)
+        }
+    }
+
+    /// `k` 番目（0-indexed）の `b` の位置
+    /// - 時間計算量: $`O(\log N)`$
+    pub fn select(&self, b: bool, k: usize) -> Option<usize> {
+        if self.rank(b, self.len) <= k {
+            return None;
+        }
+        // rank(b, i) > k となる最小の i から 1 を引いたもの
+        let (mut ng, mut ok) = (0, self.len);
+        while ok - ng > 1 {
+            let mid = (ok + ng) / 2;
+            if self.rank(b, mid) > k {
+                ok = mid;
+            } else {
+                ng = mid;
+            }
+        }
+        Some(ok - 1)
+    }
+}
+
+/// ウェーブレット行列
+///
+/// ```
+/// use cp_library_rs::data_structure::wavelet_matrix::WaveletMatrix;
+///
+/// let wm = WaveletMatrix::new(&[5, 4, 5, 5, 2, 1, 5, 6]);
+/// assert_eq!(wm.access(3), 5);
+/// assert_eq!(wm.rank(5, 4), 3);
+/// assert_eq!(wm.kth_smallest(1..5, 1), Some(4));
+/// assert_eq!(wm.range_freq(.., 2..=5), 6);
+/// assert_eq!(wm.prev_value(.., 5), Some(4));
+/// assert_eq!(wm.top_k(.., 2), vec![(5, 4), (1, 1)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct WaveletMatrix {
+    /// 要素数
+    pub size: usize,
+    /// ビット数
+    log: usize,
+    /// 上位ビットから順に各段のビット列
+    bits: Vec<BitVector>,
+    /// 各段の `0` の個数
+    zeros: Vec<usize>,
+}
+
+impl WaveletMatrix {
+    /// 配列から構築する
+    /// - 時間計算量: $`O(N \log \max A)`$
+    pub fn new(src: &[u64]) -> Self {
+        Self::build(src).0
+    }
+
+    /// 構築し，各段の並び（`levels[d]` は `d` 段目の入力の並び）も返す
+    fn build(src: &[u64]) -> (Self, Vec<Vec<u64>>) {
+        let max = src.iter().copied().max().unwrap_or(0);
+        let log = (u64::BITS - max.leading_zeros()) as usize;
+
+        let mut cur = src.to_vec();
+        let mut levels = vec![];
+        let mut bits = vec![];
+        let mut zeros = vec![];
+        for d in 0..log {
+            let shift = log - 1 - d;
+            let bv: Vec<bool> = cur.iter().map(|&v| v >> shift & 1 == 1).collect();
+            let (zs, os): (Vec<u64>, Vec<u64>) = cur.iter().partition(|&&v| v >> shift & 1 == 0);
+            zeros.push(zs.len());
+            bits.push(BitVector::new(&bv));
+            levels.push(std::mem::replace(&mut cur, [zs, os].concat()));
+        }
+        levels.push(cur);
+
+        let wm = Self {
+            size: src.len(),
+            log,
+            bits,
+            zeros,
+        };
+        (wm, levels)
+    }
+
+    #[inline]
+    fn parse_range<R: RangeBounds<usize> + Debug>(&self, range: &R) -> (usize, usize) {
+        let start = match range.start_bound() {
+            Unbounded => 0,
+            Excluded(&v) => v + 1,
+            Included(&v) => v,
+        };
+        let end = match range.end_bound() {
+            Unbounded => self.size,
+            Excluded(&v) => v,
+            Included(&v) => v + 1,
+        };
+        if start <= end && end <= self.size {
+            (start, end)
+        } else {
+            panic!("The given range is wrong: {:?}", range)
+        }
+    }
+
+    /// `d` 段目で区間 `[l, r)` のうちビット `b` の要素が次の段で占める区間
+    #[inline]
+    fn descend(&self, d: usize, b: bool, l: usize, r: usize) -> (usize, usize) {
+        let bv = &self.bits[d];
+        if b {
+            (self.zeros[d] + bv.rank1(l), self.zeros[d] + bv.rank1(r))
+        } else {
+            (bv.rank0(l), bv.rank0(r))
+        }
+    }
+
+    /// `d` 段目のビット
+    #[inline]
+    fn bit(&self, x: u64, d: usize) -> bool {
+        x >> (self.log - 1 - d) & 1 == 1
+    }
+
+    /// 値 `x` の要素が最下段で占める区間（`x` が表せない場合は `None`）
+    fn bottom_range(&self, x: u64, mut l: usize, mut r: usize) -> Option<(usize, usize)> {
+        if self.log < u64::BITS as usize && x >> self.log != 0 {
+            return None;
+        }
+        for d in 0..self.log {
+            (l, r) = self.descend(d, self.bit(x, d), l, r);
+        }
+        Some((l, r))
+    }
+
+    /// `i` 番目の要素
+    /// - 時間計算量: $`O(\log \max A)`$
+    pub fn access(&self, mut i: usize) -> u64 {
+        assert!(i < self.size, "index out of range: {i}");
+        let mut res = 0;
+        for d in 0..self.log {
+            let b = self.bits[d].get(i);
+            res = res << 1 | b as u64;
+            i = self.descend(d, b, i, i).0;
+        }
+        res
+    }
+
+    /// `[0, i)` に含まれる `x` の個数
+    /// - 時間計算量: $`O(\log \max A)`$
+    pub fn rank(&self, x: u64, i: usize) -> usize {
+        assert!(i <= self.size, "index out of range: {i}");
+        self.bottom_range(x, 0, i).map_or(0, |(l, r)| r - l)
+    }
+
+    /// `k` 番目（0-indexed）の `x` の位置
+    /// - 時間計算量: $`O(\log \max A \log N)`$
+    pub fn select(&self, x: u64, k: usize) -> Option<usize> {
+        let (l, r) = self.bottom_range(x, 0, self.size)?;
+        if r - l <= k {
+            return None;
+        }
+        let mut pos = l + k;
+        for d in (0..self.log).rev() {
+            let b = self.bit(x, d);
+            let offset = if b { self.zeros[d] } else { 0 };
+            pos = self.bits[d].select(b, pos - offset)?;
+        }
+        Some(pos)
+    }
+
+    /// 区間 `range` の中で `k` 番目（0-indexed）に小さい値
+    /// - 時間計算量: $`O(\log \max A)`$
+    pub fn kth_smallest<R: RangeBounds<usize> + Debug>(
+        &self,
+        range: R,
+        mut k: usize,
+    ) -> Option<u64> {
+        let (mut l, mut r) = self.parse_range(&range);
+        if r - l <= k {
+            return None;
+        }
+        let mut res = 0;
+        for d in 0..self.log {
+            let z = self.bits[d].rank0(r) - self.bits[d].rank0(l);
+            let b = k >= z;
+            if b {
+                k -= z;
+            }
+            res = res << 1 | b as u64;
+            (l, r) = self.descend(d, b, l, r);
+        }
+        Some(res)
+    }
+
+    /// 区間 `range` の中で `k` 番目（0-indexed）に大きい値
+    pub fn kth_largest<R: RangeBounds<usize> + Debug>(&self, range: R, k: usize) -> Option<u64> {
+        let (l, r) = self.parse_range(&range);
+        if r - l <= k {
+            return None;
+        }
+        self.kth_smallest(l..r, r - l - 1 - k)
+    }
+
+    /// 区間 `[l, r)` の中で `upper` 未満の値の個数
+    fn count_less(&self, mut l: usize, mut r: usize, upper: u64) -> usize {
+        if self.log < u64::BITS as usize && upper >> self.log != 0 {
+            return r - l;
+        }
+        let mut res = 0;
+        for d in 0..self.log {
+            let b = self.bit(upper, d);
+            if b {
+                res += self.bits[d].rank0(r) - self.bits[d].rank0(l);
+            }
+            (l, r) = self.descend(d, b, l, r);
+        }
+        res
+    }
+
+    /// 値の範囲 `values` を `[lo, hi)` に変換する（`hi = None` は上限なし）
+    fn parse_values<V: RangeBounds<u64>>(values: &V) -> (u64, Option<u64>) {
+        let lo = match values.start_bound() {
+            Unbounded => 0,
+            Included(&v) => v,
+            Excluded(&v) => v.saturating_add(1),
+        };
+        let hi = match values.end_bound() {
+            Unbounded => None,
+            Included(&v) => v.checked_add(1),
+            Excluded(&v) => Some(v),
+        };
+        (lo, hi)
+    }
+
+    /// 区間 `range` の中で値が `values` に含まれる要素の個数
+    /// - 時間計算量: $`O(\log \max A)`$
+    pub fn range_freq<R, V>(&self, range: R, values: V) -> usize
+    where
+        R: RangeBounds<usize> + Debug,
+        V: RangeBounds<u64>,
+    {
+        let (l, r) = self.parse_range(&range);
+        let (lo, hi) = Self::parse_values(&values);
+        let below_hi = hi.map_or(r - l, |hi| self.count_less(l, r, hi));
+        below_hi.saturating_sub(self.count_less(l, r, lo))
+    }
+
+    /// 区間 `range` の中で `upper` 未満の最大の値
+    /// - 時間計算量: $`O(\log \max A)`$
+    pub fn prev_value<R: RangeBounds<usize> + Debug>(&self, range: R, upper: u64) -> Option<u64> {
+        let (l, r) = self.parse_range(&range);
+        let cnt = self.count_less(l, r, upper);
+        cnt.checked_sub(1).and_then(|k| self.kth_smallest(l..r, k))
+    }
+
+    /// 区間 `range` の中で `lower` 以上の最小の値
+    /// - 時間計算量: $`O(\log \max A)`$
+    pub fn next_value<R: RangeBounds<usize> + Debug>(&self, range: R, lower: u64) -> Option<u64> {
+        let (l, r) = self.parse_range(&range);
+        let cnt = self.count_less(l, r, lower);
+        self.kth_smallest(l..r, cnt)
+    }
+
+    /// 区間 `range` の中で出現回数が多い順に `k` 個の `(値, 出現回数)`
+    ///
+    /// 出現回数が同じ場合は値の昇順
+    /// - 時間計算量: $`O(k \log \max A \log k)`$
+    pub fn top_k<R: RangeBounds<usize> + Debug>(&self, range: R, k: usize) -> Vec<(u64, usize)> {
+        let (l, r) = self.parse_range(&range);
+        let mut res = vec![];
+        // (個数, 値の上位ビット, 段, l)
+        let mut heap = BinaryHeap::new();
+        if l < r {
+            heap.push((r - l, Reverse(0_u64), 0, l));
+        }
+        while let Some((cnt, Reverse(val), d, l)) = heap.pop() {
+            if res.len() >= k {
+                break;
+            }
+            if d == self.log {
+                res.push((val, cnt));
+                continue;
+            }
+            let r = l + cnt;
+            for b in [false, true] {
+                let (nl, nr) = self.descend(d, b, l, r);
+                if nl < nr {
+                    heap.push((nr - nl, Reverse(val << 1 | b as u64), d + 1, nl));
+                }
+            }
+        }
+        res
+    }
+}
+
+/// 区間の小さい方から $`k`$ 個の和を求められるウェーブレット行列
+///
+/// ```
+/// use cp_library_rs::data_structure::wavelet_matrix::WaveletMatrixSum;
+///
+/// let wm = WaveletMatrixSum::new(&[5, 4, 5, 5, 2, 1, 5, 6]);
+/// assert_eq!(wm.sum_smallest(3..7, 2), 1 + 2);
+/// assert_eq!(wm.sum_largest(.., 3), 6 + 5 + 5);
+/// assert_eq!(wm.kth_smallest(.., 0), Some(1));
+/// ```
+#[derive(Debug, Clone)]
+pub struct WaveletMatrixSum {
+    wm: WaveletMatrix,
+    /// `sums[d]`：`d` 段目の入力の並びの累積和
+    sums: Vec<Vec<u64>>,
+}
+
+impl std::ops::Deref for WaveletMatrixSum {
+    type Target = WaveletMatrix;
+    fn deref(&self) -> &Self::Target {
+        &self.wm
+    }
+}
+
+impl WaveletMatrixSum {
+    /// 配列から構築する
+    /// - 時間計算量: $`O(N \log \max A)`$
+    pub fn new(src: &[u64]) -> Self {
+        let (wm, levels) = WaveletMatrix::build(src);
+        let sums = levels
+            .iter()
+            .map(|level| {
+                let mut acc = vec![0; level.len() + 1];
+                for (i, &v) in level.iter().enumerate() {
+                    acc[i + 1] = acc[i] + v;
+                }
+                acc
+            })
+            .collect();
+        Self { wm, sums }
+    }
+
+    /// 区間 `range` の中で小さい方から `k` 個の和（`k` が要素数より大きい場合は全体の和）
+    /// - 時間計算量: $`O(\log \max A)`$
+    pub fn sum_smallest<R: RangeBounds<usize> + Debug>(&self, range: R, k: usize) -> u64 {
+        let (mut l, mut r) = self.wm.parse_range(&range);
+        let mut k = k.min(r - l);
+        let mut res = 0;
+        for d in 0..self.wm.log {
+            let (zl, zr) = self.wm.descend(d, false, l, r);
+            if k < zr - zl {
+                (l, r) = (zl, zr);
+            } else {
+                res += self.sums[d + 1][zr] - self.sums[d + 1][zl];
+                k -= zr - zl;
+                (l, r) = self.wm.descend(d, true, l, r);
+            }
+        }
+        let bottom = &self.sums[self.wm.log];
+        res + bottom[l + k] - bottom[l]
+    }
+
+    /// 区間 `range` の中で大きい方から `k` 個の和（`k` が要素数より大きい場合は全体の和）
+    pub fn sum_largest<R: RangeBounds<usize> + Debug>(&self, range: R, k: usize) -> u64 {
+        let (l, r) = self.wm.parse_range(&range);
+        let k = k.min(r - l);
+        self.sum_smallest(l..r, r - l) - self.sum_smallest(l..r, r - l - k)
+    }
+}
+
+/// 座標圧縮した値で構築するウェーブレット行列
+///
+/// ```
+/// use cp_library_rs::data_structure::wavelet_matrix::CompressedWaveletMatrix;
+///
+/// let src = [-5, 100, 7, -5, 3];
+/// let wm = CompressedWaveletMatrix::new(&src);
+/// assert_eq!(wm.access(1), &100);
+/// assert_eq!(wm.kth_smallest(.., 2), Some(&3));
+/// assert_eq!(wm.range_freq(1.., 0..50), 2);
+/// assert_eq!(wm.next_value(.., &4), Some(&7));
+/// ```
+#[derive(Debug)]
+pub struct CompressedWaveletMatrix<'a, T> {
+    comp: Compression<'a, T>,
+    wm: WaveletMatrix,
+}
+
+impl<'a, T: Ord> CompressedWaveletMatrix<'a, T> {
+    /// 配列から構築する
+    /// - 時間計算量: $`O(N \log N)`$
+    pub fn new(src: &'a [T]) -> Self {
+        let comp = Compression::new(src);
+        let idx: Vec<u64> = src.iter().map(|v| comp.idx(v).unwrap() as u64).collect();
+        Self {
+            comp,
+            wm: WaveletMatrix::new(&idx),
+        }
+    }
+
+    /// `x` 未満の値の圧縮後の番号の個数
+    fn lower_bound(&self, x: &T) -> u64 {
+        self.comp.sorted_array.partition_point(|&v| v < x) as u64
+    }
+
+    /// 要素数
+    pub fn len(&self) -> usize {
+        self.wm.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.wm.size == 0
+    }
+
+    /// `i` 番目の要素
+    pub fn access(&self, i: usize) -> &T {
+        self.comp.val(self.wm.access(i) as usize).unwrap()
+    }
+
+    /// `[0, i)` に含まれる `x` の個数
+    pub fn rank(&self, x: &T, i: usize) -> usize {
+        self.comp
+            .idx(x)
+            .map_or(0, |idx| self.wm.rank(idx as u64, i))
+    }
+
+    /// `k` 番目（0-indexed）の `x` の位置
+    pub fn select(&self, x: &T, k: usize) -> Option<usize> {
+        self.wm.select(self.comp.idx(x)? as u64, k)
+    }
+
+    /// 区間 `range` の中で `k` 番目（0-indexed）に小さい値
+    pub fn kth_smallest<R: RangeBounds<usize> + Debug>(&self, range: R, k: usize) -> Option<&T> {
+        let idx = self.wm.kth_smallest(range, k)?;
+        self.comp.val(idx as usize)
+    }
+
+    /// 区間 `range` の中で `k` 番目（0-indexed）に大きい値
+    pub fn kth_largest<R: RangeBounds<usize> + Debug>(&self, range: R, k: usize) -> Option<&T> {
+        let idx = self.wm.kth_largest(range, k)?;
+        self.comp.val(idx as usize)
+    }
+
+    /// 区間 `range` の中で値が `values` に含まれる要素の個数
+    pub fn range_freq<R, V>(&self, range: R, values: V) -> usize
+    where
+        R: RangeBounds<usize> + Debug,
+        V: RangeBounds<T>,
+    {
+        let lo = match values.start_bound() {
+            Unbounded => 0,
+            Included(v) => self.lower_bound(v),
+            Excluded(v) => self.lower_bound(v) + self.comp.idx(v).is_some() as u64,
+        };
+        let hi = match values.end_bound() {
+            Unbounded => self.comp.size as u64,
+            Included(v) => self.lower_bound(v) + self.comp.idx(v).is_some() as u64,
+            Excluded(v) => self.lower_bound(v),
+        };
+        self.wm.range_freq(range, lo..hi)
+    }
+
+    /// 区間 `range` の中で `upper` 未満の最大の値
+    pub fn prev_value<R: RangeBounds<usize> + Debug>(&self, range: R, upper: &T) -> Option<&T> {
+        let idx = self.wm.prev_value(range, self.lower_bound(upper))?;
+        self.comp.val(idx as usize)
+    }
+
+    /// 区間 `range` の中で `lower` 以上の最小の値
+    pub fn next_value<R: RangeBounds<usize> + Debug>(&self, range: R, lower: &T) -> Option<&T> {
+        let idx = self.wm.next_value(range, self.lower_bound(lower))?;
+        self.comp.val(idx as usize)
+    }
+
+    /// 区間 `range` の中で出現回数が多い順に `k` 個の `(値, 出現回数)`
+    pub fn top_k<R: RangeBounds<usize> + Debug>(&self, range: R, k: usize) -> Vec<(&T, usize)> {
+        self.wm
+            .top_k(range, k)
+            .into_iter()
+            .map(|(idx, cnt)| (self.comp.val(idx as usize).unwrap(), cnt))
+            .collect()
+    }
+}
@@ -140,7 +140,7 @@ impl HLD {
     /// **戻り値**
     /// - `(usize, usize)` : (左, 右)
     #[inline]
-    pub fn get_subtree<T, F>(&self, u: usize) -> (usize, usize) {
+    pub fn get_subtree(&self, u: usize) -> (usize, usize) {
         (self.in_[u], self.out[u])
     }
 
@@ -10,6 +10,7 @@ pub mod lca_doubling;
 pub mod loop_detection;
 pub mod loop_detection_fold;
 pub mod namori;
+pub mod offline_dynamic_connectivity;
 pub mod rerooting;
 pub mod scc;
 pub mod simple_graph;
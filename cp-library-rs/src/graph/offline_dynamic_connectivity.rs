@@ -0,0 +1,170 @@
+//! オフライン動的連結性判定
+//!
+//! 辺の追加・削除と，連結性・連結成分数のクエリをまとめて処理する．
+//! 時間軸上のセグメント木に辺の生存区間を載せ，[`RollbackUnionFind`] を DFS しながら併合・巻き戻しする．
+
+use std::collections::HashMap;
+
+use crate::data_structure::rollback_unionfind::RollbackUnionFind;
+
+/// オフライン動的連結性判定
+///
+/// - 時間計算量: $`O(Q \log Q \log N)`$（$`Q`$ は操作の回数）
+///
+/// ```
+/// use cp_library_rs::graph::offline_dynamic_connectivity::*;
+///
+/// let mut dc = OfflineDynamicConnectivity::new(3);
+/// dc.add_edge(0, 1);
+/// let q0 = dc.is_same(0, 1);
+/// dc.add_edge(1, 2);
+/// dc.remove_edge(0, 1);
+/// let q1 = dc.is_same(0, 1);
+/// let q2 = dc.group_count();
+///
+/// let ans = dc.solve();
+/// assert_eq!(ans[q0], 1);
+/// assert_eq!(ans[q1], 0);
+/// assert_eq!(ans[q2], 2);
+/// ```
+#[derive(Debug)]
+pub struct OfflineDynamicConnectivity {
+    /// 頂点数
+    n: usize,
+    /// クエリ（`None` は連結成分数）
+    queries: Vec<Option<(usize, usize)>>,
+    /// 生存中の辺 → 追加されたクエリ時刻（多重辺の分だけ積む）
+    alive: HashMap<(usize, usize), Vec<usize>>,
+    /// 辺の生存区間 `(l, r, u, v)`（クエリ時刻 `l..r` の間存在する）
+    intervals: Vec<(usize, usize, usize, usize)>,
+}
+
+impl OfflineDynamicConnectivity {
+    /// 頂点数 `n` の空グラフ
+    pub fn new(n: usize) -> Self {
+        Self {
+            n,
+            queries: vec![],
+            alive: HashMap::default(),
+            intervals: vec![],
+        }
+    }
+
+    fn normalize(u: usize, v: usize) -> (usize, usize) {
+        (u.min(v), u.max(v))
+    }
+
+    /// 辺 `(u, v)` を追加する（多重辺も可）
+    pub fn add_edge(&mut self, u: usize, v: usize) {
+        let t = self.queries.len();
+        self.alive.entry(Self::normalize(u, v)).or_default().push(t);
+    }
+
+    /// 辺 `(u, v)` を1本削除する
+    ///
+    /// 辺が存在しない場合は panic する
+    pub fn remove_edge(&mut self, u: usize, v: usize) {
+        let (u, v) = Self::normalize(u, v);
+        let l = self
+            .alive
+            .get_mut(&(u, v))
+            .and_then(|times| times.pop())
+            .unwrap_or_else(|| panic!("edge ({u}, {v}) does not exist"));
+        let r = self.queries.len();
+        if l < r {
+            self.intervals.push((l, r, u, v));
+        }
+    }
+
+    /// 頂点 `u, v` が連結かを問うクエリを追加する
+    ///
+    /// **戻り値**
+    /// - クエリの番号（[`OfflineDynamicConnectivity::solve`] の戻り値の添字）
+    pub fn is_same(&mut self, u: usize, v: usize) -> usize {
+        self.queries.push(Some((u, v)));
+        self.queries.len() - 1
+    }
+
+    /// 連結成分の数を問うクエリを追加する
+    ///
+    /// **戻り値**
+    /// - クエリの番号（[`OfflineDynamicConnectivity::solve`] の戻り値の添字）
+    pub fn group_count(&mut self) -> usize {
+        self.queries.push(None);
+        self.queries.len() - 1
+    }
+
+    /// すべてのクエリに答える
+    ///
+    /// **戻り値**
+    /// - クエリの番号順の答え（連結性は `1` / `0`，連結成分数はその値）
+    pub fn solve(mut self) -> Vec<usize> {
+        let q = self.queries.len();
+        if q == 0 {
+            return vec![];
+        }
+
+        // 最後まで残った辺
+        for (&(u, v), times) in &self.alive {
+            for &l in times {
+                if l < q {
+                    self.intervals.push((l, q, u, v));
+                }
+            }
+        }
+
+        // 時間軸上のセグメント木に辺を載せる
+        let size = q.next_power_of_two();
+        let mut edges = vec![vec![]; 2 * size];
+        for &(l, r, u, v) in &self.intervals {
+            let (mut l, mut r) = (l + size, r + size);
+            while l < r {
+                if l & 1 == 1 {
+                    edges[l].push((u, v));
+                    l += 1;
+                }
+                if r & 1 == 1 {
+                    r -= 1;
+                    edges[r].push((u, v));
+                }
+                l >>= 1;
+                r >>= 1;
+            }
+        }
+
+        let mut uf = RollbackUnionFind::new(self.n);
+        let mut ans = vec![0; q];
+        self.dfs(1, 0, size, &edges, &mut uf, &mut ans);
+        ans
+    }
+
+    /// ノード `node`（担当する時刻は `l..r`）以下を走査する
+    fn dfs(
+        &self,
+        node: usize,
+        l: usize,
+        r: usize,
+        edges: &[Vec<(usize, usize)>],
+        uf: &mut RollbackUnionFind,
+        ans: &mut [usize],
+    ) {
+        if l >= self.queries.len() {
+            return;
+        }
+        let snapshot = uf.snapshot();
+        for &(u, v) in &edges[node] {
+            uf.unite(u, v);
+        }
+        if r - l == 1 {
+            ans[l] = match self.queries[l] {
+                Some((u, v)) => uf.is_same(u, v) as usize,
+                None => uf.group_count(),
+            };
+        } else {
+            let mid = (l + r) / 2;
+            self.dfs(2 * node, l, mid, edges, uf, ans);
+            self.dfs(2 * node + 1, mid, r, edges, uf, ans);
+        }
+        uf.rollback(snapshot);
+    }
+}
@@ -0,0 +1,350 @@
+//! インタラクティブ問題用の入出力
+//!
+//! - [`InteractiveReader`]：必要になった行だけを読み込む（[`scan!`](crate::scan) の `@from` でも読める）
+//! - 出力は [`Output::interactive`] を使う（改行のたびに flush する）
+//! - [`run_interactive`]：解答とインタラクタをメモリ上のパイプでつないで実行する
+
+use std::{
+    fmt,
+    io::{self, BufRead, BufReader, Read, StdinLock, Write},
+    str::FromStr,
+    sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender},
+    thread,
+    time::Duration,
+};
+
+use crate::io::{output::Output, scanner::Readable};
+
+/// 1行ずつ読み込むリーダー
+///
+/// 現在の行のトークンを使い切るまで，次の行を読まない
+#[derive(Debug)]
+pub struct InteractiveReader<R: BufRead> {
+    reader: R,
+    line: Vec<u8>,
+    pos: usize,
+    /// 読み込みが時間切れになったか
+    timed_out: bool,
+}
+
+impl InteractiveReader<StdinLock<'static>> {
+    /// 標準入力から読み込む
+    pub fn stdin() -> Self {
+        Self::new(io::stdin().lock())
+    }
+}
+
+impl<R: BufRead> InteractiveReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: vec![],
+            pos: 0,
+            timed_out: false,
+        }
+    }
+
+    /// 次の1行を読み込む（入力の終わりに達した場合は `false`）
+    fn fill_line(&mut self) -> bool {
+        self.line.clear();
+        self.pos = 0;
+        match self.reader.read_until(b'\n', &mut self.line) {
+            Ok(n) => n > 0,
+            Err(err) => {
+                self.timed_out = err.kind() == io::ErrorKind::TimedOut;
+                false
+            }
+        }
+    }
+
+    /// 現在の行の残りが空白だけか
+    fn is_line_consumed(&self) -> bool {
+        self.line[self.pos..].iter().all(u8::is_ascii_whitespace)
+    }
+
+    /// 現在の行の残りのトークン
+    fn rest_of_line(&mut self) -> Vec<&[u8]> {
+        let start = self.pos;
+        self.pos = self.line.len();
+        self.line[start..]
+            .split(u8::is_ascii_whitespace)
+            .filter(|token| !token.is_empty())
+            .collect()
+    }
+
+    /// 次のトークン（現在の行になければ，次の行を読み込む）
+    ///
+    /// **戻り値**
+    /// - 入力の終わりに達した場合は `None`
+    pub fn next_token(&mut self) -> Option<&[u8]> {
+        loop {
+            while self.pos < self.line.len() && self.line[self.pos].is_ascii_whitespace() {
+                self.pos += 1;
+            }
+            if self.pos < self.line.len() {
+                break;
+            }
+            if !self.fill_line() {
+                return None;
+            }
+        }
+        let start = self.pos;
+        while self.pos < self.line.len() && !self.line[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+        Some(&self.line[start..self.pos])
+    }
+
+    /// 次のトークン
+    ///
+    /// 入力の終わりに達した場合は panic する
+    pub fn token(&mut self) -> &[u8] {
+        self.next_token().expect("unexpected end of input")
+    }
+
+    /// 次のトークンを `T` に変換する
+    pub fn read<T: Readable>(&mut self) -> T {
+        T::parse(self.token())
+    }
+
+    /// 1行分のトークン
+    ///
+    /// 現在の行の残りが空白だけの場合は，次の行を読み込む
+    pub fn line(&mut self) -> Vec<&[u8]> {
+        if self.is_line_consumed() {
+            self.fill_line();
+        }
+        self.rest_of_line()
+    }
+}
+
+/// パイプの書き込み側（drop すると読み込み側は入力の終わりになる）
+#[derive(Debug)]
+pub struct PipeWriter(Sender<Vec<u8>>);
+
+/// パイプの読み込み側
+#[derive(Debug)]
+pub struct PipeReader {
+    rx: Receiver<Vec<u8>>,
+    buf: Vec<u8>,
+    pos: usize,
+    timeout: Duration,
+}
+
+/// メモリ上のパイプ
+///
+/// `timeout` の間に書き込みがなければ，読み込みは `TimedOut` のエラーになる
+pub fn pipe(timeout: Duration) -> (PipeWriter, PipeReader) {
+    let (tx, rx) = channel();
+    let reader = PipeReader {
+        rx,
+        buf: vec![],
+        pos: 0,
+        timeout,
+    };
+    (PipeWriter(tx), reader)
+}
+
+impl Write for PipeWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0
+            .send(buf.to_vec())
+            .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?;
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Read for PipeReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl BufRead for PipeReader {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        while self.pos == self.buf.len() {
+            match self.rx.recv_timeout(self.timeout) {
+                Ok(data) => {
+                    self.buf = data;
+                    self.pos = 0;
+                }
+                Err(RecvTimeoutError::Disconnected) => return Ok(&[]),
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(io::Error::from(io::ErrorKind::TimedOut))
+                }
+            }
+        }
+        Ok(&self.buf[self.pos..])
+    }
+    fn consume(&mut self, amt: usize) {
+        self.pos += amt;
+    }
+}
+
+/// [`run_interactive`] の解答側の入力
+pub type SolutionReader = InteractiveReader<BufReader<PipeReader>>;
+/// [`run_interactive`] の解答側の出力
+pub type SolutionWriter = Output<PipeWriter>;
+
+/// インタラクティブ問題の判定エラー
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InteractiveError {
+    /// クエリの回数が上限を超えた
+    QueryLimitExceeded { limit: usize },
+    /// 不正なクエリ（形式の誤りを含む）
+    InvalidQuery(String),
+    /// 誤った解答
+    WrongAnswer(String),
+    /// 解答の出力が途中で終わった
+    UnexpectedEof,
+    /// 解答が応答しない
+    Timeout,
+    /// 解答が panic した
+    SolutionPanicked(String),
+}
+
+impl fmt::Display for InteractiveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::QueryLimitExceeded { limit } => write!(f, "query limit exceeded: {limit}"),
+            Self::InvalidQuery(msg) => write!(f, "invalid query: {msg}"),
+            Self::WrongAnswer(msg) => write!(f, "wrong answer: {msg}"),
+            Self::UnexpectedEof => write!(f, "unexpected end of output"),
+            Self::Timeout => write!(f, "solution did not respond"),
+            Self::SolutionPanicked(msg) => write!(f, "solution panicked: {msg}"),
+        }
+    }
+}
+
+/// インタラクタ側の入出力
+pub struct Judge {
+    reader: InteractiveReader<BufReader<PipeReader>>,
+    /// 解答への出力
+    pub out: Output<PipeWriter>,
+    queries: usize,
+    limit: Option<usize>,
+}
+
+impl Judge {
+    /// 解答の出力から次のトークンを読んで変換する
+    ///
+    /// 出力が終わっている場合や変換できない場合はエラーを返す
+    pub fn read<T: FromStr>(&mut self) -> Result<T, InteractiveError> {
+        let token = match self.reader.next_token() {
+            Some(token) => String::from_utf8_lossy(token).to_string(),
+            None => return Err(self.eof_error()),
+        };
+        token
+            .parse()
+            .map_err(|_| InteractiveError::InvalidQuery(format!("cannot parse {token:?}")))
+    }
+
+    /// 解答の出力から，空でない1行を読む
+    pub fn read_line(&mut self) -> Result<Vec<String>, InteractiveError> {
+        while self.reader.is_line_consumed() {
+            if !self.reader.fill_line() {
+                return Err(self.eof_error());
+            }
+        }
+        Ok(self
+            .reader
+            .rest_of_line()
+            .iter()
+            .map(|token| String::from_utf8_lossy(token).to_string())
+            .collect())
+    }
+
+    /// クエリを1回数える（上限を超えた場合はエラー）
+    pub fn count_query(&mut self) -> Result<(), InteractiveError> {
+        self.queries += 1;
+        match self.limit {
+            Some(limit) if self.queries > limit => {
+                Err(InteractiveError::QueryLimitExceeded { limit })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// これまでのクエリの回数
+    pub fn queries(&self) -> usize {
+        self.queries
+    }
+
+    fn eof_error(&self) -> InteractiveError {
+        if self.reader.timed_out {
+            InteractiveError::Timeout
+        } else {
+            InteractiveError::UnexpectedEof
+        }
+    }
+}
+
+/// 解答が応答しない場合に諦めるまでの時間
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 解答 `solution` とインタラクタ `interactor` をつないで実行する
+///
+/// - `solution` は別スレッドで実行する
+/// - `interactor` はクエリごとに [`Judge::count_query`] を呼び，不正なクエリや誤答ではエラーを返す
+///
+/// **戻り値**
+/// - 正解した場合は，使ったクエリの回数
+/// - 判定エラー（解答が panic した場合は [`InteractiveError::SolutionPanicked`]）
+pub fn run_interactive<S, I>(
+    query_limit: Option<usize>,
+    solution: S,
+    interactor: I,
+) -> Result<usize, InteractiveError>
+where
+    S: FnOnce(&mut SolutionReader, &mut SolutionWriter) + Send,
+    I: FnOnce(&mut Judge) -> Result<(), InteractiveError>,
+{
+    let (to_solution, solution_input) = pipe(TIMEOUT);
+    let (solution_output, from_solution) = pipe(TIMEOUT);
+
+    thread::scope(|scope| {
+        let handle = scope.spawn(move || {
+            let mut reader = InteractiveReader::new(BufReader::new(solution_input));
+            let mut writer = Output::interactive(solution_output);
+            solution(&mut reader, &mut writer);
+        });
+
+        let mut judge = Judge {
+            reader: InteractiveReader::new(BufReader::new(from_solution)),
+            out: Output::interactive(to_solution),
+            queries: 0,
+            limit: query_limit,
+        };
+        let result = interactor(&mut judge).map(|()| judge.queries);
+        // パイプを閉じて，入力を待っている解答を終了させる
+        drop(judge);
+
+        let panicked = handle.join().err().map(|err| {
+            err.downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| err.downcast_ref::<String>().cloned())
+                .unwrap_or_default()
+        });
+
+        match (result, panicked) {
+            // インタラクタが先に判定した場合は，その判定を優先する
+            (Err(err), _)
+                if !matches!(
+                    err,
+                    InteractiveError::UnexpectedEof | InteractiveError::Timeout
+                ) =>
+            {
+                Err(err)
+            }
+            (_, Some(msg)) => Err(InteractiveError::SolutionPanicked(msg)),
+            (result, None) => result,
+        }
+    })
+}
@@ -0,0 +1,5 @@
+//! ## 入出力
+
+pub mod interactive;
+pub mod output;
+pub mod scanner;
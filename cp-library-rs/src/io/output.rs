@@ -0,0 +1,175 @@
+//! バッファ付きの出力
+//!
+//! - 出力をまとめて書き込み，drop 時に flush する
+//! - 値の並びの出力には [`put!`](crate::put) マクロを使う
+
+use std::io::{self, BufWriter, StdoutLock, Write};
+
+/// 出力できる型
+///
+/// - ライブラリの型（`Modint` など）の実装は，それぞれの型を定義するモジュールにある
+pub trait Writable {
+    /// `out` に書き込む（`precision` は浮動小数点数の小数点以下の桁数）
+    fn write_to<W: Write>(&self, out: &mut W, precision: Option<usize>) -> io::Result<()>;
+}
+
+macro_rules! impl_writable_display {
+    ( $($t:ty),* ) => {$(
+        impl Writable for $t {
+            fn write_to<W: Write>(&self, out: &mut W, _: Option<usize>) -> io::Result<()> {
+                write!(out, "{}", self)
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_writable_float {
+    ( $($t:ty),* ) => {$(
+        impl Writable for $t {
+            fn write_to<W: Write>(&self, out: &mut W, precision: Option<usize>) -> io::Result<()> {
+                match precision {
+                    Some(p) => write!(out, "{:.*}", p, self),
+                    None => write!(out, "{}", self),
+                }
+            }
+        }
+    )*};
+}
+
+impl_writable_display!(u8, u16, u32, u64, u128, usize);
+impl_writable_display!(i8, i16, i32, i64, i128, isize);
+impl_writable_display!(char, str, String);
+impl_writable_float!(f32, f64);
+
+impl<T: Writable + ?Sized> Writable for &T {
+    fn write_to<W: Write>(&self, out: &mut W, precision: Option<usize>) -> io::Result<()> {
+        (**self).write_to(out, precision)
+    }
+}
+
+/// バッファ付きの出力
+///
+/// ```
+/// use cp_library_rs::io::output::Output;
+///
+/// let mut buf = vec![];
+/// {
+///     let mut out = Output::new(&mut buf);
+///     out.precision = Some(3);
+///     out.join(&[1.0, 2.5], " ");
+///     out.yesno(true);
+/// }
+/// assert_eq!(String::from_utf8(buf).unwrap(), "1.000 2.500\nYes\n");
+/// ```
+pub struct Output<W: Write> {
+    out: BufWriter<W>,
+    /// 浮動小数点数の小数点以下の桁数（`None` のときは `Display` のまま）
+    pub precision: Option<usize>,
+    /// 改行のたびに flush するか（インタラクティブ問題用）
+    pub auto_flush: bool,
+}
+
+impl Output<StdoutLock<'static>> {
+    /// 標準出力をロックして書き込む
+    ///
+    /// 同じスレッドから `println!` を混ぜると出力順が入れ替わるため注意
+    pub fn stdout() -> Self {
+        Self::new(io::stdout().lock())
+    }
+
+    /// 標準出力に，改行のたびに flush しながら書き込む（インタラクティブ問題用）
+    pub fn stdout_interactive() -> Self {
+        Self::interactive(io::stdout().lock())
+    }
+}
+
+impl<W: Write> Output<W> {
+    pub fn new(out: W) -> Self {
+        Self {
+            out: BufWriter::new(out),
+            precision: None,
+            auto_flush: false,
+        }
+    }
+
+    /// 改行のたびに flush する
+    pub fn interactive(out: W) -> Self {
+        let mut res = Self::new(out);
+        res.auto_flush = true;
+        res
+    }
+
+    /// 値を1つ出力する（改行しない）
+    pub fn put<T: Writable + ?Sized>(&mut self, value: &T) {
+        value.write_to(&mut self.out, self.precision).unwrap();
+    }
+
+    /// 文字列をそのまま出力する
+    pub fn str(&mut self, s: &str) {
+        self.out.write_all(s.as_bytes()).unwrap();
+    }
+
+    /// 改行する（`auto_flush` のときは flush する）
+    pub fn endl(&mut self) {
+        self.str("\n");
+        if self.auto_flush {
+            self.flush();
+        }
+    }
+
+    /// 値を1つ出力して改行する
+    pub fn line<T: Writable + ?Sized>(&mut self, value: &T) {
+        self.put(value);
+        self.endl();
+    }
+
+    /// 値を `sep` 区切りで出力して改行する
+    pub fn join<I>(&mut self, values: I, sep: &str)
+    where
+        I: IntoIterator,
+        I::Item: Writable,
+    {
+        for (i, value) in values.into_iter().enumerate() {
+            if i > 0 {
+                self.str(sep);
+            }
+            self.put(&value);
+        }
+        self.endl();
+    }
+
+    /// 値を1行に1つずつ出力する
+    pub fn lines<I>(&mut self, values: I)
+    where
+        I: IntoIterator,
+        I::Item: Writable,
+    {
+        for value in values {
+            self.line(&value);
+        }
+    }
+
+    /// 2次元配列の各行を `sep` 区切りで出力する
+    pub fn grid<T: Writable>(&mut self, grid: &[Vec<T>], sep: &str) {
+        for row in grid {
+            self.join(row, sep);
+        }
+    }
+
+    /// `true` → `Yes`，`false` → `No` を出力して改行する
+    pub fn yesno(&mut self, b: bool) {
+        self.line(if b { "Yes" } else { "No" });
+    }
+
+    /// バッファの内容を書き出す
+    pub fn flush(&mut self) {
+        self.out.flush().unwrap();
+    }
+}
+
+impl<W: Write> Drop for Output<W> {
+    fn drop(&mut self) {
+        // panic 中に drop された場合に備えて，エラーは無視する
+        let _ = self.out.flush();
+    }
+}
@@ -0,0 +1,173 @@
+//! 入力全体をまとめて読み込むスキャナ
+//!
+//! - 入力を一度だけ読み込み，バイト列のままトークンに分割・変換する
+//! - 標準入力からの読み込みには [`scan!`](crate::scan) マクロを使う
+
+use std::{cell::RefCell, io::Read};
+
+use crate::number_theory::modint::Modint;
+
+/// バイト列から変換できる型
+pub trait Readable: Sized {
+    /// 空白を含まないトークンから変換する
+    fn parse(token: &[u8]) -> Self;
+}
+
+macro_rules! impl_readable_unsigned {
+    ( $($t:ty),* ) => {$(
+        impl Readable for $t {
+            fn parse(token: &[u8]) -> Self {
+                let token = token.strip_prefix(b"+").unwrap_or(token);
+                token.iter().fold(0, |acc, &c| {
+                    debug_assert!(c.is_ascii_digit(), "invalid digit: {}", c as char);
+                    acc * 10 + (c - b'0') as $t
+                })
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_readable_signed {
+    ( $($t:ty),* ) => {$(
+        impl Readable for $t {
+            fn parse(token: &[u8]) -> Self {
+                // 最小値もオーバーフローしないよう，負の方向に累積する
+                let (neg, digits) = match token.split_first() {
+                    Some((b'-', rest)) => (true, rest),
+                    Some((b'+', rest)) => (false, rest),
+                    _ => (false, token),
+                };
+                let res = digits.iter().fold(0, |acc: $t, &c| {
+                    debug_assert!(c.is_ascii_digit(), "invalid digit: {}", c as char);
+                    acc * 10 - (c - b'0') as $t
+                });
+                if neg {
+                    res
+                } else {
+                    -res
+                }
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_readable_fromstr {
+    ( $($t:ty),* ) => {$(
+        impl Readable for $t {
+            fn parse(token: &[u8]) -> Self {
+                std::str::from_utf8(token).unwrap().parse().unwrap()
+            }
+        }
+    )*};
+}
+
+impl_readable_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_readable_signed!(i8, i16, i32, i64, i128, isize);
+impl_readable_fromstr!(f32, f64, char, String);
+
+impl<const MOD: usize> Readable for Modint<MOD> {
+    /// 10進数の各桁から直接 $`\bmod`$ をとる（`u64` に収まらない値や負の値も読める）
+    fn parse(token: &[u8]) -> Self {
+        let (neg, digits) = match token.split_first() {
+            Some((b'-', rest)) => (true, rest),
+            _ => (false, token),
+        };
+        let res = digits
+            .iter()
+            .fold(0, |acc, &c| (acc * 10 + (c - b'0') as usize) % MOD);
+        if neg {
+            -Modint(res)
+        } else {
+            Modint(res)
+        }
+    }
+}
+
+/// 入力全体を保持し，先頭から順に読み進める
+#[derive(Debug, Clone)]
+pub struct Scanner {
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+impl Scanner {
+    /// `reader` の内容をすべて読み込む
+    pub fn new<R: Read>(mut reader: R) -> Self {
+        let mut buf = vec![];
+        reader.read_to_end(&mut buf).unwrap();
+        Self { buf, pos: 0 }
+    }
+
+    /// 次のトークン（空白・改行を読み飛ばす）
+    ///
+    /// **戻り値**
+    /// - 入力の終わりに達した場合は `None`
+    pub fn next_token(&mut self) -> Option<&[u8]> {
+        while self.pos < self.buf.len() && self.buf[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+        if self.pos == self.buf.len() {
+            return None;
+        }
+        let start = self.pos;
+        while self.pos < self.buf.len() && !self.buf[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+        Some(&self.buf[start..self.pos])
+    }
+
+    /// 次のトークン
+    ///
+    /// 入力の終わりに達した場合は panic する
+    pub fn token(&mut self) -> &[u8] {
+        self.next_token().expect("unexpected end of input")
+    }
+
+    /// 次のトークンを `T` に変換する
+    pub fn read<T: Readable>(&mut self) -> T {
+        T::parse(self.token())
+    }
+
+    /// 1行分のトークン
+    ///
+    /// 読みかけの行の残りが空白だけの場合は，次の行を読む（`get!` の1回の `read_line` に対応）
+    pub fn line(&mut self) -> Vec<&[u8]> {
+        let at_line_start = self.pos == 0 || self.buf[self.pos - 1] == b'\n';
+        if !at_line_start {
+            let rest = self.line_end(self.pos);
+            if self.buf[self.pos..rest].iter().all(u8::is_ascii_whitespace) {
+                self.pos = (rest + 1).min(self.buf.len());
+            }
+        }
+        let end = self.line_end(self.pos);
+        let line = &self.buf[self.pos..end];
+        self.pos = (end + 1).min(self.buf.len());
+        line.split(u8::is_ascii_whitespace)
+            .filter(|token| !token.is_empty())
+            .collect()
+    }
+
+    /// `start` を含む行の終わり（改行の位置，または入力の末尾）
+    fn line_end(&self, start: usize) -> usize {
+        self.buf[start..]
+            .iter()
+            .position(|&c| c == b'\n')
+            .map_or(self.buf.len(), |i| start + i)
+    }
+}
+
+thread_local! {
+    static STDIN: RefCell<Option<Scanner>> = const { RefCell::new(None) };
+}
+
+/// 標準入力のスキャナに対して `f` を実行する（最初の呼び出しで標準入力をすべて読み込む）
+///
+/// - `f` の中で再び `with_stdin` を呼ぶと panic する
+pub fn with_stdin<T>(f: impl FnOnce(&mut Scanner) -> T) -> T {
+    STDIN.with(|sc| {
+        let mut sc = sc
+            .try_borrow_mut()
+            .expect("stdin scanner is already in use (nested `scan!` while reading stdin)");
+        f(sc.get_or_insert_with(|| Scanner::new(std::io::stdin().lock())))
+    })
+}
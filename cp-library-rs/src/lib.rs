@@ -8,6 +8,7 @@ pub mod convolution;
 pub mod data_structure;
 pub mod geometry;
 pub mod graph;
+pub mod io;
 pub mod linear_algrebra;
 pub mod number_theory;
 pub mod string;
@@ -22,3 +23,5 @@ mod debug;
 mod debug2D;
 mod get;
 mod in_ex;
+mod put;
+mod scan;
@@ -1,11 +1,15 @@
 //! 比較を実装した分数の実装
 
 use std::cmp::Ordering;
+use std::fmt::Display;
+use std::io::{self, Write};
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
 use num::{Integer, One, Zero};
 use num_integer::gcd;
 
+use crate::io::output::Writable;
+
 /// 分数を表す構造体
 /// - `Frac(a, b)` := a / b
 #[derive(Debug, Clone, Copy)]
@@ -288,3 +292,13 @@ where
         (a1 * b2).cmp(&(a2 * b1))
     }
 }
+
+impl<T> Writable for Frac<T>
+where
+    T: Integer + Copy + Display,
+{
+    /// `p/q` の形で出力する
+    fn write_to<W: Write>(&self, out: &mut W, _: Option<usize>) -> io::Result<()> {
+        write!(out, "{}/{}", self.0, self.1)
+    }
+}
@@ -0,0 +1,37 @@
+//! バッファ付きの出力用マクロ
+
+/// [`Output`](crate::io::output::Output) に値の並びを出力する
+///
+/// ```
+/// use cp_library_rs::{io::output::Output, number_theory::modint::M998, put};
+///
+/// let mut buf = vec![];
+/// {
+///     let mut out = Output::new(&mut buf);
+///     put!(out; 1, "a", M998::new(998244355)); // 空白区切りで1行
+///     put!(out; join [1, 2, 3]);               // 配列を空白区切りで1行
+///     put!(out; join ['a', 'b'], "");          // 区切り文字を指定
+///     put!(out; lines [4, 5]);                 // 1行に1つずつ
+///     put!(out; grid vec![vec![1, 2], vec![3, 4]]);
+///     put!(out; yesno 1 + 1 == 2);
+/// }
+///
+/// assert_eq!(
+///     String::from_utf8(buf).unwrap(),
+///     "1 a 2\n1 2 3\nab\n4\n5\n1 2\n3 4\nYes\n"
+/// );
+/// ```
+#[macro_export]
+macro_rules! put {
+    ($out:expr; join $values:expr, $sep:expr) => {$out.join($values, $sep)};
+    ($out:expr; join $values:expr) => {$out.join($values, " ")};
+    ($out:expr; lines $values:expr) => {$out.lines($values)};
+    ($out:expr; grid $grid:expr, $sep:expr) => {$out.grid(&$grid, $sep)};
+    ($out:expr; grid $grid:expr) => {$out.grid(&$grid, " ")};
+    ($out:expr; yesno $b:expr) => {$out.yesno($b)};
+    ($out:expr; $first:expr $(, $rest:expr)* $(,)?) => {{
+        $out.put(&$first);
+        $( $out.str(" "); $out.put(&$rest); )*
+        $out.endl();
+    }};
+}
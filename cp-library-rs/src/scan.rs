@@ -0,0 +1,47 @@
+//! 高速な入力用マクロ
+//!
+//! - 標準入力をまとめて読み込み，バイト列から直接変換する
+//! - 文法は [`get!`](crate::get) と同じ（`use cp_library_rs::scan as get;` で置き換えられる）
+//! - ただし，空白区切りのトークン単位で読むため，`String` は行全体ではなく1トークンを読む
+
+/// 高速な入力用マクロ
+///
+/// ```ignore
+/// let N = scan!(usize);                 // 1つの値
+/// let (u, v) = scan!(usize1, usize1);   // タプル（`usize1` は1を引く）
+/// let S = scan!(chars);                 // 文字の配列
+/// let A = scan!(isize; N);              // N個の値
+/// let E = scan!(usize, usize; M);       // M個のタプル
+/// let B = scan!(M998;;);                // 1行分の値
+/// let G = scan!(usize;; H);             // H行分の値
+/// ```
+///
+/// 読み込み元を指定する場合は `scan!(@from sc; ...)` とする（`sc` は [`Scanner`](crate::io::scanner::Scanner)）
+///
+/// **`get!` との違い**
+/// - `String`，`chars` は行全体ではなく，空白を含まない1トークンを読む（`get!` は行全体を読む）
+/// - `;;` 以外は改行を区切りとして扱わないため，1行に収まっていない値も読める
+///
+/// 個数の式（`scan!(usize; scan!(usize))` など）は，標準入力を読み込む前に評価する
+#[macro_export]
+macro_rules! scan {
+    (@parse $token:expr, usize1) => {$crate::scan!(@parse $token, usize) - 1};
+    (@parse $token:expr, chars) => {$crate::scan!(@parse $token, String).chars().collect::<Vec<_>>()};
+    (@parse $token:expr, $t:ty) => {<$t as $crate::io::scanner::Readable>::parse($token)};
+    (@from $sc:ident; $t:tt ;;) => {
+        $sc.line().into_iter().map(|token| $crate::scan!(@parse token, $t)).collect::<Vec<_>>()
+    };
+    (@from $sc:ident; $t:tt ;; $n:expr) => {(0..$n).map(|_| $crate::scan!(@from $sc; $t ;;)).collect::<Vec<_>>()};
+    (@from $sc:ident; $t:tt ; $n:expr) => {(0..$n).map(|_| $crate::scan!(@from $sc; $t)).collect::<Vec<_>>()};
+    (@from $sc:ident; $($t:tt),* ; $n:expr) => {(0..$n).map(|_| $crate::scan!(@from $sc; $($t),*)).collect::<Vec<_>>()};
+    (@from $sc:ident; $p:tt) => {$crate::scan!(@parse $sc.token(), $p)};
+    (@from $sc:ident; $($p:tt),*) => {( $($crate::scan!(@from $sc; $p),)* )};
+    // 標準入力は値を1つ読むごとに借用し，個数の式を借用中に評価しない
+    (@stdin $t:tt ;;) => {$crate::io::scanner::with_stdin(|sc| $crate::scan!(@from sc; $t ;;))};
+    (@stdin $t:tt ;; $n:expr) => {(0..$n).map(|_| $crate::scan!(@stdin $t ;;)).collect::<Vec<_>>()};
+    (@stdin $t:tt ; $n:expr) => {(0..$n).map(|_| $crate::scan!(@stdin $t)).collect::<Vec<_>>()};
+    (@stdin $($t:tt),* ; $n:expr) => {(0..$n).map(|_| $crate::scan!(@stdin $($t),*)).collect::<Vec<_>>()};
+    (@stdin $p:tt) => {$crate::io::scanner::with_stdin(|sc| $crate::scan!(@from sc; $p))};
+    (@stdin $($p:tt),*) => {( $($crate::scan!(@stdin $p),)* )};
+    ($($rest:tt)*) => {$crate::scan!(@stdin $($rest)*)};
+}
@@ -0,0 +1,241 @@
+//! ## Aho-Corasick法
+//!
+//! 複数のパターンを同時に検索するオートマトン．
+//! アルファベット（記号の集合）を $`\sigma`$ として，状態ごとに遷移先を $`\sigma`$ 個の配列で持つ．
+//!
+//! - 構築の時間計算量: $`O(\sigma \sum |P_i|)`$
+//! - 検索の時間計算量: $`O(|T| \log \sigma + (\text{マッチ数}))`$
+//!
+//! 状態 `0` が根（空文字列）に対応する．
+//! `goto`，`fail`，`count`，`bfs` を用いてオートマトン上のDPを行うことができる．
+//!
+//! ```
+//! use cp_library_rs::string::aho_corasick::*;
+//!
+//! let patterns = ["he", "she", "his", "hers"].map(|p| p.as_bytes());
+//! let ac = AhoCorasick::new(&patterns);
+//!
+//! let matches: Vec<_> = ac.find_iter(b"ushers").collect();
+//! // (開始位置, パターンの番号)
+//! assert_eq!(matches, vec![(1, 1), (2, 0), (2, 3)]);
+//! ```
+//!
+//! 文字 `a`，`b` からなる長さ `n` の文字列のうち，`aa` を含まないものの個数：
+//!
+//! ```
+//! use cp_library_rs::{number_theory::modint::M998, string::aho_corasick::*};
+//!
+//! let ac = AhoCorasick::with_alphabet(&['a', 'b'], &[['a', 'a']]);
+//!
+//! let mut dp = vec![M998::new(0); ac.num_states()];
+//! dp[0] = M998::new(1);
+//! for _ in 0..10 {
+//!     let mut ndp = vec![M998::new(0); ac.num_states()];
+//!     for &u in &ac.bfs {
+//!         for &v in &ac.goto[u] {
+//!             if ac.count[v] == 0 {
+//!                 ndp[v] += dp[u];
+//!             }
+//!         }
+//!     }
+//!     dp = ndp;
+//! }
+//! assert_eq!(dp.iter().copied().sum::<M998>(), M998::new(144));
+//! ```
+
+/// 未定義の遷移
+const NONE: usize = usize::MAX;
+
+/// Aho-Corasickオートマトン
+#[derive(Debug, Clone)]
+pub struct AhoCorasick<C> {
+    /// アルファベット（昇順）
+    alphabet: Vec<C>,
+    /// 各パターンの長さ
+    pattern_len: Vec<usize>,
+    /// `goto[u][a]`: 状態 `u` から `a` 番目の記号で遷移した先の状態
+    pub goto: Vec<Vec<usize>>,
+    /// `fail[u]`: 状態 `u` の真の接尾辞のうち，状態として存在する最長のもの（根では `0`）
+    pub fail: Vec<usize>,
+    /// `outputs[u]`: 状態 `u` にちょうど一致するパターンの番号
+    pub outputs: Vec<Vec<usize>>,
+    /// `count[u]`: 状態 `u` の接尾辞に一致するパターンの個数
+    pub count: Vec<usize>,
+    /// 状態の幅優先探索順（根からの深さの昇順）
+    pub bfs: Vec<usize>,
+    /// `dict[u]`: `fail` を辿ったとき，最初に出現する `outputs` が空でない状態
+    dict: Vec<Option<usize>>,
+}
+
+impl<C: Ord + Clone> AhoCorasick<C> {
+    /// パターンの列からオートマトンを構築する
+    ///
+    /// - アルファベットはパターンに出現する記号とする
+    pub fn new<P: AsRef<[C]>>(patterns: &[P]) -> Self {
+        let alphabet: Vec<C> = patterns
+            .iter()
+            .flat_map(|p| p.as_ref().iter().cloned())
+            .collect();
+        Self::with_alphabet(&alphabet, patterns)
+    }
+
+    /// アルファベット `alphabet` を指定して，パターンの列からオートマトンを構築する
+    ///
+    /// - パターンにアルファベット以外の記号が含まれる場合はpanicする
+    pub fn with_alphabet<P: AsRef<[C]>>(alphabet: &[C], patterns: &[P]) -> Self {
+        let mut alphabet = alphabet.to_vec();
+        alphabet.sort();
+        alphabet.dedup();
+        let sigma = alphabet.len();
+
+        let mut ac = Self {
+            alphabet,
+            pattern_len: patterns.iter().map(|p| p.as_ref().len()).collect(),
+            goto: vec![vec![NONE; sigma]],
+            fail: vec![0],
+            outputs: vec![vec![]],
+            count: vec![],
+            bfs: vec![],
+            dict: vec![],
+        };
+
+        // トライ木の構築
+        for (id, p) in patterns.iter().enumerate() {
+            let mut u = 0;
+            for c in p.as_ref() {
+                let a = ac
+                    .symbol_index(c)
+                    .expect("pattern contains a symbol not in the alphabet");
+                if ac.goto[u][a] == NONE {
+                    ac.goto[u][a] = ac.goto.len();
+                    ac.goto.push(vec![NONE; sigma]);
+                    ac.fail.push(0);
+                    ac.outputs.push(vec![]);
+                }
+                u = ac.goto[u][a];
+            }
+            ac.outputs[u].push(id);
+        }
+
+        let n = ac.goto.len();
+        ac.count = vec![0; n];
+        ac.dict = vec![None; n];
+        ac.count[0] = ac.outputs[0].len();
+
+        // 幅優先探索で失敗遷移を求める
+        ac.bfs.push(0);
+        let mut i = 0;
+        while i < ac.bfs.len() {
+            let u = ac.bfs[i];
+            i += 1;
+            for a in 0..sigma {
+                let v = ac.goto[u][a];
+                let next = if u == 0 { 0 } else { ac.goto[ac.fail[u]][a] };
+                if v == NONE {
+                    ac.goto[u][a] = next;
+                    continue;
+                }
+                let f = next;
+                ac.fail[v] = f;
+                ac.count[v] = ac.outputs[v].len() + ac.count[f];
+                ac.dict[v] = if ac.outputs[f].is_empty() {
+                    ac.dict[f]
+                } else {
+                    Some(f)
+                };
+                ac.bfs.push(v);
+            }
+        }
+
+        ac
+    }
+
+    /// 状態数
+    pub fn num_states(&self) -> usize {
+        self.goto.len()
+    }
+
+    /// アルファベット（昇順）
+    pub fn alphabet(&self) -> &[C] {
+        &self.alphabet
+    }
+
+    /// 記号 `c` がアルファベットの何番目か
+    pub fn symbol_index(&self, c: &C) -> Option<usize> {
+        self.alphabet.binary_search(c).ok()
+    }
+
+    /// 状態 `u` から記号 `c` で遷移した先の状態
+    ///
+    /// - `c` がアルファベットに含まれない場合は根に遷移する
+    pub fn next(&self, u: usize, c: &C) -> usize {
+        self.symbol_index(c).map_or(0, |a| self.goto[u][a])
+    }
+
+    /// パターン `id` の長さ
+    pub fn pattern_len(&self, id: usize) -> usize {
+        self.pattern_len[id]
+    }
+
+    /// テキスト `text` に出現するパターンの個数（重複を含む）
+    pub fn count_matches(&self, text: &[C]) -> usize {
+        let mut u = 0;
+        let mut res = self.count[0];
+        for c in text {
+            u = self.next(u, c);
+            res += self.count[u];
+        }
+        res
+    }
+
+    /// テキスト `text` 中のパターンの出現 `(開始位置, パターンの番号)` を列挙する
+    ///
+    /// - 終了位置の昇順，同じ終了位置ではパターンの長さの降順に列挙する
+    pub fn find_iter<'a>(&'a self, text: &'a [C]) -> Matches<'a, C> {
+        Matches {
+            ac: self,
+            text,
+            pos: 0,
+            state: 0,
+            node: Some(0),
+            j: 0,
+        }
+    }
+}
+
+/// パターンの出現を列挙するイテレータ
+#[derive(Debug)]
+pub struct Matches<'a, C> {
+    ac: &'a AhoCorasick<C>,
+    text: &'a [C],
+    /// 読み込んだ記号数
+    pos: usize,
+    /// 現在の状態
+    state: usize,
+    /// 出力中の状態
+    node: Option<usize>,
+    /// `outputs[node]` のうち出力済みの個数
+    j: usize,
+}
+
+impl<C: Ord + Clone> Iterator for Matches<'_, C> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(node) = self.node {
+                if let Some(&id) = self.ac.outputs[node].get(self.j) {
+                    self.j += 1;
+                    return Some((self.pos - self.ac.pattern_len[id], id));
+                }
+                self.node = self.ac.dict[node];
+                self.j = 0;
+                continue;
+            }
+            let c = self.text.get(self.pos)?;
+            self.state = self.ac.next(self.state, c);
+            self.pos += 1;
+            self.node = Some(self.state);
+        }
+    }
+}
@@ -1,5 +1,6 @@
 //! ## 文字列アルゴリズム
 
+pub mod aho_corasick;
 pub mod consts;
 pub mod lcs;
 pub mod rolling_hash;
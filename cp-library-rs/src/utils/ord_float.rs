@@ -2,11 +2,14 @@
 
 use std::{
     fmt::Display,
+    io::{self, Write},
     ops::{Add, Div, Mul, Rem, Sub},
 };
 
 use num_traits::{Bounded, One, Zero};
 
+use crate::io::output::Writable;
+
 macro_rules! impl_ord_f64 {
     (f64, $op_trait:ident, $op_func:ident, $op:tt) => {
         impl $op_trait<f64> for OrdF64 {
@@ -39,7 +42,7 @@ impl PartialOrd for OrdF64 {
 
 impl Ord for OrdF64 {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.partial_cmp(other).unwrap()
+        self.0.partial_cmp(&other.0).unwrap()
     }
 }
 
@@ -49,6 +52,12 @@ impl Display for OrdF64 {
     }
 }
 
+impl Writable for OrdF64 {
+    fn write_to<W: Write>(&self, out: &mut W, precision: Option<usize>) -> io::Result<()> {
+        self.0.write_to(out, precision)
+    }
+}
+
 impl From<f64> for OrdF64 {
     fn from(value: f64) -> Self {
         Self(value)
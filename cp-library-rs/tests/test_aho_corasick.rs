@@ -0,0 +1,145 @@
+#![allow(non_snake_case)]
+
+use cp_library_rs::{number_theory::modint::M998, string::aho_corasick::*};
+use rand::*;
+
+/// 愚直に出現を列挙する（終了位置の昇順，パターンの長さの降順）
+fn naive_matches(patterns: &[Vec<u8>], text: &[u8]) -> Vec<(usize, usize)> {
+    let mut res = vec![];
+    for end in 0..=text.len() {
+        let mut list: Vec<(usize, usize)> = patterns
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.len() <= end && text[end - p.len()..end] == p[..])
+            .map(|(id, p)| (end - p.len(), id))
+            .collect();
+        list.sort_by_key(|&(start, id)| (start, id));
+        res.extend(list);
+    }
+    res
+}
+
+#[test]
+fn test_random() {
+    let mut rng = rand::rng();
+
+    for _ in 0..200 {
+        let K = rng.random_range(1..6);
+        let patterns: Vec<Vec<u8>> = (0..K)
+            .map(|_| {
+                let len = rng.random_range(1..5);
+                (0..len).map(|_| b'a' + rng.random_range(0..3)).collect()
+            })
+            .collect();
+        let text: Vec<u8> = (0..30).map(|_| b'a' + rng.random_range(0..4)).collect();
+
+        let ac = AhoCorasick::new(&patterns);
+
+        // 同じ開始位置・終了位置のパターンは番号順に並べて比較
+        let mut actual: Vec<_> = ac.find_iter(&text).collect();
+        let key = |&(s, id): &(usize, usize)| (s + patterns[id].len(), s, id);
+        actual.sort_by_key(key);
+        let mut expected = naive_matches(&patterns, &text);
+        expected.sort_by_key(key);
+
+        assert_eq!(actual, expected);
+        assert_eq!(ac.count_matches(&text), expected.len());
+    }
+}
+
+#[test]
+fn test_structure() {
+    let patterns = ["a", "ab", "bab", "bc", "bca", "c", "caa"].map(|p| p.as_bytes());
+    let ac = AhoCorasick::new(&patterns);
+
+    // 根 + 接頭辞 {a, ab, b, ba, bab, bc, bca, c, ca, caa}
+    assert_eq!(ac.num_states(), 11);
+    assert_eq!(ac.alphabet(), b"abc");
+    assert_eq!(ac.bfs.len(), 11);
+    assert_eq!(ac.bfs[0], 0);
+
+    let state = |s: &[u8]| s.iter().fold(0, |u, c| ac.next(u, c));
+
+    assert_eq!(ac.fail[state(b"bab")], state(b"ab"));
+    assert_eq!(ac.fail[state(b"caa")], state(b"a"));
+    assert_eq!(ac.fail[state(b"bca")], state(b"ca"));
+    assert_eq!(ac.outputs[state(b"bca")], vec![4]);
+    // bca, ca, a
+    assert_eq!(ac.count[state(b"bca")], 2);
+    assert_eq!(ac.count[state(b"bab")], 2);
+
+    // 失敗遷移先は幅優先探索順で先に現れる
+    let mut order = vec![0; ac.num_states()];
+    for (i, &u) in ac.bfs.iter().enumerate() {
+        order[u] = i;
+    }
+    for &u in &ac.bfs[1..] {
+        assert!(order[ac.fail[u]] < order[u]);
+    }
+
+    // アルファベット外の記号は根に戻る
+    assert_eq!(ac.next(state(b"bc"), &b'z'), 0);
+    let matches: Vec<_> = ac.find_iter(b"abzcaa").collect();
+    assert_eq!(
+        matches,
+        vec![(0, 0), (0, 1), (3, 5), (4, 0), (3, 6), (5, 0)]
+    );
+}
+
+#[test]
+fn test_empty_pattern() {
+    let ac = AhoCorasick::new(&[vec![], vec![1, 2]]);
+    let matches: Vec<_> = ac.find_iter(&[1, 2]).collect();
+    assert_eq!(matches, vec![(0, 0), (1, 0), (0, 1), (2, 0)]);
+    assert_eq!(ac.count_matches(&[1, 2]), 4);
+}
+
+#[test]
+fn test_dp_avoid_patterns() {
+    // 文字 0, 1, 2 からなる長さ N の列のうち，パターンを含まないものの個数
+    let alphabet = [0, 1, 2];
+    let patterns = [vec![0, 1], vec![2, 2, 2], vec![1, 0, 2]];
+    let ac = AhoCorasick::with_alphabet(&alphabet, &patterns);
+
+    for N in 0..=8 {
+        let mut dp = vec![M998::new(0); ac.num_states()];
+        dp[0] = M998::new(1);
+        for _ in 0..N {
+            let mut ndp = vec![M998::new(0); ac.num_states()];
+            for &u in &ac.bfs {
+                for &v in &ac.goto[u] {
+                    if ac.count[v] == 0 {
+                        ndp[v] += dp[u];
+                    }
+                }
+            }
+            dp = ndp;
+        }
+        let actual: M998 = dp.iter().copied().sum();
+
+        // 全探索
+        let mut expected = 0;
+        for mut x in 0..3_usize.pow(N as u32) {
+            let s: Vec<usize> = (0..N)
+                .map(|_| {
+                    let d = x % 3;
+                    x /= 3;
+                    d
+                })
+                .collect();
+            if patterns
+                .iter()
+                .all(|p| !s.windows(p.len()).any(|w| w == &p[..]))
+            {
+                expected += 1;
+            }
+        }
+        assert_eq!(actual, M998::new(expected));
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_symbol_not_in_alphabet() {
+    AhoCorasick::with_alphabet(&['a'], &[['a', 'b']]);
+}
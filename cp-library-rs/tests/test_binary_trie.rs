@@ -0,0 +1,89 @@
+#![allow(non_snake_case)]
+
+use cp_library_rs::data_structure::binary_trie::*;
+use rand::*;
+
+#[test]
+fn test_random() {
+    const QUERY: usize = 5000;
+    const MAX: u64 = 1 << 6;
+    let mut rng = rand::rng();
+
+    let mut trie = BinaryTrie::<6>::new();
+    // 作用後の値の多重集合
+    let mut arr: Vec<u64> = vec![];
+
+    for _ in 0..QUERY {
+        let x = rng.random_range(0..MAX);
+        match rng.random_range(0..9) {
+            0 | 1 => {
+                let cnt = rng.random_range(0..3);
+                trie.insert_count(x, cnt);
+                arr.extend(std::iter::repeat_n(x, cnt));
+            }
+            2 => {
+                let cnt = rng.random_range(0..3);
+                let expected = arr.iter().filter(|&&a| a == x).count().min(cnt);
+                assert_eq!(trie.erase_count(x, cnt), expected);
+                for _ in 0..expected {
+                    let i = arr.iter().position(|&a| a == x).unwrap();
+                    arr.swap_remove(i);
+                }
+            }
+            3 => {
+                trie.xor_all(x);
+                arr.iter_mut().for_each(|a| *a ^= x);
+            }
+            4 => {
+                assert_eq!(trie.min_xor(x), arr.iter().map(|&a| a ^ x).min());
+                assert_eq!(trie.max_xor(x), arr.iter().map(|&a| a ^ x).max());
+            }
+            5 => {
+                let mut sorted = arr.clone();
+                sorted.sort();
+                let k = rng.random_range(0..=arr.len());
+                assert_eq!(trie.kth_smallest(k), sorted.get(k).copied());
+                assert_eq!(trie.kth_largest(k), sorted.iter().rev().nth(k).copied());
+            }
+            6 => {
+                let x = rng.random_range(0..=MAX);
+                assert_eq!(trie.count_less(x), arr.iter().filter(|&&a| a < x).count());
+            }
+            7 => assert_eq!(trie.count(x), arr.iter().filter(|&&a| a == x).count()),
+            _ => {
+                assert_eq!(trie.len(), arr.len());
+                assert_eq!(trie.min(), arr.iter().copied().min());
+                assert_eq!(trie.max(), arr.iter().copied().max());
+            }
+        }
+    }
+}
+
+#[test]
+fn test_64bit() {
+    let mut trie = BinaryTrie::<64>::default();
+    assert_eq!(trie.min_xor(0), None);
+    assert_eq!(trie.kth_smallest(0), None);
+
+    trie.insert(u64::MAX);
+    trie.insert(0);
+    trie.insert(1 << 63);
+
+    assert_eq!(trie.max_xor(1), Some(u64::MAX - 1));
+    assert_eq!(trie.min_xor(u64::MAX - 1), Some(1));
+    assert_eq!(trie.count_less(u64::MAX), 2);
+
+    trie.xor_all(u64::MAX);
+    assert_eq!(trie.kth_smallest(0), Some(0));
+    assert_eq!(trie.kth_smallest(1), Some((1 << 63) - 1));
+    assert!(trie.erase(u64::MAX));
+    assert!(!trie.erase(u64::MAX));
+    assert_eq!(trie.max(), Some((1 << 63) - 1));
+}
+
+#[test]
+#[should_panic]
+fn test_out_of_range() {
+    let mut trie = BinaryTrie::<3>::new();
+    trie.insert(8);
+}
@@ -1,25 +1,26 @@
 #![allow(non_snake_case)]
 
 use cp_library_rs::data_structure::bitset::*;
+use rand::*;
 
 #[test]
 fn test_get_mut() {
     let mut bitset = BitSet::<20>::new();
 
     println!("{:?}", bitset);
-    assert!(bitset.any());
+    assert!(bitset.none());
     assert!(!bitset.all());
 
     *bitset.get_mut(10).unwrap() = true;
 
     println!("{:?}", bitset);
-    assert!(!bitset.any());
+    assert!(!bitset.none());
     assert!(!bitset.all());
 
     *bitset.get_mut(10).unwrap() = false;
 
     println!("{:?}", bitset);
-    assert!(bitset.any());
+    assert!(bitset.none());
     assert!(!bitset.all());
 
     assert!(bitset.get_mut(50).is_none());
@@ -27,7 +28,7 @@ fn test_get_mut() {
     *bitset.get_mut(0).unwrap() = true;
 
     println!("{:?}", bitset);
-    assert!(!bitset.any());
+    assert!(!bitset.none());
     assert!(!bitset.all());
 }
 
@@ -37,7 +38,7 @@ fn test_set_unset_flip() {
 
     // set
     println!("{:?}", bitset);
-    assert!(bitset.any());
+    assert!(bitset.none());
     assert!(!bitset.all());
     assert_eq!(bitset.count_ones(), 0);
 
@@ -45,13 +46,13 @@ fn test_set_unset_flip() {
         bitset.set(i);
 
         println!("{:?}", bitset);
-        assert!(!bitset.any());
+        assert!(!bitset.none());
         assert!(!bitset.all());
         assert_eq!(bitset.count_ones(), i + 1);
     }
 
     bitset.set(99);
-    assert!(!bitset.any());
+    assert!(!bitset.none());
     assert!(bitset.all());
     assert_eq!(bitset.count_ones(), 100);
 
@@ -60,7 +61,7 @@ fn test_set_unset_flip() {
         bitset.unset(i);
 
         println!("{:?}", bitset);
-        assert!(!bitset.any());
+        assert!(!bitset.none());
         assert!(!bitset.all());
         assert_eq!(bitset.count_ones(), i);
     }
@@ -70,7 +71,7 @@ fn test_set_unset_flip() {
         bitset.flip(i);
 
         println!("{:?}", bitset);
-        assert!(!bitset.any());
+        assert!(!bitset.none());
         assert!(!bitset.all());
         assert_eq!(bitset.count_ones(), 75 - i - 1);
     }
@@ -79,37 +80,89 @@ fn test_set_unset_flip() {
         bitset.flip(i);
 
         println!("{:?}", bitset);
-        assert!(!bitset.any());
+        assert!(!bitset.none());
         assert!(!bitset.all());
         assert_eq!(bitset.count_ones(), i - 25 + 1);
     }
 }
 
-// #[test]
-// fn test_right_shift() {
-//     let mut bitset = BitSet::<150>::new();
-
-//     println!("{:?}", bitset);
-
-//     for i in 40..60 {
-//         bitset.set(i);
-//     }
-
-//     for i in 80..100 {
-//         bitset.set(i);
-//     }
-
-//     for i in 120..140 {
-//         bitset.set(i);
-//     }
-
-//     println!("{:?}", bitset);
-
-//     let rh100 = bitset.clone() >> 100;
-
-//     println!("{:?}", rh100);
+#[test]
+fn test_right_shift() {
+    let mut bitset = BitSet::<150>::new();
+
+    bitset.set_range(40..60);
+    bitset.set_range(80..100);
+    bitset.set_range(120..140);
+
+    let rh100 = bitset.clone() >> 100;
+    assert_eq!(
+        rh100.iter_ones().collect::<Vec<_>>(),
+        (20..40).collect::<Vec<_>>()
+    );
+
+    let rh50 = &bitset >> 50;
+    let expected: Vec<usize> = (0..10).chain(30..50).chain(70..90).collect();
+    assert_eq!(rh50.iter_ones().collect::<Vec<_>>(), expected);
+}
 
-//     let rh50 = bitset.clone() >> 50;
+#[test]
+fn test_ops() {
+    let mut rng = rand::rng();
+
+    for _ in 0..200 {
+        let a: Vec<bool> = (0..130).map(|_| rng.random_bool(0.3)).collect();
+        let b: Vec<bool> = (0..130).map(|_| rng.random_bool(0.3)).collect();
+        let to_bitset = |v: &[bool]| {
+            let mut bs = BitSet::<130>::new();
+            for (i, _) in v.iter().enumerate().filter(|(_, &x)| x) {
+                bs.set(i);
+            }
+            bs
+        };
+        let ones = |v: &[bool]| -> Vec<usize> { (0..v.len()).filter(|&i| v[i]).collect() };
+        let (A, B) = (to_bitset(&a), to_bitset(&b));
+
+        let and: Vec<bool> = (0..130).map(|i| a[i] & b[i]).collect();
+        let or: Vec<bool> = (0..130).map(|i| a[i] | b[i]).collect();
+        let xor: Vec<bool> = (0..130).map(|i| a[i] ^ b[i]).collect();
+        let not: Vec<bool> = a.iter().map(|x| !x).collect();
+        assert_eq!((&A & &B).iter_ones().collect::<Vec<_>>(), ones(&and));
+        assert_eq!((A.clone() | &B).iter_ones().collect::<Vec<_>>(), ones(&or));
+        assert_eq!(
+            (A.clone() ^ B.clone()).iter_ones().collect::<Vec<_>>(),
+            ones(&xor)
+        );
+        assert_eq!((!&A).iter_ones().collect::<Vec<_>>(), ones(&not));
+        assert_eq!((!&A).count_ones(), 130 - A.count_ones());
+
+        let k = rng.random_range(0..200);
+        let shl: Vec<bool> = (0..130).map(|i| i >= k && a[i - k]).collect();
+        let shr: Vec<bool> = (0..130).map(|i| i + k < 130 && a[i + k]).collect();
+        assert_eq!((&A << k).iter_ones().collect::<Vec<_>>(), ones(&shl));
+        assert_eq!((&A >> k).iter_ones().collect::<Vec<_>>(), ones(&shr));
+
+        let mut C = A.clone();
+        C ^= &A;
+        assert_eq!(C, BitSet::<130>::new());
+
+        let first = ones(&a).first().copied();
+        assert_eq!(A.find_first(), first);
+        let i = rng.random_range(0..130);
+        assert_eq!(A.find_next(i), ones(&a).into_iter().find(|&j| j > i));
+        assert_eq!(A.any(), !ones(&a).is_empty());
+        assert_eq!(A.none(), ones(&a).is_empty());
+    }
+}
 
-//     println!("{:?}", rh50);
-// }
+#[test]
+fn test_range() {
+    let mut bitset = BitSet::<128>::new();
+    bitset.set_range(..);
+    assert_eq!(bitset.count_ones(), 128);
+    bitset.unset_range(3..=64);
+    assert_eq!(bitset.count_ones(), 66);
+    assert_eq!(bitset.find_next(2), Some(65));
+    assert_eq!(bitset.find_next(usize::MAX), None);
+    bitset.unset_range(..);
+    assert_eq!(bitset.find_first(), None);
+}
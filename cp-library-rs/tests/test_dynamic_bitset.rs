@@ -0,0 +1,132 @@
+#![allow(non_snake_case)]
+
+use cp_library_rs::data_structure::dynamic_bitset::*;
+use rand::*;
+
+fn from_bools(v: &[bool]) -> DynamicBitSet {
+    let mut bs = DynamicBitSet::new(v.len());
+    for (i, &x) in v.iter().enumerate() {
+        if x {
+            bs.set(i);
+        }
+    }
+    bs
+}
+
+fn to_bools(bs: &DynamicBitSet) -> Vec<bool> {
+    (0..bs.len()).map(|i| bs[i]).collect()
+}
+
+#[test]
+fn test_random() {
+    let mut rng = rand::rng();
+
+    for N in [0, 1, 63, 64, 65, 200] {
+        for _ in 0..100 {
+            let a: Vec<bool> = (0..N).map(|_| rng.random_bool(0.4)).collect();
+            let b: Vec<bool> = (0..N).map(|_| rng.random_bool(0.4)).collect();
+            let (A, B) = (from_bools(&a), from_bools(&b));
+            assert_eq!(to_bools(&A), a);
+
+            let zip = |f: fn(bool, bool) -> bool| -> Vec<bool> {
+                (0..N).map(|i| f(a[i], b[i])).collect()
+            };
+            assert_eq!(to_bools(&(&A & &B)), zip(|x, y| x & y));
+            assert_eq!(to_bools(&(A.clone() | &B)), zip(|x, y| x | y));
+            assert_eq!(to_bools(&(A.clone() ^ B.clone())), zip(|x, y| x ^ y));
+            assert_eq!(to_bools(&!&A), zip(|x, _| !x));
+            assert_eq!((!&A).count_ones(), N - A.count_ones());
+
+            let k = rng.random_range(0..=N + 70);
+            let shl: Vec<bool> = (0..N).map(|i| i >= k && a[i - k]).collect();
+            let shr: Vec<bool> = (0..N).map(|i| i + k < N && a[i + k]).collect();
+            assert_eq!(to_bools(&(&A << k)), shl);
+            assert_eq!(to_bools(&(&A >> k)), shr);
+            let mut C = A.clone();
+            C <<= k;
+            assert_eq!(to_bools(&C), shl);
+
+            let ones: Vec<usize> = (0..N).filter(|&i| a[i]).collect();
+            assert_eq!(A.iter_ones().collect::<Vec<_>>(), ones);
+            assert_eq!(A.find_first(), ones.first().copied());
+            assert_eq!(A.any(), !ones.is_empty());
+            assert_eq!(A.none(), ones.is_empty());
+            assert_eq!(A.all(), ones.len() == N);
+            if N > 0 {
+                let i = rng.random_range(0..N);
+                assert_eq!(A.find_next(i), ones.iter().copied().find(|&j| j > i));
+            }
+            assert_eq!(A.find_next(usize::MAX), None);
+
+            let l = rng.random_range(0..=N);
+            let r = rng.random_range(l..=N);
+            let mut S = A.clone();
+            let mut s = a.clone();
+            if rng.random_bool(0.5) {
+                S.set_range(l..r);
+                s[l..r].iter_mut().for_each(|x| *x = true);
+            } else {
+                S.unset_range(l..r);
+                s[l..r].iter_mut().for_each(|x| *x = false);
+            }
+            assert_eq!(to_bools(&S), s);
+            assert_eq!(S.count_ones(), s.iter().filter(|&&x| x).count());
+        }
+    }
+}
+
+#[test]
+fn test_knapsack() {
+    let mut rng = rand::rng();
+    const W: usize = 500;
+    let items: Vec<usize> = (0..30).map(|_| rng.random_range(1..100)).collect();
+
+    let mut dp = DynamicBitSet::new(W + 1);
+    dp.set(0);
+    let mut naive = vec![false; W + 1];
+    naive[0] = true;
+    for &w in &items {
+        dp |= &dp << w;
+        for j in (w..=W).rev() {
+            naive[j] |= naive[j - w];
+        }
+    }
+    assert_eq!(to_bools(&dp), naive);
+}
+
+#[test]
+fn test_transitive_closure() {
+    // 0 -> 1 -> 2 -> 3, 4 -> 0
+    let N = 5;
+    let mut reach: Vec<DynamicBitSet> = (0..N)
+        .map(|i| {
+            let mut bs = DynamicBitSet::new(N);
+            bs.set(i);
+            bs
+        })
+        .collect();
+    for (u, v) in [(0, 1), (1, 2), (2, 3), (4, 0)] {
+        reach[u].set(v);
+    }
+    // Warshall-Floyd
+    for k in 0..N {
+        for i in 0..N {
+            if reach[i][k] {
+                let rk = reach[k].clone();
+                reach[i] |= &rk;
+            }
+        }
+    }
+    assert_eq!(
+        reach[4].iter_ones().collect::<Vec<_>>(),
+        vec![0, 1, 2, 3, 4]
+    );
+    assert_eq!(reach[1].iter_ones().collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert_eq!(format!("{:?}", reach[1]), "DynamicBitSet { \"01110\" }");
+}
+
+#[test]
+#[should_panic]
+fn test_size_mismatch() {
+    let _ = &DynamicBitSet::new(3) & &DynamicBitSet::new(4);
+}
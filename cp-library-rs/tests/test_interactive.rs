@@ -0,0 +1,172 @@
+#![allow(non_snake_case)]
+
+use std::{
+    io::{BufReader, Write},
+    time::Duration,
+};
+
+use cp_library_rs::{
+    io::interactive::{
+        pipe, run_interactive, InteractiveError, InteractiveReader, Judge, SolutionReader,
+        SolutionWriter,
+    },
+    put, scan,
+};
+
+/// 1以上 `N` 以下の整数 `secret` を当てる問題
+///
+/// - `? x`：`secret` と `x` の大小（`<`，`=`，`>`）が返る
+/// - `! x`：解答
+fn guess_interactor(
+    N: usize,
+    secret: usize,
+) -> impl FnOnce(&mut Judge) -> Result<(), InteractiveError> {
+    move |judge| {
+        put!(judge.out; N);
+        loop {
+            let query = judge.read_line()?;
+            let [kind, x] = &query[..] else {
+                return Err(InteractiveError::InvalidQuery(query.join(" ")));
+            };
+            let x: usize = x
+                .parse()
+                .map_err(|_| InteractiveError::InvalidQuery(query.join(" ")))?;
+            match kind.as_str() {
+                "?" => {
+                    judge.count_query()?;
+                    if !(1..=N).contains(&x) {
+                        return Err(InteractiveError::InvalidQuery(format!("out of range: {x}")));
+                    }
+                    let res = match secret.cmp(&x) {
+                        std::cmp::Ordering::Less => "<",
+                        std::cmp::Ordering::Equal => "=",
+                        std::cmp::Ordering::Greater => ">",
+                    };
+                    put!(judge.out; res);
+                }
+                "!" if x == secret => return Ok(()),
+                "!" => return Err(InteractiveError::WrongAnswer(format!("{x} != {secret}"))),
+                _ => return Err(InteractiveError::InvalidQuery(query.join(" "))),
+            }
+        }
+    }
+}
+
+/// 二分探索で当てる
+fn binary_search(rd: &mut SolutionReader, out: &mut SolutionWriter) {
+    let N = scan!(@from rd; usize);
+    let (mut lo, mut hi) = (1, N);
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        put!(out; "?", mid);
+        match scan!(@from rd; String).as_str() {
+            "=" => {
+                (lo, hi) = (mid, mid);
+            }
+            "<" => hi = mid - 1,
+            _ => lo = mid + 1,
+        }
+    }
+    put!(out; "!", lo);
+}
+
+#[test]
+fn test_accepted() {
+    for secret in [1, 2, 500, 999, 1000] {
+        let queries = run_interactive(Some(10), binary_search, guess_interactor(1000, secret));
+        assert!(matches!(queries, Ok(q) if q <= 10), "{secret}: {queries:?}");
+    }
+}
+
+#[test]
+fn test_query_limit_exceeded() {
+    let linear_search = |rd: &mut SolutionReader, out: &mut SolutionWriter| {
+        let N = scan!(@from rd; usize);
+        for x in 1..=N {
+            put!(out; "?", x);
+            if scan!(@from rd; String) == "=" {
+                put!(out; "!", x);
+                return;
+            }
+        }
+    };
+
+    assert_eq!(
+        run_interactive(Some(10), linear_search, guess_interactor(1000, 500)),
+        Err(InteractiveError::QueryLimitExceeded { limit: 10 })
+    );
+    // 上限がなければ正解
+    assert_eq!(
+        run_interactive(None, linear_search, guess_interactor(1000, 500)),
+        Ok(500)
+    );
+}
+
+#[test]
+fn test_invalid_query() {
+    let out_of_range = |_: &mut SolutionReader, out: &mut SolutionWriter| {
+        put!(out; "?", 0);
+    };
+    assert_eq!(
+        run_interactive(None, out_of_range, guess_interactor(10, 3)),
+        Err(InteractiveError::InvalidQuery(
+            "out of range: 0".to_string()
+        ))
+    );
+
+    let malformed = |_: &mut SolutionReader, out: &mut SolutionWriter| {
+        put!(out; "?");
+    };
+    assert_eq!(
+        run_interactive(None, malformed, guess_interactor(10, 3)),
+        Err(InteractiveError::InvalidQuery("?".to_string()))
+    );
+}
+
+#[test]
+fn test_wrong_answer() {
+    let guess = |_: &mut SolutionReader, out: &mut SolutionWriter| {
+        put!(out; "!", 4);
+    };
+    assert_eq!(
+        run_interactive(None, guess, guess_interactor(10, 3)),
+        Err(InteractiveError::WrongAnswer("4 != 3".to_string()))
+    );
+}
+
+#[test]
+fn test_solution_ends_early() {
+    assert_eq!(
+        run_interactive(None, |_, _| {}, guess_interactor(10, 3)),
+        Err(InteractiveError::UnexpectedEof)
+    );
+
+    let panics = |rd: &mut SolutionReader, _: &mut SolutionWriter| {
+        let N = scan!(@from rd; usize);
+        assert!(N > 100, "N is too small");
+    };
+    assert_eq!(
+        run_interactive(None, panics, guess_interactor(10, 3)),
+        Err(InteractiveError::SolutionPanicked(
+            "N is too small".to_string()
+        ))
+    );
+}
+
+#[test]
+fn test_reader_does_not_over_read() {
+    let (mut tx, rx) = pipe(Duration::from_millis(100));
+    let mut rd = InteractiveReader::new(BufReader::new(rx));
+
+    // 1行目だけが届いている状態で，1行目を読み切れる
+    tx.write_all(b"2 3\n").unwrap();
+    assert_eq!(scan!(@from rd; usize, usize), (2, 3));
+
+    tx.write_all(b"1 2 3\n\n4\n").unwrap();
+    assert_eq!(scan!(@from rd; usize;;), vec![1, 2, 3]);
+    assert_eq!(scan!(@from rd; usize;;), Vec::<usize>::new());
+    assert_eq!(scan!(@from rd; usize), 4);
+
+    // 書き込みがなければ入力の終わりとして扱う
+    assert_eq!(rd.next_token(), None);
+}
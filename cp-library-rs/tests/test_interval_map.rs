@@ -0,0 +1,104 @@
+#![allow(non_snake_case)]
+
+use cp_library_rs::data_structure::interval_map::*;
+use rand::*;
+
+/// 配列から区間の列を作る（値が等しい隣接要素はまとめる）
+fn pieces(arr: &[Option<u8>]) -> Vec<(i64, i64, u8)> {
+    let mut res: Vec<(i64, i64, u8)> = vec![];
+    for (i, &x) in arr.iter().enumerate() {
+        let Some(v) = x else { continue };
+        let i = i as i64;
+        match res.last_mut() {
+            Some((_, r, pv)) if *r == i && *pv == v => *r += 1,
+            _ => res.push((i, i + 1, v)),
+        }
+    }
+    res
+}
+
+#[test]
+fn test_random() {
+    const N: usize = 40;
+    let mut rng = rand::rng();
+
+    let mut map = IntervalMap::<i64, u8>::new();
+    let mut arr: Vec<Option<u8>> = vec![None; N];
+    // 値の総和
+    let mut sum = 0;
+
+    for _ in 0..3000 {
+        let l = rng.random_range(0..=N);
+        let r = rng.random_range(l..=N);
+        let (L, R) = (l as i64, r as i64);
+        match rng.random_range(0..4) {
+            0 | 1 => {
+                let v = rng.random_range(0..3);
+                sum += (R - L) * v as i64;
+                map.assign_with(L..R, v, |l, r, v| {
+                    assert!(L <= l && r <= R);
+                    sum -= (r - l) * v as i64;
+                });
+                arr[l..r].iter_mut().for_each(|x| *x = Some(v));
+            }
+            2 => {
+                map.erase_with(L..R, |l, r, v| sum -= (r - l) * v as i64);
+                arr[l..r].iter_mut().for_each(|x| *x = None);
+            }
+            _ => {
+                let expected: Vec<_> = pieces(&arr)
+                    .into_iter()
+                    .filter(|&(pl, pr, _)| pl.max(L) < pr.min(R))
+                    .collect();
+                let actual: Vec<_> = map.range(L..R).map(|(&l, &r, &v)| (l, r, v)).collect();
+                assert_eq!(actual, expected);
+            }
+        }
+
+        let actual: Vec<_> = map.iter().map(|(&l, &r, &v)| (l, r, v)).collect();
+        assert_eq!(actual, pieces(&arr));
+        assert_eq!(
+            sum,
+            arr.iter().map(|x| x.map_or(0, |v| v as i64)).sum::<i64>()
+        );
+        let k = rng.random_range(-1..=N as i64);
+        let expected = usize::try_from(k)
+            .ok()
+            .and_then(|k| arr.get(k).copied().flatten());
+        assert_eq!(map.get(&k).copied(), expected);
+    }
+}
+
+#[test]
+fn test_interval_set() {
+    let mut set = IntervalSet::<u64>::new();
+    set.assign(10..20, ());
+    set.assign(30..40, ());
+    set.assign(20..25, ());
+    set.assign(5..5, ());
+
+    assert_eq!(set.len(), 2);
+    assert_eq!(set.get_piece(&24), Some((&10, &25, &())));
+    assert_eq!(set.covered_len(0..100), 25);
+    assert_eq!(set.covered_len(15..35), 15);
+    assert_eq!(set.covered_len(26..30), 0);
+    assert_eq!(set.covered_len(12..12), 0);
+
+    set.erase(12..32);
+    let pieces: Vec<_> = set.iter().map(|(&l, &r, _)| (l, r)).collect();
+    assert_eq!(pieces, vec![(10, 12), (32, 40)]);
+    assert_eq!(set.covered_len(0..100), 10);
+}
+
+#[test]
+fn test_string_key() {
+    let mut map = IntervalMap::new();
+    map.assign("b".to_string().."m".to_string(), 1);
+    map.assign("d".to_string().."f".to_string(), 2);
+
+    assert_eq!(map.get(&"a".to_string()), None);
+    assert_eq!(map.get(&"c".to_string()), Some(&1));
+    assert_eq!(map.get(&"dz".to_string()), Some(&2));
+    assert_eq!(map.get(&"f".to_string()), Some(&1));
+    assert_eq!(map.len(), 3);
+}
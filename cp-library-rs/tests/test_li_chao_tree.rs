@@ -0,0 +1,188 @@
+#![allow(non_snake_case)]
+
+use cp_library_rs::{
+    algebraic_structure::operation::{Max, Min},
+    data_structure::li_chao_tree::*,
+    utils::ord_float::OrdF64,
+};
+use rand::*;
+
+/// 線分 `(a, b, l, r)` の `x` での最小値
+fn naive_min(lines: &[(i64, i64, i64, i64)], x: i64) -> i64 {
+    lines
+        .iter()
+        .filter(|&&(_, _, l, r)| l <= x && x < r)
+        .map(|&(a, b, _, _)| a * x + b)
+        .min()
+        .unwrap_or(i64::MAX)
+}
+
+#[test]
+fn test_static_random() {
+    let mut rng = rand::rng();
+
+    for _ in 0..20 {
+        let xs: Vec<i64> = (0..30).map(|_| rng.random_range(-50..50)).collect();
+        let mut lct = LiChaoTree::<Min<i64>>::new(xs.clone());
+        let mut lines = vec![];
+
+        for _ in 0..100 {
+            let (a, b) = (rng.random_range(-100..100), rng.random_range(-1000..1000));
+            if rng.random_bool(0.5) {
+                lct.add_line((a, b));
+                lines.push((a, b, i64::MIN, i64::MAX));
+            } else {
+                let l = rng.random_range(-60..60);
+                let r = rng.random_range(l..=60);
+                lct.add_segment((a, b), l..r);
+                lines.push((a, b, l, r));
+            }
+            for &x in &xs {
+                assert_eq!(lct.query(x), naive_min(&lines, x));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_static_max_float() {
+    let xs: Vec<OrdF64> = (0..=10).map(|i| OrdF64(i as f64 * 0.5)).collect();
+    let mut lct = LiChaoTree::<Max<OrdF64>>::new(xs);
+
+    assert_eq!(lct.query(OrdF64(1.0)), OrdF64(f64::MIN));
+
+    lct.add_line((OrdF64(-1.0), OrdF64(3.0)));
+    lct.add_line((OrdF64(2.0), OrdF64(-3.0)));
+    lct.add_segment((OrdF64(0.0), OrdF64(2.5)), OrdF64(1.0)..=OrdF64(3.0));
+
+    assert_eq!(lct.query(OrdF64(0.0)), OrdF64(3.0));
+    assert_eq!(lct.query(OrdF64(1.0)), OrdF64(2.5));
+    assert_eq!(lct.query(OrdF64(2.5)), OrdF64(2.5));
+    assert_eq!(lct.query(OrdF64(3.0)), OrdF64(3.0));
+    assert_eq!(lct.query(OrdF64(5.0)), OrdF64(7.0));
+}
+
+#[test]
+#[should_panic]
+fn test_static_unknown_point() {
+    let lct = LiChaoTree::<Min<i64>>::new(vec![0, 2]);
+    lct.query(1);
+}
+
+#[test]
+fn test_dynamic_random() {
+    const MIN: i64 = -1_000_000;
+    const MAX: i64 = 1_000_000;
+    let mut rng = rand::rng();
+
+    let mut lct = DynamicLiChaoTree::<i64, Min<i64>>::new(MIN, MAX);
+    let mut lines = vec![];
+
+    for _ in 0..500 {
+        let (a, b) = (
+            rng.random_range(-1000..1000),
+            rng.random_range(-1_000_000_000..1_000_000_000),
+        );
+        if rng.random_bool(0.5) {
+            lct.add_line((a, b));
+            lines.push((a, b, MIN, MAX));
+        } else {
+            let l = rng.random_range(MIN..MAX);
+            let r = rng.random_range(l..=MAX);
+            lct.add_segment((a, b), l..r);
+            lines.push((a, b, l, r));
+        }
+        for _ in 0..10 {
+            let x = rng.random_range(MIN..MAX);
+            assert_eq!(lct.query(x), naive_min(&lines, x));
+        }
+    }
+}
+
+#[test]
+fn test_dynamic_i128() {
+    let mut lct = DynamicLiChaoTree::<u64, Max<i128>>::new(0, u64::MAX);
+    let big = 1_i128 << 40;
+    lct.add_line((big, 0));
+    lct.add_line((-big, big << 20));
+
+    assert_eq!(lct.query(0), big << 20);
+    assert_eq!(lct.query(1 << 20), big << 20);
+    assert_eq!(lct.query(u64::MAX - 1), big * (u64::MAX - 1) as i128);
+}
+
+#[test]
+fn test_dynamic_full_range() {
+    // 座標の範囲の幅が型の範囲を超える
+    let mut lct = DynamicLiChaoTree::<i64, Min<i128>>::new(i64::MIN, i64::MAX);
+    lct.add_line((1, 0));
+    lct.add_line((-1, 0));
+    lct.add_segment((0, -5), i64::MAX - 10..);
+
+    for x in [i64::MIN, -1, 0, 1, i64::MAX - 11, i64::MAX - 1] {
+        let expected = -(x as i128).abs();
+        let expected = if x >= i64::MAX - 10 {
+            expected.min(-5)
+        } else {
+            expected
+        };
+        assert_eq!(lct.query(x), expected);
+    }
+
+    let mut lct = DynamicLiChaoTree::<i8, Max<i64>>::new(i8::MIN, i8::MAX);
+    lct.add_line((1, 0));
+    for x in i8::MIN..i8::MAX {
+        assert_eq!(lct.query(x), x as i64);
+    }
+}
+
+#[test]
+fn test_monotone_cht() {
+    let mut rng = rand::rng();
+
+    for _ in 0..50 {
+        // 傾きの降順
+        let mut slopes: Vec<i64> = (0..50).map(|_| rng.random_range(-20..20)).collect();
+        slopes.sort_by(|a, b| b.cmp(a));
+
+        let mut cht = MonotoneCHT::<Min<i64>>::new();
+        let mut lines = vec![];
+        for a in slopes {
+            let b = rng.random_range(-100..100);
+            cht.add_line((a, b));
+            lines.push((a, b, i64::MIN, i64::MAX));
+
+            let x = rng.random_range(-30..30);
+            assert_eq!(cht.query(x), naive_min(&lines, x));
+        }
+
+        // 昇順のクエリ
+        for x in -30..30 {
+            assert_eq!(cht.query_monotone(x), naive_min(&lines, x));
+        }
+    }
+}
+
+#[test]
+fn test_monotone_cht_max() {
+    let mut cht = MonotoneCHT::<Max<i64>>::new();
+    assert_eq!(cht.query(0), i64::MIN);
+
+    cht.add_line((-1, 0));
+    cht.add_line((0, -2));
+    cht.add_line((0, 1));
+    cht.add_line((1, 0));
+    assert_eq!(cht.len(), 3);
+
+    assert_eq!(cht.query(-3), 3);
+    assert_eq!(cht.query(0), 1);
+    assert_eq!(cht.query(3), 3);
+}
+
+#[test]
+#[should_panic]
+fn test_monotone_cht_wrong_order() {
+    let mut cht = MonotoneCHT::<Min<i64>>::new();
+    cht.add_line((1, 0));
+    cht.add_line((2, 0));
+}
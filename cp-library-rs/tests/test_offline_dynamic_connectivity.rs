@@ -0,0 +1,87 @@
+#![allow(non_snake_case)]
+
+use cp_library_rs::{
+    data_structure::union_find::UnionFind, graph::offline_dynamic_connectivity::*,
+};
+use rand::*;
+
+#[test]
+fn test_sample() {
+    let mut dc = OfflineDynamicConnectivity::new(4);
+    let q0 = dc.group_count();
+    dc.add_edge(0, 1);
+    dc.add_edge(1, 0);
+    dc.add_edge(2, 3);
+    let q1 = dc.is_same(1, 0);
+    dc.remove_edge(0, 1);
+    // 多重辺が1本残っている
+    let q2 = dc.is_same(0, 1);
+    dc.remove_edge(1, 0);
+    let q3 = dc.is_same(0, 1);
+    let q4 = dc.group_count();
+
+    let ans = dc.solve();
+    assert_eq!(ans.len(), 5);
+    assert_eq!(ans[q0], 4);
+    assert_eq!(ans[q1], 1);
+    assert_eq!(ans[q2], 1);
+    assert_eq!(ans[q3], 0);
+    assert_eq!(ans[q4], 3);
+}
+
+#[test]
+fn test_empty() {
+    assert!(OfflineDynamicConnectivity::new(3).solve().is_empty());
+}
+
+#[test]
+#[should_panic]
+fn test_remove_missing_edge() {
+    let mut dc = OfflineDynamicConnectivity::new(3);
+    dc.add_edge(0, 1);
+    dc.remove_edge(1, 2);
+}
+
+#[test]
+fn test_random() {
+    const N: usize = 12;
+    let mut rng = rand::rng();
+
+    for _ in 0..20 {
+        let mut dc = OfflineDynamicConnectivity::new(N);
+        let mut edges: Vec<(usize, usize)> = vec![];
+        let mut expected = vec![];
+
+        for _ in 0..300 {
+            match rng.random_range(0..4) {
+                0 => {
+                    let (u, v) = (rng.random_range(0..N), rng.random_range(0..N));
+                    dc.add_edge(u, v);
+                    edges.push((u, v));
+                }
+                1 if !edges.is_empty() => {
+                    let (u, v) = edges.swap_remove(rng.random_range(0..edges.len()));
+                    dc.remove_edge(v, u);
+                }
+                _ => {
+                    // 愚直に構築したものと比較
+                    let mut naive = UnionFind::new(N);
+                    for &(u, v) in &edges {
+                        naive.unite(u, v);
+                    }
+                    if rng.random_bool(0.5) {
+                        let (u, v) = (rng.random_range(0..N), rng.random_range(0..N));
+                        expected.push((dc.is_same(u, v), naive.is_same(u, v) as usize));
+                    } else {
+                        expected.push((dc.group_count(), naive.group_count()));
+                    }
+                }
+            }
+        }
+
+        let ans = dc.solve();
+        for (i, e) in expected {
+            assert_eq!(ans[i], e);
+        }
+    }
+}
@@ -0,0 +1,139 @@
+#![allow(non_snake_case)]
+
+use std::{cell::RefCell, io::Write, rc::Rc};
+
+use cp_library_rs::{
+    io::output::Output,
+    number_theory::{frac::Frac, modint::M998},
+    put,
+    utils::ord_float::OrdF64,
+};
+
+/// 書き込まれた内容を外から確認できる Writer
+#[derive(Clone, Default)]
+struct SharedBuf(Rc<RefCell<Vec<u8>>>);
+
+impl SharedBuf {
+    fn contents(&self) -> String {
+        String::from_utf8(self.0.borrow().clone()).unwrap()
+    }
+}
+
+impl Write for SharedBuf {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn output(f: impl FnOnce(&mut Output<&mut Vec<u8>>)) -> String {
+    let mut buf = vec![];
+    {
+        let mut out = Output::new(&mut buf);
+        f(&mut out);
+    }
+    String::from_utf8(buf).unwrap()
+}
+
+#[test]
+fn test_flush_on_drop() {
+    let buf = SharedBuf::default();
+    let mut out = Output::new(buf.clone());
+
+    put!(out; 1, 2, 3);
+    assert_eq!(buf.contents(), "");
+
+    out.flush();
+    assert_eq!(buf.contents(), "1 2 3\n");
+
+    put!(out; yesno false);
+    drop(out);
+    assert_eq!(buf.contents(), "1 2 3\nNo\n");
+}
+
+#[test]
+fn test_interactive_flush() {
+    let buf = SharedBuf::default();
+    let mut out = Output::interactive(buf.clone());
+
+    // 改行までは書き出さない
+    out.put(&1);
+    assert_eq!(buf.contents(), "");
+
+    out.endl();
+    assert_eq!(buf.contents(), "1\n");
+
+    put!(out; "?", 2, 3);
+    assert_eq!(buf.contents(), "1\n? 2 3\n");
+}
+
+#[test]
+fn test_sequences() {
+    let A = vec![3, 1, 4];
+
+    assert_eq!(output(|out| put!(out; join &A)), "3 1 4\n");
+    assert_eq!(output(|out| put!(out; join &A, ",")), "3,1,4\n");
+    assert_eq!(
+        output(|out| put!(out; join A.iter().map(|a| a * 2))),
+        "6 2 8\n"
+    );
+    assert_eq!(output(|out| put!(out; lines &A)), "3\n1\n4\n");
+    assert_eq!(output(|out| put!(out; join Vec::<usize>::new())), "\n");
+}
+
+#[test]
+fn test_grid() {
+    let grid = vec![vec![1, 2, 3], vec![4, 5, 6]];
+    assert_eq!(output(|out| put!(out; grid grid)), "1 2 3\n4 5 6\n");
+
+    let grid = vec![vec!['#', '.'], vec!['.', '#']];
+    assert_eq!(output(|out| put!(out; grid grid, "")), "#.\n.#\n");
+}
+
+#[test]
+fn test_yesno() {
+    assert_eq!(
+        output(|out| {
+            put!(out; yesno true);
+            put!(out; yesno 1 > 2);
+        }),
+        "Yes\nNo\n"
+    );
+}
+
+#[test]
+fn test_values() {
+    assert_eq!(
+        output(|out| put!(out; M998::new(998244354), -1isize, u128::MAX, 'c', "s")),
+        "1 -1 340282366920938463463374607431768211455 c s\n"
+    );
+}
+
+#[test]
+fn test_precision() {
+    let values = |out: &mut Output<&mut Vec<u8>>| {
+        put!(out; 0.5, OrdF64(2.0), Frac::new(1, 3), M998::new(7));
+    };
+
+    assert_eq!(output(values), "0.5 2 1/3 7\n");
+    assert_eq!(
+        output(|out| {
+            out.precision = Some(4);
+            values(out);
+        }),
+        "0.5000 2.0000 1/3 7\n"
+    );
+}
+
+#[test]
+fn test_frac() {
+    // 大きい分子・分母も誤差なく出力する
+    let p = 1_000_000_000_000_000_003_i128;
+    assert_eq!(
+        output(|out| put!(out; Frac::new(p, -3 * p + 1), Frac::new(-6_i64, -4))),
+        format!("-{p}/{} 3/2\n", 3 * p - 1)
+    );
+}
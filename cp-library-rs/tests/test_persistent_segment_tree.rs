@@ -0,0 +1,117 @@
+#![allow(non_snake_case)]
+
+use cp_library_rs::{
+    algebraic_structure::operation::{Add, Min},
+    data_structure::persistent_segment_tree::*,
+};
+use rand::*;
+
+#[test]
+fn test_versions() {
+    let mut seg = PersistentSegmentTree::<Add<isize>>::new(5);
+    let v0 = seg.initial();
+    let v1 = seg.update(v0, 1, 3);
+    let v2 = seg.update(v1, 3, -2);
+    let v3 = seg.update(v1, 1, 10);
+
+    assert_eq!(seg.get_range(v0, ..), 0);
+    assert_eq!(seg.get_range(v1, ..), 3);
+    assert_eq!(seg.get_range(v2, ..), 1);
+    assert_eq!(seg.get_range(v2, 2..), -2);
+    assert_eq!(seg.get_range(v3, ..), 10);
+    assert_eq!(*seg.get(v3, 1), 10);
+    assert_eq!(*seg.get(v2, 1), 3);
+}
+
+#[test]
+fn test_random_versions() {
+    const N: usize = 37;
+    let mut rng = rand::rng();
+
+    let init: Vec<i64> = (0..N).map(|_| rng.random_range(-100..100)).collect();
+    let mut seg = PersistentSegmentTree::<Min<i64>>::from_vec(init.clone());
+    let mut versions = vec![(seg.initial(), init)];
+
+    for _ in 0..1000 {
+        // 過去のバージョンを更新する
+        let (ver, mut arr) = versions[rng.random_range(0..versions.len())].clone();
+        let i = rng.random_range(0..N);
+        let x = rng.random_range(-100..100);
+        arr[i] = x;
+        versions.push((seg.update(ver, i, x), arr));
+
+        // 過去のバージョンを取得する
+        let (ver, arr) = &versions[rng.random_range(0..versions.len())];
+        let l = rng.random_range(0..=N);
+        let r = rng.random_range(l..=N);
+        let expected = arr[l..r].iter().copied().min().unwrap_or(i64::MAX);
+        assert_eq!(seg.get_range(*ver, l..r), expected);
+    }
+}
+
+#[test]
+fn test_binary_search() {
+    const N: usize = 20;
+    let mut rng = rand::rng();
+
+    let mut seg = PersistentSegmentTree::<Add<usize>>::new(N);
+    let mut versions = vec![(seg.initial(), vec![0; N])];
+
+    for _ in 0..300 {
+        let (ver, mut arr) = versions[rng.random_range(0..versions.len())].clone();
+        let i = rng.random_range(0..N);
+        arr[i] = rng.random_range(0..10);
+        versions.push((seg.update(ver, i, arr[i]), arr));
+
+        let (ver, arr) = &versions[rng.random_range(0..versions.len())];
+        let lim = rng.random_range(0..50);
+
+        // max_right
+        let l = rng.random_range(0..=N);
+        let x = (l..=N)
+            .take_while(|&x| arr[l..x].iter().sum::<usize>() <= lim)
+            .last()
+            .unwrap();
+        assert_eq!(
+            seg.max_right(*ver, l, |s| s <= lim),
+            (arr[l..x].iter().sum(), x)
+        );
+
+        // min_left
+        let r = rng.random_range(0..=N);
+        let x = (0..=r)
+            .rev()
+            .take_while(|&x| arr[x..r].iter().sum::<usize>() <= lim)
+            .last()
+            .unwrap();
+        assert_eq!(
+            seg.min_left(*ver, r, |s| s <= lim),
+            (arr[x..r].iter().sum(), x)
+        );
+    }
+}
+
+#[test]
+fn test_range_kth() {
+    const N: usize = 50;
+    let mut rng = rand::rng();
+
+    let A: Vec<i32> = (0..N).map(|_| rng.random_range(-10..10)).collect();
+    let kth = RangeKth::new(&A);
+
+    for l in 0..=N {
+        for r in l..=N {
+            let mut sorted = A[l..r].to_vec();
+            sorted.sort();
+            for k in 0..=r - l {
+                assert_eq!(kth.kth_smallest(l..r, k), sorted.get(k));
+                assert_eq!(kth.kth_largest(l..r, k), sorted.iter().rev().nth(k));
+            }
+            let x = rng.random_range(-11..11);
+            assert_eq!(
+                kth.count_less(l..r, &x),
+                sorted.iter().filter(|&&v| v < x).count()
+            );
+        }
+    }
+}
@@ -1 +1,103 @@
 #![allow(non_snake_case)]
+
+use cp_library_rs::data_structure::{rollback_unionfind::*, union_find::UnionFind};
+use rand::*;
+
+#[test]
+fn test_unite_undo() {
+    let mut uf = RollbackUnionFind::new(6);
+
+    assert!(uf.unite(0, 1).is_some());
+    assert!(uf.unite(2, 3).is_some());
+    assert!(uf.unite(1, 3).is_some());
+    /*
+     * 0-1-2-3 4 5
+     */
+    assert_eq!(uf.group_count(), 3);
+    assert_eq!(uf.size(2), 4);
+    assert!(uf.is_same(0, 3));
+
+    // 併合済みの場合も1回の操作として数える
+    assert_eq!(uf.unite(0, 2), None);
+    assert!(uf.undo());
+    assert!(uf.is_same(0, 3));
+
+    assert!(uf.undo());
+    /*
+     * 0-1 2-3 4 5
+     */
+    assert_eq!(uf.group_count(), 4);
+    assert!(!uf.is_same(0, 3));
+    assert_eq!(uf.size(0), 2);
+    assert_eq!(uf.size(3), 2);
+
+    assert!(uf.undo());
+    assert!(uf.undo());
+    assert!(!uf.undo());
+    assert_eq!(uf.group_count(), 6);
+    assert!((0..6).all(|i| uf.size(i) == 1));
+}
+
+#[test]
+fn test_snapshot_rollback() {
+    let mut uf = RollbackUnionFind::new(5);
+    uf.unite(0, 1);
+    let snapshot = uf.snapshot();
+
+    uf.unite(1, 2);
+    uf.unite(3, 4);
+    uf.unite(2, 4);
+    assert_eq!(uf.size(0), 5);
+
+    uf.rollback(snapshot);
+    assert_eq!(uf.snapshot(), snapshot);
+    assert_eq!(uf.group_count(), 4);
+    assert!(uf.is_same(0, 1));
+    assert!(!uf.is_same(1, 2));
+    assert!(!uf.is_same(3, 4));
+}
+
+#[test]
+fn test_random_rollback() {
+    const N: usize = 30;
+    let mut rng = rand::rng();
+
+    let mut uf = RollbackUnionFind::new(N);
+    // これまでの辺（rollback に合わせて切り詰める）
+    let mut edges: Vec<(usize, usize)> = vec![];
+    let mut snapshots = vec![];
+
+    for _ in 0..2000 {
+        match rng.random_range(0..10) {
+            0 => snapshots.push((uf.snapshot(), edges.len())),
+            1 => {
+                if let Some((snapshot, len)) = snapshots.pop() {
+                    uf.rollback(snapshot);
+                    edges.truncate(len);
+                }
+            }
+            2 => {
+                if uf.undo() {
+                    edges.pop();
+                }
+                snapshots.retain(|&(_, len)| len <= edges.len());
+            }
+            _ => {
+                let (u, v) = (rng.random_range(0..N), rng.random_range(0..N));
+                uf.unite(u, v);
+                edges.push((u, v));
+            }
+        }
+
+        // 愚直に構築したものと比較
+        let mut naive = UnionFind::new(N);
+        for &(u, v) in &edges {
+            naive.unite(u, v);
+        }
+        assert_eq!(uf.group_count(), naive.group_count());
+        for i in 0..N {
+            assert_eq!(uf.size(i), naive.get_size(i));
+            assert_eq!(uf.is_same(0, i), naive.is_same(0, i));
+        }
+    }
+}
@@ -0,0 +1,158 @@
+#![allow(non_snake_case)]
+
+use cp_library_rs::{
+    io::scanner::{Readable, Scanner},
+    number_theory::modint::{M998, MOD998},
+    scan,
+};
+
+#[test]
+fn test_parse_integers() {
+    assert_eq!(usize::parse(b"0"), 0);
+    assert_eq!(usize::parse(b"18446744073709551615"), usize::MAX);
+    assert_eq!(isize::parse(b"-9223372036854775808"), isize::MIN);
+    assert_eq!(isize::parse(b"9223372036854775807"), isize::MAX);
+    assert_eq!(i32::parse(b"+42"), 42);
+    assert_eq!(
+        u128::parse(b"340282366920938463463374607431768211455"),
+        u128::MAX
+    );
+    assert_eq!(
+        i128::parse(b"-170141183460469231731687303715884105728"),
+        i128::MIN
+    );
+}
+
+#[test]
+fn test_parse_others() {
+    assert_eq!(f64::parse(b"-1.25"), -1.25);
+    assert_eq!(char::parse(b"x"), 'x');
+    assert_eq!(String::parse(b"hello"), "hello".to_string());
+}
+
+#[test]
+fn test_parse_modint() {
+    assert_eq!(M998::parse(b"998244354"), M998::new(1));
+    assert_eq!(M998::parse(b"-1"), M998::new(MOD998 - 1));
+    // u128 にも収まらない値
+    let big = "1".repeat(50);
+    assert_eq!(M998::parse(big.as_bytes()), big.parse::<M998>().unwrap());
+}
+
+#[test]
+fn test_tokens_across_lines() {
+    let mut sc = Scanner::new("3\n1 2\n  3\n\n4 5 6".as_bytes());
+
+    let N = scan!(@from sc; usize);
+    assert_eq!(N, 3);
+
+    // 改行の位置によらずトークン単位で読む
+    let A = scan!(@from sc; isize; N);
+    assert_eq!(A, vec![1, 2, 3]);
+
+    let (a, b, c) = scan!(@from sc; usize1, u128, M998);
+    assert_eq!((a, b, c), (3, 5, M998::new(6)));
+
+    assert_eq!(sc.next_token(), None);
+}
+
+#[test]
+fn test_grammar() {
+    let input = "\
+4 2
+abc
+0 1
+2 3
+1 2 3
+4 5
+6
+-1 -2
+";
+    let mut sc = Scanner::new(input.as_bytes());
+
+    let (N, M) = scan!(@from sc; usize, usize);
+    assert_eq!((N, M), (4, 2));
+
+    let S = scan!(@from sc; chars);
+    assert_eq!(S, vec!['a', 'b', 'c']);
+
+    let E = scan!(@from sc; usize, usize1; M);
+    assert_eq!(E, vec![(0, 0), (2, 2)]);
+
+    // 1行ずつ
+    let rows = scan!(@from sc; usize;; 3);
+    assert_eq!(rows, vec![vec![1, 2, 3], vec![4, 5], vec![6]]);
+
+    let row = scan!(@from sc; isize;;);
+    assert_eq!(row, vec![-1, -2]);
+}
+
+#[test]
+fn test_line_after_tokens() {
+    // 読みかけの行の残りがある場合は，その残りを1行として読む
+    let mut sc = Scanner::new("1 2 3\n4 5\n\n6".as_bytes());
+
+    assert_eq!(scan!(@from sc; usize), 1);
+    assert_eq!(scan!(@from sc; usize;;), vec![2, 3]);
+    assert_eq!(scan!(@from sc; usize;;), vec![4, 5]);
+
+    // 空行は空の配列
+    assert_eq!(scan!(@from sc; usize;;), Vec::<usize>::new());
+    assert_eq!(scan!(@from sc; usize;;), vec![6]);
+}
+
+#[test]
+fn test_crlf() {
+    let mut sc = Scanner::new("2\r\nab\r\n1 2\r\n".as_bytes());
+
+    assert_eq!(scan!(@from sc; usize), 2);
+    assert_eq!(scan!(@from sc; String), "ab");
+    assert_eq!(scan!(@from sc; usize;;), vec![1, 2]);
+}
+
+/// 標準入力を与えてこのテストバイナリの `name` を子プロセスで実行し，その標準出力を返す
+fn run_with_stdin(name: &str, input: &str) -> String {
+    use std::{
+        io::Write,
+        process::{Command, Stdio},
+    };
+
+    let mut child = Command::new(std::env::current_exe().unwrap())
+        .args([name, "--exact", "--nocapture", "--test-threads=1"])
+        .env("SCAN_STDIN_CHILD", "1")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// 個数の式の中で標準入力を読む
+fn read_nested() -> usize {
+    scan!(usize)
+}
+
+#[test]
+fn test_stdin_nested_length() {
+    if std::env::var("SCAN_STDIN_CHILD").is_ok() {
+        let A = scan!(usize; scan!(usize));
+        let B = scan!(usize, char; read_nested());
+        let C = scan!(isize;; scan!(usize));
+        let S = scan!(String);
+        println!("RESULT {A:?} {B:?} {C:?} {S}");
+        return;
+    }
+    let stdout = run_with_stdin(
+        "test_stdin_nested_length",
+        "3\n1 2 3\n2\n4 a\n5 b\n2\n-1 -2\n3\nhello world\n",
+    );
+    assert!(stdout.contains("RESULT [1, 2, 3] [(4, 'a'), (5, 'b')] [[-1, -2], [3]] hello\n"));
+}
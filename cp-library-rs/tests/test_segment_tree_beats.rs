@@ -0,0 +1,195 @@
+#![allow(non_snake_case)]
+
+use cp_library_rs::data_structure::segment_tree_beats::*;
+use rand::*;
+
+#[test]
+fn test_hand() {
+    let mut seg = SegmentTreeBeats::from_vec(vec![2, 7, 1, 8, 2, 8]);
+    // [2, 7, 1, 8, 2, 8]
+
+    assert_eq!(seg.sum(..), 28);
+    assert_eq!(seg.max(..4), 8);
+    assert_eq!(seg.min(1..), 1);
+
+    seg.chmin(1..5, 3);
+    // [2, 3, 1, 3, 2, 8]
+
+    assert_eq!(seg.sum(..), 19);
+    assert_eq!(seg.max(..5), 3);
+    assert_eq!(seg.max(..), 8);
+
+    seg.chmax(.., 2);
+    // [2, 3, 2, 3, 2, 8]
+
+    assert_eq!(seg.sum(..), 20);
+    assert_eq!(seg.min(..), 2);
+
+    seg.add(2..=3, -5);
+    // [2, 3, -3, -2, 2, 8]
+
+    assert_eq!(seg.sum(..), 10);
+    assert_eq!(seg.min(..), -3);
+
+    seg.assign(1..5, 4);
+    // [2, 4, 4, 4, 4, 8]
+
+    assert_eq!(seg.sum(..), 26);
+    assert_eq!(seg.get(3), 4);
+    assert_eq!(seg.min(1..5), 4);
+    assert_eq!(seg.max(1..5), 4);
+
+    // 空の区間
+    assert_eq!(seg.sum(3..3), 0);
+    assert_eq!(seg.min(3..3), i32::MAX);
+    assert_eq!(seg.max(3..3), i32::MIN);
+}
+
+#[test]
+fn test_random() {
+    const N: usize = 50;
+    const QUERY: usize = 20_000;
+    let mut rng = rand::rng();
+
+    let mut arr: Vec<i64> = (0..N).map(|_| rng.random_range(-1000..1000)).collect();
+    let mut seg = SegmentTreeBeats::from_vec(arr.clone());
+
+    for _ in 0..QUERY {
+        let l = rng.random_range(0..=N);
+        let r = rng.random_range(l..=N);
+        let x = rng.random_range(-1000..1000);
+
+        match rng.random_range(0..7) {
+            0 => {
+                seg.chmin(l..r, x);
+                arr[l..r].iter_mut().for_each(|a| *a = (*a).min(x));
+            }
+            1 => {
+                seg.chmax(l..r, x);
+                arr[l..r].iter_mut().for_each(|a| *a = (*a).max(x));
+            }
+            2 => {
+                seg.add(l..r, x / 10);
+                arr[l..r].iter_mut().for_each(|a| *a += x / 10);
+            }
+            3 => {
+                seg.assign(l..r, x);
+                arr[l..r].iter_mut().for_each(|a| *a = x);
+            }
+            4 => assert_eq!(seg.sum(l..r), arr[l..r].iter().sum::<i64>()),
+            5 => assert_eq!(
+                seg.min(l..r),
+                arr[l..r].iter().copied().min().unwrap_or(i64::MAX)
+            ),
+            _ => assert_eq!(
+                seg.max(l..r),
+                arr[l..r].iter().copied().max().unwrap_or(i64::MIN)
+            ),
+        }
+    }
+
+    for (i, &a) in arr.iter().enumerate() {
+        assert_eq!(seg.get(i), a);
+    }
+}
+
+#[test]
+fn test_unsigned() {
+    let mut seg = SegmentTreeBeats::<u32>::new(4);
+    seg.add(.., 10);
+    seg.chmin(1..3, 4);
+    seg.chmax(2.., 6);
+    // [10, 4, 6, 10]
+    assert_eq!(seg.sum(..), 30);
+    assert_eq!(seg.min(..), 4);
+}
+
+#[test]
+#[should_panic]
+fn test_wrong_range() {
+    let mut seg = SegmentTreeBeats::<i64>::new(3);
+    seg.chmin(..4, 0);
+}
+
+#[test]
+fn test_random_unsigned_with_zeros() {
+    const N: usize = 40;
+    const QUERY: usize = 20_000;
+    let mut rng = rand::rng();
+
+    // 0 や小さい値が多く現れるようにする
+    let mut arr: Vec<u32> = (0..N).map(|_| rng.random_range(0..4)).collect();
+    let mut seg = SegmentTreeBeats::from_vec(arr.clone());
+
+    for _ in 0..QUERY {
+        let l = rng.random_range(0..=N);
+        let r = rng.random_range(l..=N);
+        let x = rng.random_range(0..8);
+
+        match rng.random_range(0..8) {
+            0 => {
+                seg.chmin(l..r, x);
+                arr[l..r].iter_mut().for_each(|a| *a = (*a).min(x));
+            }
+            1 => {
+                seg.chmax(l..r, x);
+                arr[l..r].iter_mut().for_each(|a| *a = (*a).max(x));
+            }
+            2 => {
+                seg.add(l..r, x);
+                arr[l..r].iter_mut().for_each(|a| *a += x);
+            }
+            3 => {
+                seg.assign(l..r, x);
+                arr[l..r].iter_mut().for_each(|a| *a = x);
+            }
+            4 => {
+                // 0 に戻す
+                seg.assign(l..r, 0);
+                arr[l..r].iter_mut().for_each(|a| *a = 0);
+            }
+            5 => assert_eq!(seg.sum(l..r), arr[l..r].iter().sum::<u32>()),
+            6 => assert_eq!(
+                seg.min(l..r),
+                arr[l..r].iter().copied().min().unwrap_or(u32::MAX)
+            ),
+            _ => assert_eq!(
+                seg.max(l..r),
+                arr[l..r].iter().copied().max().unwrap_or(u32::MIN)
+            ),
+        }
+    }
+
+    for (i, &a) in arr.iter().enumerate() {
+        assert_eq!(seg.get(i), a);
+    }
+}
+
+#[test]
+fn test_extreme_values() {
+    // 2番目の値が型の最小値・最大値に一致する場合
+    let mut seg = SegmentTreeBeats::from_vec(vec![0_u32, 5]);
+    seg.add(.., 3);
+    seg.chmin(.., 2);
+    assert_eq!(seg.sum(..), 4);
+    assert_eq!(seg.min(..), 2);
+    assert_eq!(seg.max(..), 2);
+
+    let mut seg = SegmentTreeBeats::from_vec(vec![0_u32, 0, 5, 5]);
+    seg.add(.., 3);
+    seg.chmin(.., 2);
+    assert_eq!(seg.sum(..), 8);
+
+    let mut seg = SegmentTreeBeats::from_vec(vec![i64::MIN, 5]);
+    seg.add(.., 3);
+    seg.chmin(.., 2);
+    assert_eq!(seg.min(..), i64::MIN + 3);
+    assert_eq!(seg.max(..), 2);
+    assert_eq!(seg.get(0), i64::MIN + 3);
+
+    let mut seg = SegmentTreeBeats::from_vec(vec![i64::MAX - 10, -5]);
+    seg.add(.., -3);
+    seg.chmax(.., 0);
+    assert_eq!(seg.min(..), 0);
+    assert_eq!(seg.max(..), i64::MAX - 13);
+}
@@ -1,6 +1,7 @@
 #![allow(non_snake_case)]
 
 use cp_library_rs::data_structure::trie::*;
+use rand::{rngs::ThreadRng, *};
 
 #[test]
 fn test_trie_node() {
@@ -31,3 +32,192 @@ fn test_trie_node() {
     let dict = trie.traverse();
     println!("{:?}", dict);
 }
+
+#[test]
+fn test_trie_str() {
+    let mut trie: Trie<usize> = Trie::default();
+
+    assert_eq!(trie.insert("powell", 5), None);
+    assert_eq!(trie.insert("pow", 3), None);
+    assert_eq!(trie.insert("kenta", 5), None);
+    assert_eq!(trie.insert("", 0), None);
+    assert_eq!(trie.insert("pow", 4), Some(3));
+    // 小文字以外の文字
+    assert_eq!(trie.insert("Ünïcödé", 7), None);
+
+    assert_eq!(trie.len(), 5);
+    assert_eq!(trie.get("pow"), Some(&4));
+    assert_eq!(trie.get("po"), None);
+    assert_eq!(trie.count_prefix("pow"), 2);
+    assert_eq!(trie.count_prefix(""), 5);
+    assert_eq!(trie.longest_common_prefix_with("powder"), 3);
+    assert_eq!(trie.kth(0), Some("".to_string()));
+    assert_eq!(trie.kth(2), Some("pow".to_string()));
+    assert_eq!(trie.kth(5), None);
+
+    assert_eq!(
+        trie.traverse(),
+        vec![
+            ("".to_string(), &0),
+            ("kenta".to_string(), &5),
+            ("pow".to_string(), &4),
+            ("powell".to_string(), &5),
+            ("Ünïcödé".to_string(), &7),
+        ]
+    );
+
+    assert_eq!(trie.remove("pow"), Some(4));
+    assert_eq!(trie.remove("pow"), None);
+    assert_eq!(trie.remove("unknown"), None);
+    assert_eq!(trie.len(), 4);
+    assert_eq!(trie.count_prefix("pow"), 1);
+    assert_eq!(trie.kth(2), Some("powell".to_string()));
+}
+
+#[test]
+fn test_generic_trie_random() {
+    let mut rng = rand::rng();
+    let mut trie = GenericTrie::<u32, ()>::new();
+    let mut words: Vec<Vec<u32>> = vec![];
+
+    let gen = |rng: &mut ThreadRng| -> Vec<u32> {
+        let len = rng.random_range(0..5);
+        (0..len).map(|_| rng.random_range(0..3)).collect()
+    };
+
+    for _ in 0..3000 {
+        let w = gen(&mut rng);
+        match rng.random_range(0..6) {
+            0 | 1 => {
+                trie.insert(&w);
+                words.push(w);
+            }
+            2 => {
+                let exists = words.iter().position(|v| *v == w);
+                assert_eq!(trie.remove(&w), exists.is_some());
+                if let Some(i) = exists {
+                    words.swap_remove(i);
+                }
+            }
+            3 => {
+                assert_eq!(trie.count(&w), words.iter().filter(|&v| *v == w).count());
+                assert_eq!(
+                    trie.count_prefix(&w),
+                    words.iter().filter(|&v| v.starts_with(&w)).count()
+                );
+            }
+            4 => {
+                let expected = words
+                    .iter()
+                    .map(|v| v.iter().zip(&w).take_while(|(a, b)| a == b).count())
+                    .max()
+                    .unwrap_or(0);
+                assert_eq!(trie.longest_common_prefix_with(&w), expected);
+            }
+            _ => {
+                let mut sorted = words.clone();
+                sorted.sort();
+                let k = rng.random_range(0..=sorted.len());
+                assert_eq!(trie.kth(k), sorted.get(k).cloned());
+            }
+        }
+        assert_eq!(trie.len(), words.len());
+    }
+}
+
+#[test]
+fn test_byte_trie() {
+    let mut trie = ByteTrie::<&str>::new();
+    *trie.get_or_insert_mut(b"ab") = Some("x");
+    *trie.get_or_insert_mut(b"a") = Some("y");
+    trie.insert_count(b"ab", 3);
+
+    // 値の設定だけでは個数は変化しない
+    assert_eq!(trie.count(b"a"), 0);
+    assert_eq!(trie.count(b"ab"), 3);
+    assert_eq!(trie.remove_count(b"ab", 2), 2);
+    assert_eq!(trie.get(b"ab"), Some(&"x"));
+    // 個数が 0 になると値も削除される
+    assert_eq!(trie.remove_count(b"ab", 5), 1);
+    assert_eq!(trie.get(b"ab"), None);
+    assert_eq!(trie.traverse(), vec![(b"a".to_vec(), &"y")]);
+}
+
+#[test]
+fn test_trie_get_or_insert_mut() {
+    let mut trie: Trie<usize> = Trie::default();
+    *trie.get_or_insert_mut("abc") = Some(1);
+    *trie.get_or_insert_mut("abc").as_mut().unwrap() += 1;
+    trie.get_or_insert_mut("ab").get_or_insert(5);
+
+    // 値を設定しない場合は個数に含まれない
+    assert!(trie.get_or_insert_mut("abd").is_none());
+    assert_eq!(trie.get_or_insert_mut("x").take(), None);
+    assert_eq!(trie.get("abd"), None);
+
+    // 値を設定した文字列は個数に含まれる
+    assert_eq!(trie.len(), 2);
+    assert_eq!(trie.count_prefix("ab"), 2);
+    assert_eq!(trie.count_prefix("abc"), 1);
+    assert_eq!(trie.kth(1), Some("abc".to_string()));
+
+    // 同じ文字列を挿入しても個数は変化しない
+    assert_eq!(trie.insert("abc", 3), Some(2));
+    assert_eq!(trie.len(), 2);
+
+    assert_eq!(trie.remove("abc"), Some(3));
+    assert_eq!(trie.len(), 1);
+    // `None` を書き込むと個数から除く
+    *trie.get_or_insert_mut("ab") = None;
+    assert_eq!(trie.len(), 0);
+    assert_eq!(trie.kth(0), None);
+    *trie.get_or_insert_mut("ab") = Some(5);
+    assert_eq!(trie.len(), 1);
+    assert_eq!(trie.count_prefix("abc"), 0);
+    assert_eq!(trie.get("abc"), None);
+    assert_eq!(trie.traverse(), vec![("ab".to_string(), &5)]);
+
+    // 削除後に再び追加する
+    assert_eq!(trie.insert("abc", 4), None);
+    assert_eq!(trie.len(), 2);
+    assert_eq!(
+        trie.traverse(),
+        vec![("ab".to_string(), &5), ("abc".to_string(), &4)]
+    );
+}
+
+#[test]
+fn test_trie_random() {
+    let mut rng = rand::rng();
+    let mut trie: Trie<u32> = Trie::default();
+    let mut map = std::collections::BTreeMap::new();
+
+    for _ in 0..3000 {
+        let len = rng.random_range(0..4);
+        let key: String = (0..len)
+            .map(|_| ['a', 'b', 'c'][rng.random_range(0..3)])
+            .collect();
+        let v = rng.random_range(0..100);
+        match rng.random_range(0..4) {
+            0 => assert_eq!(trie.insert(&key, v), map.insert(key.clone(), v)),
+            1 => {
+                let mut slot = trie.get_or_insert_mut(&key);
+                assert_eq!(slot.as_ref(), map.get(&key));
+                if v % 2 == 0 {
+                    *slot = Some(v);
+                    map.insert(key.clone(), v);
+                }
+            }
+            2 => assert_eq!(trie.remove(&key), map.remove(&key)),
+            _ => assert_eq!(
+                trie.count_prefix(&key),
+                map.keys().filter(|k| k.starts_with(&key)).count()
+            ),
+        }
+        assert_eq!(trie.len(), map.len());
+        assert_eq!(
+            trie.traverse(),
+            map.iter().map(|(k, v)| (k.clone(), v)).collect::<Vec<_>>()
+        );
+    }
+}
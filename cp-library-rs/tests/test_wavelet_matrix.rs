@@ -0,0 +1,167 @@
+#![allow(non_snake_case)]
+
+use std::collections::BTreeMap;
+
+use cp_library_rs::data_structure::wavelet_matrix::*;
+use rand::*;
+
+#[test]
+fn test_bit_vector() {
+    let bits: Vec<bool> = (0..200).map(|i| i % 3 == 0 || i % 7 == 0).collect();
+    let bv = BitVector::new(&bits);
+
+    let mut ones = 0;
+    for (i, &b) in bits.iter().enumerate() {
+        assert_eq!(bv.get(i), b);
+        assert_eq!(bv.rank1(i), ones);
+        assert_eq!(bv.rank0(i), i - ones);
+        ones += b as usize;
+    }
+    let pos: Vec<usize> = (0..200).filter(|&i| bits[i]).collect();
+    for (k, &p) in pos.iter().enumerate() {
+        assert_eq!(bv.select(true, k), Some(p));
+    }
+    assert_eq!(bv.select(true, pos.len()), None);
+    assert_eq!(bv.select(false, 0), Some(1));
+}
+
+#[test]
+fn test_random() {
+    const N: usize = 60;
+    let mut rng = rand::rng();
+
+    for max in [1, 2, 10, 1000, u64::MAX] {
+        let A: Vec<u64> = (0..N).map(|_| rng.random_range(0..=max)).collect();
+        let wm = WaveletMatrix::new(&A);
+        let values: Vec<u64> = A.iter().copied().chain([0, max, max / 2]).collect();
+
+        for (i, &a) in A.iter().enumerate() {
+            assert_eq!(wm.access(i), a);
+        }
+        for &x in &values {
+            let pos: Vec<usize> = (0..N).filter(|&i| A[i] == x).collect();
+            for i in 0..=N {
+                assert_eq!(wm.rank(x, i), A[..i].iter().filter(|&&a| a == x).count());
+            }
+            for k in 0..=pos.len() {
+                assert_eq!(wm.select(x, k), pos.get(k).copied());
+            }
+        }
+
+        for _ in 0..300 {
+            let l = rng.random_range(0..=N);
+            let r = rng.random_range(l..=N);
+            let mut sorted = A[l..r].to_vec();
+            sorted.sort();
+
+            let k = rng.random_range(0..=r - l);
+            assert_eq!(wm.kth_smallest(l..r, k), sorted.get(k).copied());
+            assert_eq!(wm.kth_largest(l..r, k), sorted.iter().rev().nth(k).copied());
+
+            let x = values[rng.random_range(0..values.len())];
+            let y = values[rng.random_range(0..values.len())];
+            assert_eq!(
+                wm.range_freq(l..r, x..y),
+                sorted.iter().filter(|&&a| x <= a && a < y).count()
+            );
+            assert_eq!(
+                wm.range_freq(l..r, x..=y),
+                sorted.iter().filter(|&&a| x <= a && a <= y).count()
+            );
+            assert_eq!(
+                wm.range_freq(l..r, x..),
+                sorted.iter().filter(|&&a| x <= a).count()
+            );
+            assert_eq!(
+                wm.prev_value(l..r, x),
+                sorted.iter().rev().find(|&&a| a < x).copied()
+            );
+            assert_eq!(
+                wm.next_value(l..r, x),
+                sorted.iter().find(|&&a| a >= x).copied()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_top_k() {
+    const N: usize = 100;
+    let mut rng = rand::rng();
+    let A: Vec<u64> = (0..N).map(|_| rng.random_range(0..12)).collect();
+    let wm = WaveletMatrix::new(&A);
+
+    for _ in 0..100 {
+        let l = rng.random_range(0..=N);
+        let r = rng.random_range(l..=N);
+        let k = rng.random_range(0..15);
+
+        let mut freq = BTreeMap::new();
+        for &a in &A[l..r] {
+            *freq.entry(a).or_insert(0) += 1;
+        }
+        let mut expected: Vec<(u64, usize)> = freq.into_iter().collect();
+        expected.sort_by_key(|&(v, c)| (std::cmp::Reverse(c), v));
+        expected.truncate(k);
+
+        assert_eq!(wm.top_k(l..r, k), expected);
+    }
+}
+
+#[test]
+fn test_sum() {
+    const N: usize = 80;
+    let mut rng = rand::rng();
+    let A: Vec<u64> = (0..N).map(|_| rng.random_range(0..1_000_000)).collect();
+    let wm = WaveletMatrixSum::new(&A);
+
+    for _ in 0..500 {
+        let l = rng.random_range(0..=N);
+        let r = rng.random_range(l..=N);
+        let mut sorted = A[l..r].to_vec();
+        sorted.sort();
+
+        let k = rng.random_range(0..=r - l + 2);
+        assert_eq!(wm.sum_smallest(l..r, k), sorted.iter().take(k).sum::<u64>());
+        assert_eq!(
+            wm.sum_largest(l..r, k),
+            sorted.iter().rev().take(k).sum::<u64>()
+        );
+    }
+}
+
+#[test]
+fn test_compressed() {
+    let A = vec![-3, 10, -3, 7, 100, 7, 7, -50];
+    let wm = CompressedWaveletMatrix::new(&A);
+
+    assert_eq!(wm.len(), 8);
+    assert_eq!(wm.access(4), &100);
+    assert_eq!(wm.rank(&7, 6), 2);
+    assert_eq!(wm.rank(&8, 8), 0);
+    assert_eq!(wm.select(&-3, 1), Some(2));
+    assert_eq!(wm.kth_smallest(1..6, 0), Some(&-3));
+    assert_eq!(wm.kth_largest(.., 0), Some(&100));
+    assert_eq!(wm.range_freq(.., -3..=7), 5);
+    assert_eq!(wm.range_freq(.., 0..), 5);
+    assert_eq!(wm.range_freq(.., -4..8), 5);
+    assert_eq!(wm.prev_value(.., &7), Some(&-3));
+    assert_eq!(wm.prev_value(.., &-50), None);
+    assert_eq!(wm.next_value(..4, &8), Some(&10));
+    assert_eq!(wm.top_k(.., 2), vec![(&7, 3), (&-3, 2)]);
+}
+
+#[test]
+fn test_empty() {
+    let wm = WaveletMatrix::new(&[]);
+    assert_eq!(wm.kth_smallest(.., 0), None);
+    assert_eq!(wm.range_freq(.., ..), 0);
+    assert!(wm.top_k(.., 3).is_empty());
+
+    // すべて 0
+    let wm = WaveletMatrix::new(&[0, 0, 0]);
+    assert_eq!(wm.kth_smallest(1.., 1), Some(0));
+    assert_eq!(wm.rank(0, 2), 2);
+    assert_eq!(wm.rank(1, 2), 0);
+    assert_eq!(wm.select(0, 2), Some(2));
+}
@@ -5,15 +5,61 @@ use std::{
 
 use anyhow::{bail, Context, Result};
 
-pub fn resolve_git_library_path(entry_file: &Path, crate_name: &str) -> Result<PathBuf> {
+/// `Cargo.lock` 中のパッケージ
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LockedPackage {
+    version: Option<String>,
+    /// パス依存の場合は `None`
+    source: Option<String>,
+}
+
+impl LockedPackage {
+    fn describe(&self) -> String {
+        let version = self.version.as_deref().unwrap_or("unknown");
+        match &self.source {
+            Some(source) => format!("{version} ({source})"),
+            None => format!("{version} (path)"),
+        }
+    }
+}
+
+/// `Cargo.lock` からライブラリのパスを解決する
+///
+/// - git 依存: cargo の git checkouts から探す
+/// - パス依存: ワークスペースの `Cargo.toml` に書かれた `path` から探す
+/// - レジストリ依存: `Cargo.lock` の隣の `vendor`，または cargo の registry のキャッシュから探す
+pub fn resolve_library_path(entry_file: &Path, crate_name: &str) -> Result<PathBuf> {
     let cargo_lock = find_cargo_lock(entry_file)?;
-    let source = find_git_source(&cargo_lock, crate_name)?;
-    let (repo_name, rev) = parse_git_source(&source)?;
-    let checkout = find_git_checkout(&repo_name, rev.as_deref())?;
-    find_crate_dir_in_checkout(&checkout, crate_name)
+    let packages = find_packages(&cargo_lock, crate_name)?;
+
+    if packages.len() > 1 {
+        let versions = packages
+            .iter()
+            .map(LockedPackage::describe)
+            .collect::<Vec<_>>()
+            .join(", ");
+        bail!("multiple versions of {crate_name} found in Cargo.lock: {versions}");
+    }
+
+    let package = &packages[0];
+    match package.source.as_deref() {
+        Some(source) if source.starts_with("git+") => {
+            let (repo_name, rev) = parse_git_source(source)?;
+            let checkout = find_git_checkout(&repo_name, rev.as_deref())?;
+            find_crate_dir_in_checkout(&checkout, crate_name)
+        }
+        Some(source) if source.starts_with("registry+") || source.starts_with("sparse+") => {
+            let Some(version) = &package.version else {
+                bail!("version of {crate_name} is missing in Cargo.lock");
+            };
+            find_registry_source(&cargo_lock, crate_name, version)
+        }
+        Some(source) => bail!("unsupported source for {crate_name}: {source}"),
+        None => find_path_dependency(&cargo_lock, entry_file, crate_name),
+    }
 }
 
-fn find_cargo_lock(entry_file: &Path) -> Result<PathBuf> {
+pub(crate) fn find_cargo_lock(entry_file: &Path) -> Result<PathBuf> {
     let mut dir = if entry_file.is_dir() {
         entry_file.to_path_buf()
     } else {
@@ -36,11 +82,11 @@ fn find_cargo_lock(entry_file: &Path) -> Result<PathBuf> {
     bail!("Cargo.lock not found from {:?}", entry_file);
 }
 
-fn find_git_source(cargo_lock: &Path, crate_name: &str) -> Result<String> {
+fn find_packages(cargo_lock: &Path, crate_name: &str) -> Result<Vec<LockedPackage>> {
     let contents = fs::read_to_string(cargo_lock)
         .with_context(|| format!("failed to read {:?}", cargo_lock))?;
 
-    let mut matches: Vec<(Option<String>, Option<String>)> = Vec::new();
+    let mut matches = Vec::new();
 
     let mut in_package = false;
     let mut name: Option<String> = None;
@@ -52,7 +98,10 @@ fn find_git_source(cargo_lock: &Path, crate_name: &str) -> Result<String> {
         if line == "[[package]]" {
             if let Some(n) = name.take() {
                 if n == crate_name {
-                    matches.push((version.take(), source.take()));
+                    matches.push(LockedPackage {
+                        version: version.take(),
+                        source: source.take(),
+                    });
                 }
             }
             in_package = true;
@@ -77,7 +126,7 @@ fn find_git_source(cargo_lock: &Path, crate_name: &str) -> Result<String> {
 
     if let Some(n) = name {
         if n == crate_name {
-            matches.push((version, source));
+            matches.push(LockedPackage { version, source });
         }
     }
 
@@ -85,36 +134,217 @@ fn find_git_source(cargo_lock: &Path, crate_name: &str) -> Result<String> {
         bail!("crate not found in Cargo.lock: {crate_name}");
     }
 
-    let git_matches: Vec<(Option<String>, String)> = matches
-        .into_iter()
-        .filter_map(|(version, source)| {
-            source.and_then(|s| {
-                if s.starts_with("git+") {
-                    Some((version, s))
-                } else {
-                    None
-                }
-            })
-        })
-        .collect();
+    Ok(matches)
+}
 
-    if git_matches.is_empty() {
-        bail!("crate is not a git dependency: {crate_name}");
+/// パス依存のクレートを，ワークスペースの `Cargo.toml` の `path` から探す
+fn find_path_dependency(cargo_lock: &Path, entry_file: &Path, crate_name: &str) -> Result<PathBuf> {
+    let root = cargo_lock
+        .parent()
+        .context("Cargo.lock has no parent directory")?;
+
+    // ワークスペースのルート，メンバー，入力ファイルからルートまでの各ディレクトリ
+    let mut dirs = vec![root.to_path_buf()];
+    dirs.extend(workspace_members(root)?);
+    let mut dir = entry_file.parent();
+    while let Some(d) = dir {
+        if !d.starts_with(root) {
+            break;
+        }
+        dirs.push(d.to_path_buf());
+        dir = d.parent();
+    }
+
+    let mut candidates: Vec<PathBuf> = Vec::new();
+    for dir in dirs {
+        let manifest = dir.join("Cargo.toml");
+        if !manifest.exists() {
+            continue;
+        }
+        let contents = fs::read_to_string(&manifest)
+            .with_context(|| format!("failed to read {:?}", manifest))?;
+        for path in find_dependency_paths(&contents, crate_name) {
+            let path = dir.join(path);
+            if !is_crate_root(&path, crate_name)? {
+                continue;
+            }
+            let path = path.canonicalize().unwrap_or(path);
+            if !candidates.contains(&path) {
+                candidates.push(path);
+            }
+        }
+    }
+
+    match candidates.len() {
+        0 => bail!("path dependency not found in workspace manifests: {crate_name} under {root:?}"),
+        1 => Ok(candidates.remove(0)),
+        _ => {
+            let paths = candidates
+                .iter()
+                .map(|p| format!("{p:?}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            bail!("multiple path dependencies found for {crate_name}: {paths}");
+        }
+    }
+}
+
+/// `[workspace]` の `members` のディレクトリ（末尾の `/*` のみ展開する）
+fn workspace_members(root: &Path) -> Result<Vec<PathBuf>> {
+    let manifest = root.join("Cargo.toml");
+    if !manifest.exists() {
+        return Ok(vec![]);
+    }
+    let contents =
+        fs::read_to_string(&manifest).with_context(|| format!("failed to read {:?}", manifest))?;
+
+    let mut members = Vec::new();
+    let mut in_workspace = false;
+    let mut in_members = false;
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.starts_with('[') && !in_members {
+            in_workspace = line == "[workspace]";
+            continue;
+        }
+        if !in_workspace {
+            continue;
+        }
+        let values = if in_members {
+            line
+        } else if let Some(rest) = line.strip_prefix("members") {
+            in_members = true;
+            rest.trim_start().trim_start_matches('=').trim()
+        } else {
+            continue;
+        };
+        if values.contains(']') {
+            in_members = false;
+        }
+        for value in values.trim_matches(|c| c == '[' || c == ']').split(',') {
+            let value = value.trim().trim_matches('"');
+            if value.is_empty() {
+                continue;
+            }
+            match value.strip_suffix("/*") {
+                Some(parent) => {
+                    let parent = root.join(parent);
+                    if let Ok(entries) = fs::read_dir(&parent) {
+                        for entry in entries {
+                            let path = entry?.path();
+                            if path.is_dir() {
+                                members.push(path);
+                            }
+                        }
+                    }
+                }
+                None => members.push(root.join(value)),
+            }
+        }
     }
 
-    if git_matches.len() > 1 {
-        let versions = git_matches
+    Ok(members)
+}
+
+/// `Cargo.toml` の依存関係のうち，`crate_name` を指す `path` を抜き出す
+///
+/// - `crate_name = { path = ".." }`
+/// - `alias = { package = "crate_name", path = ".." }`
+/// - `[dependencies.crate_name]` の下の `path = ".."`
+fn find_dependency_paths(contents: &str, crate_name: &str) -> Vec<String> {
+    let mut paths = Vec::new();
+    // 依存関係のテーブル（`[dependencies.crate_name]`）の中か
+    let mut in_table = false;
+    let mut in_dependencies = false;
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            let section = line.trim_matches(|c| c == '[' || c == ']');
+            in_dependencies = section.ends_with("dependencies");
+            in_table = section
+                .rsplit_once('.')
+                .is_some_and(|(table, name)| table.ends_with("dependencies") && name == crate_name);
+            continue;
+        }
+        if in_table {
+            if let Some(path) = parse_string_value(line, "path") {
+                paths.push(path);
+            }
+            continue;
+        }
+        if !in_dependencies {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let Some(table) = value
+            .trim()
+            .strip_prefix('{')
+            .and_then(|v| v.strip_suffix('}'))
+        else {
+            continue;
+        };
+        let fields: Vec<&str> = table.split(',').map(str::trim).collect();
+        let package = fields
             .iter()
-            .map(|(version, _)| version.clone().unwrap_or_else(|| "unknown".to_string()))
-            .collect::<Vec<_>>()
-            .join(", ");
-        bail!("multiple git dependencies found for {crate_name} in Cargo.lock: {versions}");
+            .find_map(|field| parse_string_value(field, "package"));
+        if package.as_deref().unwrap_or(key.trim()) != crate_name {
+            continue;
+        }
+        if let Some(path) = fields
+            .iter()
+            .find_map(|field| parse_string_value(field, "path"))
+        {
+            paths.push(path);
+        }
+    }
+
+    paths
+}
+
+/// レジストリ依存のクレートのソースを探す
+fn find_registry_source(cargo_lock: &Path, crate_name: &str, version: &str) -> Result<PathBuf> {
+    let dir_name = format!("{crate_name}-{version}");
+
+    // `cargo vendor` されたディレクトリ
+    if let Some(root) = cargo_lock.parent() {
+        for vendored in [
+            root.join("vendor").join(&dir_name),
+            root.join("vendor").join(crate_name),
+        ] {
+            if is_crate_root(&vendored, crate_name)? {
+                return Ok(vendored);
+            }
+        }
+    }
+
+    let registry_dir = cargo_home_dir()?.join("registry").join("src");
+    if !registry_dir.exists() {
+        bail!("cargo registry directory not found: {:?}", registry_dir);
     }
 
-    Ok(git_matches[0].1.clone())
+    let mut found = Vec::new();
+    for entry in
+        fs::read_dir(&registry_dir).with_context(|| format!("failed to read {:?}", registry_dir))?
+    {
+        let path = entry?.path().join(&dir_name);
+        if is_crate_root(&path, crate_name)? {
+            found.push(path);
+        }
+    }
+
+    if found.is_empty() {
+        bail!("{dir_name} not found in cargo registry: {:?}", registry_dir);
+    }
+
+    // 同じバージョンであれば中身は同じなので，どのインデックスのものでもよい
+    found.sort();
+    Ok(found.remove(0))
 }
 
-fn parse_string_value(line: &str, key: &str) -> Option<String> {
+pub(crate) fn parse_string_value(line: &str, key: &str) -> Option<String> {
     let prefix = format!("{key} =");
     if !line.starts_with(&prefix) {
         return None;
@@ -275,9 +505,14 @@ fn find_crate_dir_in_checkout(checkout: &Path, crate_name: &str) -> Result<PathB
 }
 
 fn is_crate_root(dir: &Path, crate_name: &str) -> Result<bool> {
+    Ok(package_name(dir)?.is_some_and(|name| name == crate_name))
+}
+
+/// `dir/Cargo.toml` の `[package]` の `name`（マニフェストがない場合は `None`）
+pub(crate) fn package_name(dir: &Path) -> Result<Option<String>> {
     let manifest = dir.join("Cargo.toml");
     if !manifest.exists() {
-        return Ok(false);
+        return Ok(None);
     }
     let contents =
         fs::read_to_string(&manifest).with_context(|| format!("failed to read {:?}", manifest))?;
@@ -294,10 +529,10 @@ fn is_crate_root(dir: &Path, crate_name: &str) -> Result<bool> {
         }
         if in_package {
             if let Some(value) = parse_string_value(line, "name") {
-                return Ok(value == crate_name);
+                return Ok(Some(value));
             }
         }
     }
 
-    Ok(false)
+    Ok(None)
 }
@@ -0,0 +1,205 @@
+//! 展開結果をローカルでコンパイルし，エラーの位置を展開元のファイルに対応付ける
+
+use std::{
+    env, fmt, fs,
+    path::{Path, PathBuf},
+    process::{self, Command},
+};
+
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+
+use crate::expander::ModuleExpander;
+
+/// コンパイラの診断メッセージ
+#[derive(Debug)]
+pub struct Diagnostic {
+    /// `error`，`warning` など
+    pub level: String,
+    pub message: String,
+    /// 元のファイルでの位置（ファイル，行，列）
+    ///
+    /// - 列を対応付けられない場合は `None`
+    pub location: Option<(PathBuf, usize, Option<usize>)>,
+}
+
+impl Diagnostic {
+    pub fn is_error(&self) -> bool {
+        self.level == "error"
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.level, self.message)?;
+        match &self.location {
+            Some((file, line, Some(column))) => {
+                write!(f, "\n  --> {}:{line}:{column}", file.display())?
+            }
+            Some((file, line, None)) => write!(f, "\n  --> {}:{line}", file.display())?,
+            None => (),
+        }
+        Ok(())
+    }
+}
+
+/// 展開結果を一時ディレクトリに書き出して `cargo check` を実行する
+///
+/// 展開したライブラリ部分の診断は，展開元の `src/<category>/<file>.rs` の行に対応付ける
+pub fn check(expander: &mut ModuleExpander) -> Result<Vec<Diagnostic>> {
+    let contents = expander.expand()?;
+
+    let dir = env::temp_dir().join(format!("expander-check-{}", process::id()));
+    fs::create_dir_all(dir.join("src")).with_context(|| format!("failed to create {:?}", dir))?;
+
+    let result = run_cargo_check(expander, &dir, &contents);
+    fs::remove_dir_all(&dir).ok();
+
+    result
+}
+
+fn run_cargo_check(
+    expander: &ModuleExpander,
+    dir: &Path,
+    contents: &str,
+) -> Result<Vec<Diagnostic>> {
+    let library_root = expander.library_root();
+
+    fs::write(dir.join("Cargo.toml"), manifest(library_root)?)?;
+    let cargo_lock = library_root.join("Cargo.lock");
+    if cargo_lock.exists() {
+        fs::copy(&cargo_lock, dir.join("Cargo.lock"))?;
+    }
+    fs::write(dir.join("src").join("main.rs"), contents)?;
+
+    // 依存クレートのビルド結果は使い回す
+    let target_dir = env::temp_dir().join("expander-check-target");
+
+    // ジャッジと同じバージョンの rustc があればそれを使う
+    let toolchain = expander
+        .profile
+        .as_ref()
+        .and_then(|p| p.rustc.as_ref())
+        .filter(|rustc| {
+            let installed = toolchain_installed(rustc);
+            if !installed {
+                log::warn!(
+                    "toolchain {rustc} is not installed; checking with the default toolchain"
+                );
+            }
+            installed
+        });
+
+    let mut command = match toolchain {
+        Some(rustc) => {
+            let mut command = Command::new("cargo");
+            command.env("RUSTUP_TOOLCHAIN", rustc);
+            command
+        }
+        None => Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string())),
+    };
+    command
+        .args(["check", "--quiet", "--message-format=json"])
+        .current_dir(dir)
+        .env("CARGO_TARGET_DIR", target_dir);
+
+    let output = command.output().context("failed to run cargo")?;
+
+    let mut diagnostics = vec![];
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let Ok(value) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        if value["reason"] != "compiler-message" {
+            continue;
+        }
+        if let Some(diagnostic) = to_diagnostic(expander, &value["message"]) {
+            diagnostics.push(diagnostic);
+        }
+    }
+
+    if !output.status.success() && diagnostics.iter().all(|d| !d.is_error()) {
+        bail!(
+            "cargo check failed:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    Ok(diagnostics)
+}
+
+fn toolchain_installed(toolchain: &str) -> bool {
+    Command::new("rustup")
+        .args(["run", toolchain, "rustc", "--version"])
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+/// ライブラリの依存クレートをそのまま使う `Cargo.toml`
+fn manifest(library_root: &Path) -> Result<String> {
+    let library_manifest = library_root.join("Cargo.toml");
+    let contents = fs::read_to_string(&library_manifest)
+        .with_context(|| format!("failed to read {:?}", library_manifest))?;
+
+    let mut edition = "2021".to_string();
+    let mut dependencies = vec![];
+    let mut section = "";
+
+    for line in contents.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            section = trimmed;
+            continue;
+        }
+        match section {
+            "[package]" => {
+                if let Some(value) = trimmed
+                    .strip_prefix("edition")
+                    .and_then(|rest| rest.trim().strip_prefix('='))
+                {
+                    edition = value.trim().trim_matches('"').to_string();
+                }
+            }
+            "[dependencies]" => dependencies.push(line),
+            _ => (),
+        }
+    }
+
+    Ok(format!(
+        "[package]\nname = \"expander-check\"\nversion = \"0.1.0\"\nedition = \"{edition}\"\n\n[workspace]\n\n[dependencies]\n{}\n",
+        dependencies.join("\n")
+    ))
+}
+
+fn to_diagnostic(expander: &ModuleExpander, message: &Value) -> Option<Diagnostic> {
+    let level = message["level"].as_str()?.to_string();
+    let text = message["message"].as_str()?.to_string();
+
+    let spans = message["spans"].as_array()?;
+    let span = spans
+        .iter()
+        .find(|span| span["is_primary"].as_bool() == Some(true))
+        .or(spans.first());
+
+    // `aborting due to ..` などの要約や，位置を持たない補足は除く
+    let Some(span) = span else {
+        if level != "error" || text.starts_with("aborting due to") {
+            return None;
+        }
+        return Some(Diagnostic {
+            level,
+            message: text,
+            location: None,
+        });
+    };
+
+    let line = span["line_start"].as_u64()? as usize;
+    let column = span["column_start"].as_u64()? as usize;
+    let location = expander.map_location(line, column);
+
+    Some(Diagnostic {
+        level,
+        message: text,
+        location,
+    })
+}
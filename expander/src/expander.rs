@@ -1,18 +1,92 @@
 use std::{
-    collections::BTreeSet,
+    collections::{BTreeMap, BTreeSet},
     fmt::Write,
     fs,
     path::{Path, PathBuf},
 };
 
 use anyhow::bail;
-use itertools::Itertools;
 
-use crate::{module_path::ModulePath, parser::get_deps};
+use crate::{
+    cargo_lock::package_name,
+    graph::DependencyGraph,
+    minify::minify,
+    module_path::ModulePath,
+    parser::get_paths,
+    profile::{external_crates, Profile},
+    rewrite::{prefix_crate_paths, qualify_library_paths, unqualify_library_paths},
+    shake::TreeShaker,
+};
 
 /// タブ文字
 const TAB: &str = "    ";
 
+/// 展開元のライブラリのファイルとその行番号，列のずれ（列を対応付けられない場合は `None`）
+type Origin = (ModulePath, usize, Option<isize>);
+
+/// ライブラリ内のパス（クレート名以降）を，それを含むファイルに解決する
+///
+/// - `[category]`，`[category, *]`：カテゴリ内のすべてのファイル
+/// - `[macro, ..]`：ライブラリ直下のファイル
+/// - `[category, file, ..]`：カテゴリ内のファイル
+pub fn resolve_path(
+    library_path: &Path,
+    path: &[String],
+) -> anyhow::Result<Vec<ModulePath>, anyhow::Error> {
+    match path {
+        [category] if library_path.join(category).join("mod.rs").exists() => {
+            category_modules(library_path, category)
+        }
+        [category, file, ..] if file == "*" => category_modules(library_path, category),
+        [file, ..] if library_path.join(file).with_extension("rs").is_file() => {
+            Ok(vec![ModulePath::Macro { file: file.clone() }])
+        }
+        [category, file, ..]
+            if library_path
+                .join(category)
+                .join(file)
+                .with_extension("rs")
+                .is_file() =>
+        {
+            Ok(vec![ModulePath::Module {
+                category: category.clone(),
+                file: file.clone(),
+            }])
+        }
+        _ => Ok(vec![]),
+    }
+}
+
+/// カテゴリの `mod.rs` で宣言されているファイルの一覧
+fn category_modules(
+    library_path: &Path,
+    category: &str,
+) -> anyhow::Result<Vec<ModulePath>, anyhow::Error> {
+    let p = library_path.join(category).join("mod.rs");
+    let source = match fs::read_to_string(&p) {
+        Ok(source) => source,
+        Err(err) => bail!("{}: {:?}", err, p),
+    };
+    let syntax_tree = syn::parse_file(&source)?;
+
+    Ok(syntax_tree
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            syn::Item::Mod(item_mod) if item_mod.content.is_none() => Some(ModulePath::Module {
+                category: category.to_string(),
+                file: item_mod.ident.to_string(),
+            }),
+            _ => None,
+        })
+        .collect())
+}
+
+/// 行頭の空白の幅
+fn indent_width(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
 #[derive(Debug)]
 pub struct ModuleExpander {
     /// 呼び出し元のファイル
@@ -25,17 +99,36 @@ pub struct ModuleExpander {
     pub import_name: String,
     /// ライブラリ自体の名前
     pub library_name: String,
+    /// 到達可能な要素だけを展開するか
+    pub tree_shaking: bool,
+    /// ライブラリ部分を縮小するか
+    pub minify: bool,
+    /// 展開結果の大きさの上限（バイト）
+    pub max_bytes: Option<usize>,
+    /// ジャッジの環境（使用できる外部クレート）
+    pub profile: Option<Profile>,
+    /// モジュール間の依存関係（`solve_dependancies` で求める）
+    pub graph: DependencyGraph,
+    /// 展開後の行番号 -> 展開元のライブラリのファイルとその行番号
+    line_map: BTreeMap<usize, Origin>,
+    /// 展開後のファイルのうち，呼び出し元のコードの行数
+    user_lines: usize,
+    /// 呼び出し元のコードのうち，パスの書き換えで列がずれた行
+    rewritten_lines: BTreeSet<usize>,
 }
 
 impl ModuleExpander {
     /// パーサーの初期化
     pub fn new(entry_file: PathBuf, mut library_path: PathBuf) -> anyhow::Result<Self, String> {
-        // ライブラリ名
-        let Some(library_name) = library_path
-            .file_name()
-            .map(|s| s.to_str().unwrap().to_string())
-        else {
-            return Err("No such library: {library_path:?}".to_string());
+        // ライブラリ名（`Cargo.toml` の `[package]` の `name`，なければディレクトリ名）
+        // registry や vendor のディレクトリ名は `<crate>-<version>` となるため，マニフェストを優先する
+        let library_name = match package_name(&library_path) {
+            Ok(Some(name)) => name,
+            Ok(None) => match library_path.file_name().and_then(|s| s.to_str()) {
+                Some(name) => name.to_string(),
+                None => return Err(format!("No such library: {library_path:?}")),
+            },
+            Err(err) => return Err(err.to_string()),
         };
 
         // インポート時の名前
@@ -49,35 +142,55 @@ impl ModuleExpander {
             library_path,
             import_name,
             library_name,
+            tree_shaking: false,
+            minify: false,
+            max_bytes: None,
+            profile: None,
+            graph: DependencyGraph::default(),
+            line_map: BTreeMap::new(),
+            user_lines: 0,
+            rewritten_lines: BTreeSet::new(),
         })
     }
 
     /// 再帰的に依存関係を解析する
     /// 結果を`self.dependancies`に保存する
     pub fn solve_dependancies(&mut self) -> anyhow::Result<(), anyhow::Error> {
-        let mut deps = BTreeSet::new();
+        let mut graph = DependencyGraph::default();
+        let mut found = vec![];
 
-        // entry_fileを解析
-        let source = match fs::read_to_string(&self.entry_file) {
-            Ok(contents) => contents,
-            Err(err) => bail!("{}: {:?}", err, self.entry_file),
-        };
+        // entry_fileを解析（展開済みの部分は除く）
+        let source = self.read_entry()?;
 
-        for dep in get_deps(&source, &self.import_name) {
-            deps.insert(dep.clone());
-            Self::dfs(&dep, &self.library_path, &mut deps)?;
+        for path in get_paths(&source, &self.import_name) {
+            let resolved = resolve_path(&self.library_path, &path)?;
+            if resolved.is_empty() {
+                bail!("unresolved path: {}::{}", self.import_name, path.join("::"));
+            }
+            let path = format!("{}::{}", self.import_name, path.join("::"));
+            for dep in resolved {
+                if graph.add_edge(&None, &dep, path.clone()) {
+                    found.push(dep);
+                }
+            }
+        }
+
+        // 直接使っているモジュールを先に登録してから探索する
+        for dep in found {
+            Self::dfs(&dep, &self.library_path, &mut graph)?;
         }
 
-        self.dependancies = Some(deps);
+        self.dependancies = Some(graph.parents.keys().cloned().collect());
+        self.graph = graph;
 
         Ok(())
     }
 
-    /// 再帰的に依存関係を解析し，depsに追加する
+    /// 再帰的に依存関係を解析し，graphに追加する
     fn dfs(
         dep: &ModulePath,
         library_path: &Path,
-        deps: &mut BTreeSet<ModulePath>,
+        graph: &mut DependencyGraph,
     ) -> anyhow::Result<(), anyhow::Error> {
         // パスの生成
         let p = dep.to_pathbuf(library_path.to_path_buf());
@@ -87,13 +200,54 @@ impl ModuleExpander {
             Err(err) => bail!("{}: {:?}", err, p),
         };
 
-        for dep in get_deps(&source, "crate") {
-            if deps.contains(&dep) {
+        let mut found = vec![];
+        for path in get_paths(&source, "crate") {
+            let resolved = resolve_path(library_path, &path)?;
+            if resolved.is_empty() {
+                bail!("unresolved path: crate::{} in {:?}", path.join("::"), p);
+            }
+            let path = format!("crate::{}", path.join("::"));
+            found.extend(resolved.into_iter().map(|dep| (dep, path.clone())));
+        }
+        // 同じカテゴリのファイルへの相対パス（`super::file::..`）
+        if let ModulePath::Module { category, .. } = dep {
+            for path in get_paths(&source, "super") {
+                let resolved = match &path[..] {
+                    // カテゴリ全体を取り込むことになるため，グロブは許可しない
+                    [glob] if glob == "*" => {
+                        bail!(
+                            "glob import of the parent module is not supported: super::* in {:?}",
+                            p
+                        )
+                    }
+                    // `super::super::..` はクレート直下からのパス
+                    [parent, rest @ ..] if parent == "super" => resolve_path(library_path, rest)?,
+                    _ => resolve_path(
+                        library_path,
+                        &std::iter::once(category.clone())
+                            .chain(path.iter().cloned())
+                            .collect::<Vec<_>>(),
+                    )?,
+                };
+                let path = format!("super::{}", path.join("::"));
+                found.extend(resolved.into_iter().map(|dep| (dep, path.clone())));
+            }
+        }
+
+        let from = Some(dep.clone());
+        let mut added = vec![];
+        for (next, path) in found {
+            // 自分自身への参照は辺に含めない
+            if &next == dep {
                 continue;
             }
-            deps.insert(dep.clone());
-            // 再帰的に探索
-            Self::dfs(&dep, library_path, deps)?;
+            if graph.add_edge(&from, &next, path) {
+                added.push(next);
+            }
+        }
+        // 再帰的に探索
+        for next in added {
+            Self::dfs(&next, library_path, graph)?;
         }
 
         Ok(())
@@ -106,11 +260,11 @@ impl ModuleExpander {
             self.solve_dependancies()?;
         }
 
-        // 元ファイル読み取り
-        let mut contents = match fs::read_to_string(&self.entry_file) {
-            Ok(contents) => contents,
-            Err(err) => bail!("{}: {:?}", err, self.entry_file),
-        };
+        // 元ファイル読み取り（展開済みの場合は元に戻す）
+        let mut contents = self.read_entry()?;
+        self.user_lines = contents.lines().count();
+        self.line_map.clear();
+        self.rewritten_lines.clear();
 
         // 依存していない場合
         if self.dependancies.as_ref().unwrap().is_empty() {
@@ -118,86 +272,334 @@ impl ModuleExpander {
             return Ok(contents);
         }
 
-        // "${IMPORT_NAME}" -> "crate"
-        contents = contents.replace(&self.import_name, &format!("crate::{}", self.import_name));
+        // 到達可能な要素の解析
+        let shaker = if self.tree_shaking {
+            let mut shaker =
+                TreeShaker::new(&self.library_path, self.dependancies.as_ref().unwrap())?;
+            shaker.shake(&contents, &self.import_name);
+            Some(shaker)
+        } else {
+            None
+        };
+
+        // 展開するモジュールのソースコード
+        let mut modules = vec![];
+        for dep in self.dependancies.as_ref().unwrap() {
+            let source = match &shaker {
+                Some(shaker) => match shaker.render(dep) {
+                    Some(source) => source,
+                    None => continue,
+                },
+                None => self.read_module(dep)?,
+            };
+            modules.push((dep, source));
+        }
+
+        // ジャッジで使用できない外部クレートを使っていないか
+        self.check_profile(&modules)?;
+
+        // "${IMPORT_NAME}::" -> "crate::${IMPORT_NAME}::"
+        let macros = self.library_macros()?;
+        let qualified = qualify_library_paths(&contents, &self.import_name, &macros)?;
+        self.rewritten_lines = (1..)
+            .zip(contents.lines().zip(qualified.lines()))
+            .filter(|(_, (before, after))| before != after)
+            .map(|(i, _)| i)
+            .collect();
+        contents = qualified;
 
+        let tab = self.tab();
         write!(
             &mut contents,
-            "
-// ==================== {} ====================
-pub mod {} {{
-    #![allow(dead_code)]
+            "{}pub mod {} {{
+{tab}#![allow(dead_code)]
 ",
-            self.library_name, self.import_name
+            self.library_begin(),
+            self.import_name
         )
         .unwrap();
 
         // カテゴリごとに展開
         let mut prev_category = "";
+        // 各モジュールの展開後の大きさ
+        let mut sizes = vec![];
+        // 展開後の行番号 -> 元のファイルの行番号
+        let mut line_map = BTreeMap::new();
 
-        for dep in self.dependancies.as_ref().unwrap() {
+        for (dep, source) in &modules {
             prev_category = match dep {
                 ModulePath::Macro { .. } => {
                     if !prev_category.is_empty() {
-                        writeln!(&mut contents, "{TAB}}}").unwrap();
+                        writeln!(&mut contents, "{tab}}}").unwrap();
                     }
-                    contents += &self.get_module(dep, 1)?;
+                    let module = self.wrap_module(dep, source, 1, &macros)?;
+                    sizes.push((*dep, module.len()));
+                    line_map.extend(self.map_module_lines(&contents, dep, &module, &macros)?);
+                    contents += &module;
                     ""
                 }
                 ModulePath::Module { category, .. } => {
-                    if category == prev_category {
-                        contents += &self.get_module(dep, 2)?;
-                    } else {
+                    if category != prev_category {
                         if !prev_category.is_empty() {
-                            writeln!(&mut contents, "{TAB}}}").unwrap();
+                            writeln!(&mut contents, "{tab}}}").unwrap();
                         }
-                        writeln!(&mut contents, "{TAB}pub mod {category} {{").unwrap();
-                        contents += &self.get_module(dep, 2)?;
+                        writeln!(&mut contents, "{tab}pub mod {category} {{").unwrap();
                     }
+                    let module = self.wrap_module(dep, source, 2, &macros)?;
+                    sizes.push((*dep, module.len()));
+                    line_map.extend(self.map_module_lines(&contents, dep, &module, &macros)?);
+                    contents += &module;
                     category
                 }
             };
         }
 
         if !prev_category.is_empty() {
-            writeln!(&mut contents, "{TAB}}}").unwrap();
+            writeln!(&mut contents, "{tab}}}").unwrap();
         }
         contents += "}";
 
+        self.check_size(&contents, sizes)?;
+        self.line_map = line_map;
+
         Ok(contents)
     }
 
+    /// 展開結果の大きさが上限を超えていないか確認する
+    fn check_size(
+        &self,
+        contents: &str,
+        mut sizes: Vec<(&ModulePath, usize)>,
+    ) -> anyhow::Result<(), anyhow::Error> {
+        let Some(max_bytes) = self.max_bytes else {
+            return Ok(());
+        };
+        if contents.len() <= max_bytes {
+            return Ok(());
+        }
+
+        sizes.sort_by_key(|&(dep, size)| (std::cmp::Reverse(size), dep));
+        let largest = sizes
+            .iter()
+            .take(10)
+            .map(|(dep, size)| format!("  {}: {size} bytes", dep.qualified_name()))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        bail!(
+            "expanded output is {} bytes, which exceeds the limit of {max_bytes} bytes\nlargest modules:\n{largest}",
+            contents.len()
+        );
+    }
+
+    /// 展開するモジュールが，プロファイルで許可されていない外部クレートを使っていればエラーにする
+    fn check_profile(
+        &self,
+        modules: &[(&ModulePath, String)],
+    ) -> anyhow::Result<(), anyhow::Error> {
+        let Some(profile) = &self.profile else {
+            return Ok(());
+        };
+
+        let disallowed: Vec<String> = external_crates(self.library_root())?
+            .into_iter()
+            .filter(|krate| !profile.allows(krate))
+            .collect();
+
+        let mut violations = vec![];
+        for (dep, source) in modules {
+            for krate in &disallowed {
+                for path in get_paths(source, krate) {
+                    violations.push(format!(
+                        "  {}: use of `{krate}::{}`",
+                        dep.qualified_name(),
+                        path.join("::")
+                    ));
+                }
+            }
+        }
+
+        if violations.is_empty() {
+            return Ok(());
+        }
+
+        bail!(
+            "crates unavailable on {} are used:\n{}",
+            profile.name,
+            violations.join("\n")
+        );
+    }
+
     /// ファイルをモジュールに出力
     pub fn get_module(
         &self,
         dep: &ModulePath,
         indent: usize,
     ) -> anyhow::Result<String, anyhow::Error> {
+        let file = self.read_module(dep)?;
+        let macros = self.library_macros()?;
+
+        self.wrap_module(dep, &file, indent, &macros)
+    }
+
+    /// ライブラリのファイルを読み込む
+    fn read_module(&self, dep: &ModulePath) -> anyhow::Result<String, anyhow::Error> {
         let p = dep.to_pathbuf(self.library_path.to_path_buf());
-        let mut file = match fs::read_to_string(&p) {
-            Ok(contents) => contents,
+        match fs::read_to_string(&p) {
+            Ok(contents) => Ok(contents),
             Err(err) => bail!("{}: {:?}", err, p),
+        }
+    }
+
+    /// ライブラリ直下で定義されているマクロの一覧
+    fn library_macros(&self) -> anyhow::Result<BTreeSet<String>, anyhow::Error> {
+        let entries = match fs::read_dir(&self.library_path) {
+            Ok(entries) => entries,
+            Err(err) => bail!("{}: {:?}", err, self.library_path),
+        };
+
+        let mut macros = BTreeSet::new();
+        for entry in entries {
+            let path = entry?.path();
+            if !path.is_file() || path.extension().is_none_or(|ext| ext != "rs") {
+                continue;
+            }
+            if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
+                if name != "lib" {
+                    macros.insert(name.to_string());
+                }
+            }
+        }
+
+        Ok(macros)
+    }
+
+    /// インデントに使う文字列
+    fn tab(&self) -> &'static str {
+        if self.minify {
+            ""
+        } else {
+            TAB
+        }
+    }
+
+    /// 展開したライブラリの開始位置を表す文字列
+    fn library_begin(&self) -> String {
+        format!(
+            "\n// ==================== {} ====================\n",
+            self.library_name
+        )
+    }
+
+    /// 展開後のモジュールの各行が，元のファイルの何行目に対応するかを求める
+    ///
+    /// 行の内容を前から順に照合するため，tree shaking や縮小を行っても対応が取れる
+    ///
+    /// 列は，内容が一致した行のうちパスを書き換えていない行についてだけ，インデントの差から対応付ける
+    fn map_module_lines(
+        &self,
+        contents: &str,
+        dep: &ModulePath,
+        module: &str,
+        macros: &BTreeSet<String>,
+    ) -> anyhow::Result<Vec<(usize, Origin)>, anyhow::Error> {
+        let raw = self.read_module(dep)?;
+        let prefixed = prefix_crate_paths(&raw, &self.import_name, macros)?;
+        let raw: Vec<&str> = raw.lines().collect();
+        let original: Vec<&str> = prefixed.lines().collect();
+
+        // モジュールの先頭の行番号（1始まり）
+        let start = contents.matches('\n').count() + 1;
+
+        let mut res = vec![];
+        let mut next = 0;
+        for (i, line) in module.lines().enumerate() {
+            if let Some(j) = original[next..]
+                .iter()
+                .position(|l| l.trim() == line.trim())
+            {
+                let j = next + j;
+                let shift = (raw[j] == original[j])
+                    .then(|| indent_width(line) as isize - indent_width(raw[j]) as isize);
+                next = j + 1;
+                res.push((start + i, (dep.clone(), next, shift)));
+            } else if next > 0 {
+                res.push((start + i, (dep.clone(), next, None)));
+            }
+        }
+
+        Ok(res)
+    }
+
+    /// ライブラリのルートディレクトリ（`Cargo.toml` のあるディレクトリ）
+    pub fn library_root(&self) -> &Path {
+        self.library_path.parent().unwrap_or(&self.library_path)
+    }
+
+    /// 展開後のファイルの位置（行，列）を，元のファイルとその位置に変換する
+    ///
+    /// 列を対応付けられない場合（パスを書き換えた行など）は，列を `None` とする
+    pub fn map_location(
+        &self,
+        line: usize,
+        column: usize,
+    ) -> Option<(PathBuf, usize, Option<usize>)> {
+        if line <= self.user_lines {
+            let column = (!self.rewritten_lines.contains(&line)).then_some(column);
+            return Some((self.entry_file.clone(), line, column));
+        }
+        self.line_map.get(&line).map(|(dep, original, shift)| {
+            let column = shift.and_then(|shift| {
+                column
+                    .checked_add_signed(-shift)
+                    .filter(|&column| column > 0)
+            });
+            (
+                dep.to_pathbuf(self.library_path.to_path_buf()),
+                *original,
+                column,
+            )
+        })
+    }
+
+    /// ソースコードをモジュールで包む
+    fn wrap_module(
+        &self,
+        dep: &ModulePath,
+        file: &str,
+        indent: usize,
+        macros: &BTreeSet<String>,
+    ) -> anyhow::Result<String, anyhow::Error> {
+        // ドキュメントコメント・テスト・空行・インデントを削除
+        let file = if self.minify {
+            minify(file)?
+        } else {
+            file.to_string()
         };
 
-        // "crate" -> "crate::${IMPORT_NAME}"
-        file = file.replace("crate", &format!("crate::{}", self.import_name));
+        // "crate::" -> "crate::${IMPORT_NAME}::"
+        let file = prefix_crate_paths(&file, &self.import_name, macros)?;
 
-        let mut res = format!("{}pub mod {dep} {{\n", TAB.repeat(indent));
+        let tab = self.tab();
+        let mut res = format!("{}pub mod {dep} {{\n", tab.repeat(indent));
 
         // 各行を追加
         for line in file.lines().filter(|l| !l.is_empty()) {
-            res += &TAB.repeat(indent + 1);
+            res += &tab.repeat(indent + 1);
             res += line;
             res += "\n";
         }
 
-        writeln!(&mut res, "{}}}", TAB.repeat(indent)).unwrap();
+        writeln!(&mut res, "{}}}", tab.repeat(indent)).unwrap();
 
         Ok(res)
     }
 
     /// 依存関係をファイルに展開する
+    ///
+    /// すでに展開済みの場合は，ライブラリ部分を削除してから依存関係を解析し直す
     pub fn expand_inplace(&mut self) -> anyhow::Result<(), anyhow::Error> {
+        self.dependancies = None;
         let contents = self.expand()?;
 
         // ファイルに書き込み
@@ -209,25 +611,40 @@ pub mod {} {{
         Ok(())
     }
 
+    /// 呼び出し元のファイルを読み込む
+    ///
+    /// すでに展開済みの場合は，ライブラリ部分を削除して元のソースコードに戻す
+    fn read_entry(&self) -> anyhow::Result<String, anyhow::Error> {
+        let contents = match fs::read_to_string(&self.entry_file) {
+            Ok(contents) => contents,
+            Err(err) => bail!("{}: {:?}", err, self.entry_file),
+        };
+
+        self.restore_source(&contents)
+    }
+
+    /// 展開済みのソースコードから元のソースコードを復元する
+    pub fn restore_source(&self, source: &str) -> anyhow::Result<String, anyhow::Error> {
+        // library以降を削除
+        let Some(pos) = source.find(&self.library_begin()) else {
+            log::debug!("expanded library not found");
+            return Ok(source.to_string());
+        };
+
+        // "crate::${IMPORT_NAME}::" -> "${IMPORT_NAME}::"
+        let macros = self.library_macros()?;
+        unqualify_library_paths(&source[..pos], &self.import_name, &macros)
+    }
+
     /// 復元する
     pub fn restore(&self) -> anyhow::Result<(), anyhow::Error> {
         // 現在のファイルの内容
-        let mut file = match fs::read_to_string(&self.entry_file) {
+        let file = match fs::read_to_string(&self.entry_file) {
             Ok(contents) => contents,
             Err(err) => bail!("{}: {:?}", err, self.entry_file),
         };
 
-        // "crate::${IMPORT_NAME}" -> "${IMPORT_NAME}"
-        file = file.replace(&format!("crate::{}", self.import_name), &self.import_name);
-
-        // ライブラリの位置
-        let library_begin = format!(
-            "// ==================== {} ====================",
-            self.library_name
-        );
-
-        // library以降を削除
-        file = file.lines().take_while(|&l| l != library_begin).join("\n");
+        let file = self.restore_source(&file)?;
 
         // 元のファイルに書き出し
         match fs::write(&self.entry_file, file) {
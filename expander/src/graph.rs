@@ -0,0 +1,107 @@
+//! モジュールの依存関係グラフ
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+};
+
+use serde_json::json;
+
+use crate::module_path::ModulePath;
+
+/// 依存元（`None` は入力ファイル）
+pub type Source = Option<ModulePath>;
+
+/// `solve_dependancies` で見つけたモジュール間の依存関係
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DependencyGraph {
+    /// (依存元, 依存先) -> 依存のもとになったパス
+    pub edges: BTreeMap<(Source, ModulePath), BTreeSet<String>>,
+    /// 各モジュールを最初に取り込んだ依存元とそのパス
+    pub parents: BTreeMap<ModulePath, (Source, String)>,
+}
+
+impl DependencyGraph {
+    /// 辺を追加する．`dep` を初めて取り込んだ場合は `true` を返す
+    pub fn add_edge(&mut self, from: &Source, dep: &ModulePath, path: String) -> bool {
+        self.edges
+            .entry((from.clone(), dep.clone()))
+            .or_default()
+            .insert(path.clone());
+
+        if self.parents.contains_key(dep) {
+            return false;
+        }
+        self.parents.insert(dep.clone(), (from.clone(), path));
+        true
+    }
+
+    /// 依存しているモジュールの一覧（各モジュールを取り込んだ依存元とパスを添える）
+    pub fn to_list(&self, entry_name: &str) -> String {
+        let mut res = String::new();
+        for (dep, (from, path)) in &self.parents {
+            writeln!(
+                &mut res,
+                "{} <- {} ({path})",
+                dep.qualified_name(),
+                node_name(from, entry_name)
+            )
+            .unwrap();
+        }
+        res
+    }
+
+    /// Graphviz の dot 形式
+    pub fn to_dot(&self, entry_name: &str) -> String {
+        let mut res = "digraph dependencies {\n".to_string();
+        writeln!(&mut res, "    {:?} [shape=box];", entry_name).unwrap();
+        for dep in self.parents.keys() {
+            writeln!(&mut res, "    {:?};", dep.qualified_name()).unwrap();
+        }
+        for (from, to) in self.edges.keys() {
+            writeln!(
+                &mut res,
+                "    {:?} -> {:?};",
+                node_name(from, entry_name),
+                to.qualified_name()
+            )
+            .unwrap();
+        }
+        res += "}\n";
+        res
+    }
+
+    /// JSON 形式
+    pub fn to_json(&self, entry_name: &str) -> String {
+        let nodes: Vec<String> = self
+            .parents
+            .keys()
+            .map(|dep| dep.qualified_name())
+            .collect();
+        let edges: Vec<_> = self
+            .edges
+            .iter()
+            .map(|((from, to), paths)| {
+                json!({
+                    "from": node_name(from, entry_name),
+                    "to": to.qualified_name(),
+                    "paths": paths,
+                })
+            })
+            .collect();
+
+        let graph = json!({
+            "entry": entry_name,
+            "nodes": nodes,
+            "edges": edges,
+        });
+        serde_json::to_string_pretty(&graph).unwrap()
+    }
+}
+
+fn node_name(node: &Source, entry_name: &str) -> String {
+    match node {
+        Some(dep) => dep.qualified_name(),
+        None => entry_name.to_string(),
+    }
+}
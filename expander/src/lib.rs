@@ -1,4 +1,12 @@
 pub mod cargo_lock;
+pub mod check;
 pub mod expander;
+pub mod graph;
+pub mod manifest;
+pub mod minify;
 pub mod module_path;
 pub mod parser;
+pub mod profile;
+pub mod rewrite;
+pub mod shake;
+pub mod span;
@@ -1,12 +1,35 @@
-use clap::Parser;
-use expander::{cargo_lock::resolve_git_library_path, expander::ModuleExpander};
-use std::{path::PathBuf, process::exit};
+use clap::{Parser, ValueEnum};
+use expander::{
+    cargo_lock::resolve_library_path,
+    check::check,
+    expander::ModuleExpander,
+    manifest::{bin_targets, find_bin, find_manifest},
+    profile::Profile,
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::exit,
+};
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 struct Args {
     /// path or alias of input file
-    input: String,
+    #[arg(required_unless_present_any = ["bin", "all_bins"], conflicts_with = "bin")]
+    input: Option<String>,
+    /// name or cargo-compete alias of the bin target to expand
+    #[arg(long)]
+    bin: Option<String>,
+    /// path to Cargo.toml used to find bin targets (defaults to the nearest one)
+    #[arg(long)]
+    manifest_path: Option<PathBuf>,
+    /// expand every bin target in the package into --out-dir
+    #[arg(long, requires = "out_dir", conflicts_with_all = ["input", "bin"])]
+    all_bins: bool,
+    /// output directory for --all-bins
+    #[arg(short, long)]
+    out_dir: Option<PathBuf>,
     /// library path (exclusive with --crate-name)
     #[arg(short, long)]
     library: Option<PathBuf>,
@@ -18,6 +41,34 @@ struct Args {
     /// restore file from backup
     #[arg(short, long)]
     restore: bool,
+    /// expand only items reachable from the input file
+    #[arg(long)]
+    tree_shake: bool,
+    /// strip doc comments, tests, blank lines and indentation from the library section
+    #[arg(long)]
+    minify: bool,
+    /// fail if the expanded output exceeds this size in bytes (0 to disable)
+    #[arg(long, default_value_t = 512 * 1024)]
+    max_bytes: usize,
+    /// compile the expanded output and report errors at their original location
+    #[arg(long)]
+    check: bool,
+    /// judge profile (atcoder, codeforces, yosupo or a path to expander.toml);
+    /// defaults to expander.toml next to Cargo.lock
+    #[arg(long)]
+    profile: Option<String>,
+    /// print the resolved modules with the path that pulled each one in
+    #[arg(long)]
+    deps: bool,
+    /// print the module dependency graph
+    #[arg(long, value_enum)]
+    graph: Option<GraphFormat>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum GraphFormat {
+    Dot,
+    Json,
 }
 
 fn main() {
@@ -28,7 +79,26 @@ fn main() {
     let args = Args::parse();
 
     // 入力ファイルのパスを取得
-    let input_path = PathBuf::from(&args.input);
+    let manifest_path = || {
+        args.manifest_path
+            .clone()
+            .map_or_else(find_manifest, Ok)
+            .unwrap_or_else(|err| {
+                log::error!("{err}");
+                exit(1);
+            })
+    };
+    let input_path = match (&args.input, &args.bin) {
+        (Some(input), _) => PathBuf::from(input),
+        (None, Some(bin)) => match find_bin(&manifest_path(), bin) {
+            Ok(bin) => bin.path,
+            Err(err) => {
+                log::error!("{err}");
+                exit(1);
+            }
+        },
+        (None, None) => manifest_path(),
+    };
     log::info!("input_path: {:?}", input_path);
 
     let library_path = match (&args.library, &args.crate_name) {
@@ -44,28 +114,25 @@ fn main() {
             log::info!("library_path: {:?}", library);
             library.clone()
         }
-        (None, Some(crate_name)) => match resolve_git_library_path(&input_path, crate_name) {
+        (None, Some(crate_name)) => match resolve_library_path(&input_path, crate_name) {
             Ok(path) => {
                 log::info!("library_path: {:?}", path);
                 path
             }
             Err(err) => {
-                log::error!("failed to resolve git library path");
+                log::error!("failed to resolve library path");
                 log::error!("{err}");
                 exit(1);
             }
         },
     };
 
-    // expanderの初期化
-    let mut expander = match ModuleExpander::new(input_path.clone(), library_path) {
-        Ok(expander) => expander,
-        Err(err) => {
-            log::error!("expander initialize failed");
-            log::error!("{err}");
-            exit(1);
-        }
-    };
+    if args.all_bins {
+        expand_all_bins(&args, &input_path, library_path);
+        return;
+    }
+
+    let mut expander = new_expander(&args, input_path.clone(), library_path);
 
     log::info!("library_name: {}", expander.library_name);
     log::info!("import_name: {}", expander.import_name);
@@ -79,6 +146,39 @@ fn main() {
                 exit(1);
             }
         }
+    } else if args.deps || args.graph.is_some() {
+        if let Err(err) = expander.solve_dependancies() {
+            log::error!("failed to solve dependancies");
+            log::error!("{err}");
+            exit(1);
+        }
+        let entry_name = input_path.file_name().map_or_else(
+            || input_path.to_string_lossy().to_string(),
+            |s| s.to_string_lossy().to_string(),
+        );
+        let output = match args.graph {
+            Some(GraphFormat::Dot) => expander.graph.to_dot(&entry_name),
+            Some(GraphFormat::Json) => expander.graph.to_json(&entry_name),
+            None => expander.graph.to_list(&entry_name),
+        };
+        print!("{output}");
+    } else if args.check {
+        match check(&mut expander) {
+            Ok(diagnostics) => {
+                for diagnostic in &diagnostics {
+                    eprintln!("{diagnostic}");
+                }
+                if diagnostics.iter().any(|d| d.is_error()) {
+                    exit(1);
+                }
+                log::info!("check complete");
+            }
+            Err(err) => {
+                log::error!("check failed");
+                log::error!("{err}");
+                exit(1);
+            }
+        }
     } else if args.inplace {
         match expander.expand_inplace() {
             Ok(_) => {
@@ -105,3 +205,69 @@ fn main() {
         }
     }
 }
+
+/// 設定を反映した expander を作る
+fn new_expander(args: &Args, input_path: PathBuf, library_path: PathBuf) -> ModuleExpander {
+    let mut expander = match ModuleExpander::new(input_path.clone(), library_path) {
+        Ok(expander) => expander,
+        Err(err) => {
+            log::error!("expander initialize failed");
+            log::error!("{err}");
+            exit(1);
+        }
+    };
+
+    expander.tree_shaking = args.tree_shake;
+    expander.minify = args.minify;
+    expander.max_bytes = (args.max_bytes > 0).then_some(args.max_bytes);
+    expander.profile = match &args.profile {
+        Some(profile) => Profile::load(profile).map(Some),
+        None => Profile::find(&input_path),
+    }
+    .unwrap_or_else(|err| {
+        log::error!("failed to load profile");
+        log::error!("{err}");
+        exit(1);
+    });
+    if let Some(profile) = &expander.profile {
+        log::info!("profile: {}", profile.name);
+    }
+
+    expander
+}
+
+/// パッケージのすべての bin を展開し，`--out-dir` に `<name>.rs` として書き出す
+fn expand_all_bins(args: &Args, manifest_path: &Path, library_path: PathBuf) {
+    let out_dir = args.out_dir.as_ref().unwrap();
+    if let Err(err) = fs::create_dir_all(out_dir) {
+        log::error!("{err}: {:?}", out_dir);
+        exit(1);
+    }
+
+    let bins = bin_targets(manifest_path).unwrap_or_else(|err| {
+        log::error!("{err}");
+        exit(1);
+    });
+
+    let mut failed = vec![];
+    for bin in bins {
+        let mut expander = new_expander(args, bin.path.clone(), library_path.clone());
+        let output = out_dir.join(format!("{}.rs", bin.name));
+        let result = expander
+            .expand()
+            .and_then(|contents| Ok(fs::write(&output, contents)?));
+        match result {
+            Ok(()) => log::info!("{}: {:?}", bin.name, output),
+            Err(err) => {
+                log::error!("{}: expand failed", bin.name);
+                log::error!("{err}");
+                failed.push(bin.name);
+            }
+        }
+    }
+
+    if !failed.is_empty() {
+        log::error!("failed to expand: {}", failed.join(", "));
+        exit(1);
+    }
+}
@@ -0,0 +1,240 @@
+//! `Cargo.toml` から bin ターゲットのソースファイルを探す
+//!
+//! cargo-compete のワークスペースでは，bin の名前（`[package.metadata.cargo-compete.bin]` のキーや
+//! `alias`）でファイルを指定できる
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+
+use crate::cargo_lock::parse_string_value;
+
+/// bin ターゲット
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BinTarget {
+    /// bin の名前
+    pub name: String,
+    /// cargo-compete の別名
+    pub alias: Option<String>,
+    /// ソースファイルのパス
+    pub path: PathBuf,
+}
+
+impl BinTarget {
+    /// 名前または別名が一致するか
+    pub fn matches(&self, name: &str) -> bool {
+        self.name == name || self.alias.as_deref() == Some(name)
+    }
+}
+
+/// カレントディレクトリから上の階層にある `Cargo.toml` を探す
+pub fn find_manifest() -> Result<PathBuf> {
+    let mut dir = env::current_dir().context("failed to get current directory")?;
+    loop {
+        let candidate = dir.join("Cargo.toml");
+        if candidate.exists() {
+            return Ok(candidate);
+        }
+        if !dir.pop() {
+            break;
+        }
+    }
+    bail!("Cargo.toml not found; specify --manifest-path");
+}
+
+/// 名前（または cargo-compete の別名）から bin ターゲットを探す
+pub fn find_bin(manifest: &Path, name: &str) -> Result<BinTarget> {
+    let bins = bin_targets(manifest)?;
+    if let Some(bin) = bins.iter().find(|bin| bin.matches(name)) {
+        return Ok(bin.clone());
+    }
+
+    let names = bins
+        .iter()
+        .map(|bin| bin.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    bail!(
+        "bin not found in {:?}: {name} (available: {names})",
+        manifest
+    );
+}
+
+/// パッケージの bin ターゲットを列挙する（名前順）
+///
+/// - `[[bin]]` に書かれたもの（`path` がなければ `src/bin/<name>.rs`）
+/// - `[package.metadata.cargo-compete.bin]` に書かれたもの
+/// - `src/bin/<name>.rs`，`src/bin/<name>/main.rs`
+pub fn bin_targets(manifest: &Path) -> Result<Vec<BinTarget>> {
+    let contents =
+        fs::read_to_string(manifest).with_context(|| format!("failed to read {:?}", manifest))?;
+    let root = manifest
+        .parent()
+        .context("manifest has no parent directory")?;
+
+    let mut bins: Vec<BinTarget> = vec![];
+
+    // `[[bin]]`
+    for (name, path) in parse_bin_tables(&contents) {
+        let path = match path {
+            Some(path) => root.join(path),
+            None => default_bin_path(root, &name),
+        };
+        bins.push(BinTarget {
+            name,
+            alias: None,
+            path,
+        });
+    }
+
+    // `[package.metadata.cargo-compete.bin]`
+    for (name, alias) in parse_compete_bins(&contents) {
+        if let Some(bin) = bins.iter_mut().find(|bin| bin.name == name) {
+            bin.alias = alias;
+            continue;
+        }
+        let mut path = default_bin_path(root, &name);
+        if let Some(alias) = &alias {
+            if !path.exists() {
+                path = default_bin_path(root, alias);
+            }
+        }
+        bins.push(BinTarget { name, alias, path });
+    }
+
+    // `src/bin` 以下のファイル
+    let bin_dir = root.join("src").join("bin");
+    if bin_dir.is_dir() {
+        for entry in
+            fs::read_dir(&bin_dir).with_context(|| format!("failed to read {:?}", bin_dir))?
+        {
+            let path = entry?.path();
+            let name = if path.is_dir() && path.join("main.rs").exists() {
+                path.file_name()
+            } else if path.extension().is_some_and(|ext| ext == "rs") {
+                path.file_stem()
+            } else {
+                continue;
+            };
+            let name = name.unwrap().to_string_lossy().to_string();
+            if bins.iter().any(|bin| bin.matches(&name)) {
+                continue;
+            }
+            bins.push(BinTarget {
+                path: default_bin_path(root, &name),
+                name,
+                alias: None,
+            });
+        }
+    }
+
+    bins.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(bins)
+}
+
+/// `src/bin/<name>.rs`（なければ `src/bin/<name>/main.rs`）
+fn default_bin_path(root: &Path, name: &str) -> PathBuf {
+    let bin_dir = root.join("src").join("bin");
+    let file = bin_dir.join(format!("{name}.rs"));
+    let dir = bin_dir.join(name).join("main.rs");
+    if !file.exists() && dir.exists() {
+        dir
+    } else {
+        file
+    }
+}
+
+/// `[[bin]]` の `name` と `path`
+fn parse_bin_tables(contents: &str) -> Vec<(String, Option<String>)> {
+    let mut bins = vec![];
+    let mut current: Option<(Option<String>, Option<String>)> = None;
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            if let Some((Some(name), path)) = current.take() {
+                bins.push((name, path));
+            }
+            if line == "[[bin]]" {
+                current = Some((None, None));
+            }
+            continue;
+        }
+        let Some((name, path)) = current.as_mut() else {
+            continue;
+        };
+        if let Some(value) = parse_string_value(line, "name") {
+            *name = Some(value);
+        } else if let Some(value) = parse_string_value(line, "path") {
+            *path = Some(value);
+        }
+    }
+
+    if let Some((Some(name), path)) = current {
+        bins.push((name, path));
+    }
+
+    bins
+}
+
+/// `[package.metadata.cargo-compete.bin]` のキーと `alias`
+///
+/// ```toml
+/// [package.metadata.cargo-compete.bin]
+/// aplusb = { problem = "https://judge.yosupo.jp/problem/aplusb" }
+/// abc001-a = { alias = "a", problem = "https://atcoder.jp/contests/abc001/tasks/abc001_1" }
+///
+/// [package.metadata.cargo-compete.bin.abc001-b]
+/// alias = "b"
+/// ```
+fn parse_compete_bins(contents: &str) -> Vec<(String, Option<String>)> {
+    const SECTION: &str = "package.metadata.cargo-compete.bin";
+
+    let mut bins: Vec<(String, Option<String>)> = vec![];
+    let mut in_section = false;
+    // `[package.metadata.cargo-compete.bin.<name>]` の中か
+    let mut table: Option<usize> = None;
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            let section = line.trim_matches(|c| c == '[' || c == ']');
+            in_section = section == SECTION;
+            table = section
+                .strip_prefix(SECTION)
+                .and_then(|rest| rest.strip_prefix('.'))
+                .map(|name| {
+                    bins.push((name.trim_matches('"').to_string(), None));
+                    bins.len() - 1
+                });
+            continue;
+        }
+        if let Some(i) = table {
+            if let Some(alias) = parse_string_value(line, "alias") {
+                bins[i].1 = Some(alias);
+            }
+            continue;
+        }
+        if !in_section || line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((name, value)) = line.split_once('=') else {
+            continue;
+        };
+        let alias = value
+            .trim()
+            .strip_prefix('{')
+            .and_then(|v| v.strip_suffix('}'))
+            .and_then(|table| {
+                table
+                    .split(',')
+                    .find_map(|field| parse_string_value(field.trim(), "alias"))
+            });
+        bins.push((name.trim().trim_matches('"').to_string(), alias));
+    }
+
+    bins
+}
@@ -0,0 +1,109 @@
+//! 展開するライブラリのソースコードを縮小する
+//!
+//! - ドキュメントコメントを削除する
+//! - `#[cfg(test)]` が付いた要素（`mod tests` など）を削除する
+//! - 空行を削除し，インデントを詰める（複数行にわたる文字列リテラルの中は変更しない）
+
+use std::{collections::BTreeSet, ops::Range, str::FromStr};
+
+use anyhow::bail;
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+
+use crate::{
+    shake::{is_cfg_test, item_attrs},
+    span::LineIndex,
+};
+
+/// ソースコードを縮小する
+pub fn minify(source: &str) -> anyhow::Result<String> {
+    let index = LineIndex::new(source);
+    let mut removed: Vec<Range<usize>> = vec![];
+
+    // テスト用の要素
+    let syntax_tree = syn::parse_file(source)?;
+    for item in &syntax_tree.items {
+        if is_cfg_test(item_attrs(item)) {
+            removed.extend(index.node_range(item));
+        }
+    }
+
+    // ドキュメントコメント
+    collect_doc_comments(tokenize(source)?, source, &index, &mut removed);
+
+    removed.sort_by_key(|r| (r.start, r.end));
+    let mut stripped = String::with_capacity(source.len());
+    let mut last = 0;
+    for range in removed {
+        if range.start < last {
+            continue;
+        }
+        stripped += &source[last..range.start];
+        last = range.end;
+    }
+    stripped += &source[last..];
+
+    // 複数行にわたるリテラルの2行目以降（1始まり）
+    let mut in_literal = BTreeSet::new();
+    collect_multiline_literals(tokenize(&stripped)?, &mut in_literal);
+
+    let mut res = String::with_capacity(stripped.len());
+    for (i, line) in stripped.lines().enumerate() {
+        if in_literal.contains(&(i + 1)) {
+            res += line;
+        } else if line.trim().is_empty() {
+            continue;
+        } else {
+            res += line.trim();
+        }
+        res += "\n";
+    }
+
+    Ok(res)
+}
+
+fn tokenize(source: &str) -> anyhow::Result<TokenStream> {
+    match TokenStream::from_str(source) {
+        Ok(tokens) => Ok(tokens),
+        Err(err) => bail!("failed to tokenize: {err}"),
+    }
+}
+
+/// `///`，`//!`，`/** */`，`/*! */` の形のドキュメントコメントの範囲を集める
+fn collect_doc_comments(
+    stream: TokenStream,
+    source: &str,
+    index: &LineIndex,
+    out: &mut Vec<Range<usize>>,
+) {
+    let tokens: Vec<TokenTree> = stream.into_iter().collect();
+    for (i, tt) in tokens.iter().enumerate() {
+        match tt {
+            TokenTree::Punct(p) if p.as_char() == '#' => {
+                let range = index.range(p.span());
+                let text = &source[range.clone()];
+                let is_comment = text.starts_with("//") || text.starts_with("/*");
+                let is_doc = tokens[i + 1..].iter().take(2).any(
+                    |tt| matches!(tt, TokenTree::Group(g) if g.delimiter() == Delimiter::Bracket),
+                );
+                if is_comment && is_doc {
+                    out.push(range);
+                }
+            }
+            TokenTree::Group(group) => collect_doc_comments(group.stream(), source, index, out),
+            _ => (),
+        }
+    }
+}
+
+fn collect_multiline_literals(stream: TokenStream, out: &mut BTreeSet<usize>) {
+    for tt in stream {
+        match tt {
+            TokenTree::Literal(lit) => {
+                let (start, end) = (lit.span().start(), lit.span().end());
+                out.extend(start.line + 1..=end.line);
+            }
+            TokenTree::Group(group) => collect_multiline_literals(group.stream(), out),
+            _ => (),
+        }
+    }
+}
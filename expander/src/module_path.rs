@@ -25,6 +25,14 @@ impl ModulePath {
         // ".rs"拡張子をつける
         lib_path.with_extension("rs")
     }
+
+    /// `category::file` 形式のモジュール名
+    pub fn qualified_name(&self) -> String {
+        match self {
+            ModulePath::Macro { file } => file.clone(),
+            ModulePath::Module { category, file } => format!("{category}::{file}"),
+        }
+    }
 }
 
 impl Display for ModulePath {
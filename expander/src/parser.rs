@@ -1,8 +1,9 @@
 use std::collections::BTreeSet;
 
+use proc_macro2::TokenTree;
 use syn::{visit::Visit, UseTree};
 
-use crate::module_path::ModulePath;
+use crate::{module_path::ModulePath, span::flatten_tokens};
 
 /// use文の解析器
 pub struct UseVisitor {
@@ -46,32 +47,125 @@ fn deps_to_vec(tree: &UseTree, paths: &mut Vec<Vec<String>>, mut current_path: V
     }
 }
 
-/// ファイルの文字列から，クレート内での依存関係を抜き出す
-pub fn get_deps(source: &str, target: &str) -> BTreeSet<ModulePath> {
+/// use文・パス・マクロ呼び出しの解析器
+///
+/// - `super` から始まるパスは，ファイル自身のモジュールからの相対パスに直す
+/// - ファイル内のモジュール（`mod tests { .. }` など）からファイル内を指す `super` のパスは含めない
+pub struct PathVisitor {
+    pub paths: Vec<Vec<String>>,
+    /// ファイル内でのモジュールの深さ
+    depth: usize,
+}
+
+impl PathVisitor {
+    fn push(&mut self, path: Vec<String>) {
+        let supers = path.iter().take_while(|seg| *seg == "super").count();
+        if supers == 0 {
+            self.paths.push(path);
+        } else if supers > self.depth {
+            self.paths.push(path[self.depth..].to_vec());
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for PathVisitor {
+    fn visit_item_use(&mut self, item_use: &'ast syn::ItemUse) {
+        let mut paths = vec![];
+        deps_to_vec(&item_use.tree, &mut paths, Vec::new());
+        for path in paths {
+            self.push(path);
+        }
+        syn::visit::visit_item_use(self, item_use);
+    }
+
+    fn visit_item_mod(&mut self, item_mod: &'ast syn::ItemMod) {
+        self.depth += 1;
+        syn::visit::visit_item_mod(self, item_mod);
+        self.depth -= 1;
+    }
+
+    fn visit_path(&mut self, path: &'ast syn::Path) {
+        self.push(
+            path.segments
+                .iter()
+                .map(|seg| seg.ident.to_string())
+                .collect(),
+        );
+        syn::visit::visit_path(self, path);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        // マクロの引数は構文木にならないため，トークン列からパスを探す
+        let mut tokens = vec![];
+        flatten_tokens(mac.tokens.clone(), &mut tokens);
+
+        let mut i = 0;
+        while i < tokens.len() {
+            let TokenTree::Ident(ident) = &tokens[i] else {
+                i += 1;
+                continue;
+            };
+            let mut path = vec![ident.to_string()];
+            i += 1;
+            while let [TokenTree::Punct(p1), TokenTree::Punct(p2), TokenTree::Ident(ident), ..] =
+                &tokens[i..]
+            {
+                if p1.as_char() != ':' || p2.as_char() != ':' {
+                    break;
+                }
+                path.push(ident.to_string());
+                i += 3;
+            }
+            if path.len() >= 2 {
+                self.push(path);
+            }
+        }
+
+        syn::visit::visit_macro(self, mac);
+    }
+}
+
+/// ファイルの文字列から，`target` から始まるパスを（`target` を除いて）抜き出す
+///
+/// - use文（グロブを含む）
+/// - 式・型の中のパス（`target::graph::dijkstra::dijkstra(..)` など）
+/// - マクロ呼び出し（`target::get!(..)`）とその引数の中のパス
+/// - `target` が `super` のときは，ファイル自身のモジュールの親からの相対パス
+pub fn get_paths(source: &str, target: &str) -> BTreeSet<Vec<String>> {
     // 構文木を構築
     let syntax_tree = syn::parse_file(source).expect("Unable to parse file");
     // 解析
-    let mut visitor = UseVisitor { uses: vec![] };
+    let mut visitor = PathVisitor {
+        paths: vec![],
+        depth: 0,
+    };
     visitor.visit_file(&syntax_tree);
 
-    // 内部の依存関係を調べる
+    visitor
+        .paths
+        .into_iter()
+        .filter(|p| p.len() >= 2 && p[0] == target)
+        .map(|p| p[1..].to_vec())
+        .collect()
+}
+
+/// ファイルの文字列から，クレート内での依存関係を抜き出す
+///
+/// グロブ（`target::category::*`）の解決にはライブラリの構成が必要なため，ここでは無視する
+pub fn get_deps(source: &str, target: &str) -> BTreeSet<ModulePath> {
     let mut local_deps = BTreeSet::new();
 
-    for p in &visitor.uses {
-        // if let [dep, file, ..] = &p[..] {
-        //     if dep == target {
-        //         local_deps.insert(file.clone());
-        //     }
-        // }
+    for p in get_paths(source, target) {
         match &p[..] {
-            [crate_name, category, file, ..] if crate_name == target => {
+            [_, file, ..] if file == "*" => (),
+            [category, file, ..] => {
                 let dep = ModulePath::Module {
                     category: category.clone(),
                     file: file.clone(),
                 };
                 local_deps.insert(dep);
             }
-            [crate_name, file] if crate_name == target => {
+            [file] => {
                 let dep = ModulePath::Macro { file: file.clone() };
                 local_deps.insert(dep);
             }
@@ -0,0 +1,279 @@
+//! ジャッジごとに使用できる外部クレートと rustc のバージョン
+
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+
+use crate::cargo_lock::find_cargo_lock;
+
+/// 設定ファイルの名前（`Cargo.lock` と同じディレクトリに置く）
+pub const CONFIG_FILE: &str = "expander.toml";
+
+/// AtCoder（2023年の言語アップデート）で使用できるクレート
+const ATCODER_CRATES: &[&str] = &[
+    "ac_library",
+    "alga",
+    "amplify",
+    "ascii",
+    "bitset_fixed",
+    "bstr",
+    "either",
+    "fixedbitset",
+    "hashbag",
+    "im_rc",
+    "indexing",
+    "itertools",
+    "itertools_num",
+    "lazy_static",
+    "maplit",
+    "memoise",
+    "nalgebra",
+    "ndarray",
+    "num",
+    "num_bigint",
+    "num_complex",
+    "num_derive",
+    "num_integer",
+    "num_iter",
+    "num_rational",
+    "num_traits",
+    "omniswap",
+    "once_cell",
+    "ordered_float",
+    "permutohedron",
+    "petgraph",
+    "proconio",
+    "rand",
+    "rand_chacha",
+    "rand_core",
+    "rand_distr",
+    "rand_pcg",
+    "rand_xorshift",
+    "rand_xoshiro",
+    "recur_fn",
+    "regex",
+    "rpds",
+    "rustc_hash",
+    "smallvec",
+    "statrs",
+    "superslice",
+    "text_io",
+];
+
+/// ジャッジの環境
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    /// プロファイルの名前
+    pub name: String,
+    /// ジャッジの rustc のバージョン
+    pub rustc: Option<String>,
+    /// 使用できる外部クレート（`-` は `_` に置き換えた名前）
+    pub crates: BTreeSet<String>,
+}
+
+impl Profile {
+    /// 組み込みのプロファイル（`atcoder`，`codeforces`，`yosupo`）
+    pub fn builtin(name: &str) -> Option<Self> {
+        let (rustc, crates): (Option<&str>, &[&str]) = match name {
+            "atcoder" => (Some("1.70.0"), ATCODER_CRATES),
+            "codeforces" => (Some("1.75.0"), &[]),
+            "yosupo" => (None, &[]),
+            _ => return None,
+        };
+        Some(Profile {
+            name: name.to_string(),
+            rustc: rustc.map(str::to_string),
+            crates: crates.iter().map(|c| c.to_string()).collect(),
+        })
+    }
+
+    /// 組み込みのプロファイル名，または設定ファイルのパスからプロファイルを読み込む
+    pub fn load(name_or_path: &str) -> Result<Self> {
+        if let Some(profile) = Self::builtin(name_or_path) {
+            return Ok(profile);
+        }
+        let path = Path::new(name_or_path);
+        if path.is_file() {
+            return Self::from_file(path);
+        }
+        bail!("unknown profile: {name_or_path} (expected atcoder, codeforces, yosupo or a path to {CONFIG_FILE})");
+    }
+
+    /// 入力ファイルから上の階層にある `Cargo.lock` の隣の設定ファイルを探す
+    pub fn find(entry_file: &Path) -> Result<Option<Self>> {
+        let Some(config) = find_config(entry_file) else {
+            return Ok(None);
+        };
+        Self::from_file(&config).map(Some)
+    }
+
+    /// 設定ファイルを読み込む
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let contents =
+            fs::read_to_string(path).with_context(|| format!("failed to read {:?}", path))?;
+        Self::parse(&contents).with_context(|| format!("failed to parse {:?}", path))
+    }
+
+    /// 設定ファイルの内容を解析する
+    ///
+    /// ```toml
+    /// # 基にする組み込みのプロファイル（省略可）
+    /// profile = "codeforces"
+    /// name = "my-judge"
+    /// rustc = "1.75.0"
+    /// crates = ["num-traits", "itertools"]
+    /// ```
+    pub fn parse(contents: &str) -> Result<Self> {
+        let mut base = None;
+        let mut name = None;
+        let mut rustc = None;
+        let mut crates = None;
+
+        for (key, value) in parse_entries(contents)? {
+            match key.as_str() {
+                "profile" => {
+                    let profile = parse_string(&value)?;
+                    match Self::builtin(&profile) {
+                        Some(profile) => base = Some(profile),
+                        None => bail!("unknown profile: {profile}"),
+                    }
+                }
+                "name" => name = Some(parse_string(&value)?),
+                "rustc" => rustc = Some(parse_string(&value)?),
+                "crates" => crates = Some(parse_array(&value)?),
+                _ => bail!("unknown key: {key}"),
+            }
+        }
+
+        let mut profile = base.unwrap_or(Profile {
+            name: "custom".to_string(),
+            rustc: None,
+            crates: BTreeSet::new(),
+        });
+        if let Some(name) = name {
+            profile.name = name;
+        }
+        if rustc.is_some() {
+            profile.rustc = rustc;
+        }
+        if let Some(crates) = crates {
+            profile.crates = crates.iter().map(|c| c.replace('-', "_")).collect();
+        }
+
+        Ok(profile)
+    }
+
+    /// 外部クレートを使用できるか
+    pub fn allows(&self, crate_name: &str) -> bool {
+        self.crates.contains(&crate_name.replace('-', "_"))
+    }
+}
+
+/// 入力ファイルに対応する設定ファイルのパス
+pub fn find_config(entry_file: &Path) -> Option<PathBuf> {
+    let cargo_lock = find_cargo_lock(entry_file).ok()?;
+    let config = cargo_lock.with_file_name(CONFIG_FILE);
+    config.exists().then_some(config)
+}
+
+/// ライブラリの `Cargo.toml` の `[dependencies]` にある外部クレート（`-` は `_` に置き換えた名前）
+///
+/// ```toml
+/// [dependencies]
+/// num-traits = "0.2"
+/// rand = { version = "0.9", features = ["small_rng"] }
+///
+/// [dependencies.itertools]
+/// version = "0.14"
+/// ```
+pub fn external_crates(library_root: &Path) -> Result<BTreeSet<String>> {
+    let manifest = library_root.join("Cargo.toml");
+    let contents =
+        fs::read_to_string(&manifest).with_context(|| format!("failed to read {:?}", manifest))?;
+
+    let mut crates = BTreeSet::new();
+    let mut in_dependencies = false;
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            let section = line.trim_matches(|c| c == '[' || c == ']').trim();
+            in_dependencies = section == "dependencies";
+            // `[dependencies.<name>]`
+            if let Some(name) = section.strip_prefix("dependencies.") {
+                crates.insert(name.trim().trim_matches('"').replace('-', "_"));
+            }
+            continue;
+        }
+        if !in_dependencies || line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some((key, _)) = line.split_once('=') {
+            // `name.workspace = true` のようなドット区切りのキー
+            let name = key.split('.').next().unwrap();
+            crates.insert(name.trim().trim_matches('"').replace('-', "_"));
+        }
+    }
+
+    Ok(crates)
+}
+
+/// `key = value` の組を読む（配列は複数行にまたがってもよい）
+fn parse_entries(contents: &str) -> Result<Vec<(String, String)>> {
+    let mut entries: Vec<(String, String)> = vec![];
+    let mut pending: Option<(String, String)> = None;
+
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some((key, mut value)) = pending.take() {
+            value += line;
+            if line.ends_with(']') {
+                entries.push((key, value));
+            } else {
+                pending = Some((key, value));
+            }
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            bail!("invalid line: {line}");
+        };
+        let (key, value) = (key.trim().to_string(), value.trim().to_string());
+        if value.starts_with('[') && !value.ends_with(']') {
+            pending = Some((key, value));
+        } else {
+            entries.push((key, value));
+        }
+    }
+
+    if let Some((key, _)) = pending {
+        bail!("unterminated array: {key}");
+    }
+
+    Ok(entries)
+}
+
+fn parse_string(value: &str) -> Result<String> {
+    match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        Some(value) => Ok(value.to_string()),
+        None => bail!("expected a string: {value}"),
+    }
+}
+
+fn parse_array(value: &str) -> Result<Vec<String>> {
+    let Some(inner) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) else {
+        bail!("expected an array: {value}");
+    };
+    inner
+        .split(',')
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(parse_string)
+        .collect()
+}
@@ -0,0 +1,224 @@
+//! トークン列に基づくパスの書き換え
+//!
+//! 文字列置換では識別子の一部（`crate_name` など），コメント，文字列リテラルまで
+//! 書き換えてしまうため，トークン列を解析して実際のパスの先頭だけを書き換える．
+
+use std::{collections::BTreeSet, ops::Range, str::FromStr};
+
+use anyhow::bail;
+use proc_macro2::{Delimiter, Spacing, TokenStream, TokenTree};
+
+use crate::span::LineIndex;
+
+/// 書き換え（範囲と置き換える文字列）
+type Edit = (Range<usize>, String);
+
+/// ライブラリのファイル中の `crate::` を `crate::${IMPORT_NAME}::` に書き換える
+///
+/// - `$crate::` も同様に書き換える
+/// - `crate::${MACRO}` （`#[macro_export]` されたマクロ）はクレート直下にあるため書き換えない
+pub fn prefix_crate_paths(
+    source: &str,
+    import_name: &str,
+    macros: &BTreeSet<String>,
+) -> anyhow::Result<String> {
+    let index = LineIndex::new(source);
+    let mut edits = vec![];
+
+    walk(tokenize(source)?, &mut |tokens, i| {
+        if !is_ident(tokens.get(i), "crate") || !is_path_sep(tokens, i + 1) {
+            return;
+        }
+        if is_macro_path(tokens, i + 3, macros) {
+            return;
+        }
+        let end = index.range(tokens[i].span()).end;
+        edits.push((end..end, format!("::{import_name}")));
+    });
+
+    Ok(apply(source, edits))
+}
+
+/// 呼び出し元のファイル中の `${IMPORT_NAME}::` を `crate::${IMPORT_NAME}::` に書き換える
+///
+/// - `${IMPORT_NAME}::${MACRO}!` はクレート直下のマクロを指すように `crate::${MACRO}!` に書き換える
+/// - `use ${IMPORT_NAME}::${MACRO} as ${NAME};` も同様に `use crate::${MACRO} as ${NAME};` に書き換える
+/// - 別名をつけたマクロを含む `use ${IMPORT_NAME}::{..};` は，`use crate::{..};` としてマクロ以外の要素に `${IMPORT_NAME}::` をつける
+pub fn qualify_library_paths(
+    source: &str,
+    import_name: &str,
+    macros: &BTreeSet<String>,
+) -> anyhow::Result<String> {
+    let index = LineIndex::new(source);
+    let mut edits = vec![];
+
+    walk(tokenize(source)?, &mut |tokens, i| {
+        if !is_ident(tokens.get(i), import_name) || !is_path_sep(tokens, i + 1) {
+            return;
+        }
+        if i >= 2 && is_path_sep(tokens, i - 2) {
+            return;
+        }
+        let range = index.range(tokens[i].span());
+        if let Some((items, starts)) = macro_rename_group(tokens, i + 3, macros) {
+            edits.push((range, "crate".to_string()));
+            for j in starts {
+                if is_macro_item(&items, j, macros) {
+                    continue;
+                }
+                let start = index.range(items[j].span()).start;
+                edits.push((start..start, format!("{import_name}::")));
+            }
+        } else if is_macro_call(tokens, i + 3, macros) || is_macro_rename(tokens, i + 3, macros) {
+            edits.push((range, "crate".to_string()));
+        } else {
+            edits.push((range.start..range.start, "crate::".to_string()));
+        }
+    });
+
+    Ok(apply(source, edits))
+}
+
+/// [`qualify_library_paths`] による書き換えを元に戻す
+pub fn unqualify_library_paths(
+    source: &str,
+    import_name: &str,
+    macros: &BTreeSet<String>,
+) -> anyhow::Result<String> {
+    let index = LineIndex::new(source);
+    let mut edits = vec![];
+
+    walk(tokenize(source)?, &mut |tokens, i| {
+        if !is_ident(tokens.get(i), "crate") || !is_path_sep(tokens, i + 1) {
+            return;
+        }
+        if i >= 1 && matches!(&tokens[i - 1], TokenTree::Punct(p) if p.as_char() == '$') {
+            return;
+        }
+        if i >= 2 && is_path_sep(tokens, i - 2) {
+            return;
+        }
+        let range = index.range(tokens[i].span());
+        if is_ident(tokens.get(i + 3), import_name) && is_path_sep(tokens, i + 4) {
+            let next = index.range(tokens[i + 3].span());
+            edits.push((range.start..next.start, String::new()));
+        } else if let Some((items, starts)) = macro_rename_group(tokens, i + 3, macros) {
+            edits.push((range, import_name.to_string()));
+            for j in starts {
+                if is_ident(items.get(j), import_name) && is_path_sep(&items, j + 1) {
+                    let start = index.range(items[j].span()).start;
+                    let next = index.range(items[j + 3].span()).start;
+                    edits.push((start..next, String::new()));
+                }
+            }
+        } else if is_macro_call(tokens, i + 3, macros) || is_macro_rename(tokens, i + 3, macros) {
+            edits.push((range, import_name.to_string()));
+        }
+    });
+
+    Ok(apply(source, edits))
+}
+
+fn tokenize(source: &str) -> anyhow::Result<TokenStream> {
+    match TokenStream::from_str(source) {
+        Ok(tokens) => Ok(tokens),
+        Err(err) => bail!("failed to tokenize: {err}"),
+    }
+}
+
+/// グループの中まで再帰的に，各トークンについて `f(同じ階層のトークン列, 位置)` を呼ぶ
+fn walk(stream: TokenStream, f: &mut impl FnMut(&[TokenTree], usize)) {
+    let tokens: Vec<TokenTree> = stream.into_iter().collect();
+    for i in 0..tokens.len() {
+        f(&tokens, i);
+        if let TokenTree::Group(group) = &tokens[i] {
+            walk(group.stream(), f);
+        }
+    }
+}
+
+fn is_ident(token: Option<&TokenTree>, name: &str) -> bool {
+    matches!(token, Some(TokenTree::Ident(ident)) if ident == name)
+}
+
+/// `tokens[i..]` が `::` で始まるか
+fn is_path_sep(tokens: &[TokenTree], i: usize) -> bool {
+    matches!(
+        (tokens.get(i), tokens.get(i + 1)),
+        (Some(TokenTree::Punct(p1)), Some(TokenTree::Punct(p2)))
+            if p1.as_char() == ':' && p1.spacing() == Spacing::Joint && p2.as_char() == ':'
+    )
+}
+
+/// `tokens[i..]` が `${MACRO}!` で始まるか
+fn is_macro_call(tokens: &[TokenTree], i: usize, macros: &BTreeSet<String>) -> bool {
+    let Some(TokenTree::Ident(ident)) = tokens.get(i) else {
+        return false;
+    };
+    macros.contains(&ident.to_string())
+        && matches!(tokens.get(i + 1), Some(TokenTree::Punct(p)) if p.as_char() == '!')
+}
+
+/// `tokens[i..]` が別名をつけたマクロのインポート（`${MACRO} as ${NAME}`）か
+fn is_macro_rename(tokens: &[TokenTree], i: usize, macros: &BTreeSet<String>) -> bool {
+    let Some(TokenTree::Ident(ident)) = tokens.get(i) else {
+        return false;
+    };
+    macros.contains(&ident.to_string()) && is_ident(tokens.get(i + 1), "as")
+}
+
+/// `tokens[i..]` がマクロだけからなる `use` の要素（`${MACRO}` または `${MACRO} as ${NAME}`）か
+fn is_macro_item(tokens: &[TokenTree], i: usize, macros: &BTreeSet<String>) -> bool {
+    let Some(TokenTree::Ident(ident)) = tokens.get(i) else {
+        return false;
+    };
+    macros.contains(&ident.to_string())
+        && (is_ident(tokens.get(i + 1), "as")
+            || tokens
+                .get(i + 1)
+                .is_none_or(|t| matches!(t, TokenTree::Punct(p) if p.as_char() == ',')))
+}
+
+/// `tokens[i]` が別名をつけたマクロを含む `{..}` のとき，その中のトークン列と各要素の開始位置
+fn macro_rename_group(
+    tokens: &[TokenTree],
+    i: usize,
+    macros: &BTreeSet<String>,
+) -> Option<(Vec<TokenTree>, Vec<usize>)> {
+    let Some(TokenTree::Group(group)) = tokens.get(i) else {
+        return None;
+    };
+    if group.delimiter() != Delimiter::Brace {
+        return None;
+    }
+    let items: Vec<TokenTree> = group.stream().into_iter().collect();
+    let starts: Vec<usize> = (0..items.len())
+        .filter(|&j| j == 0 || matches!(&items[j - 1], TokenTree::Punct(p) if p.as_char() == ','))
+        .collect();
+    starts
+        .iter()
+        .any(|&j| is_macro_rename(&items, j, macros))
+        .then_some((items, starts))
+}
+
+/// `tokens[i..]` がマクロの呼び出し，またはマクロで終わるパスか
+fn is_macro_path(tokens: &[TokenTree], i: usize, macros: &BTreeSet<String>) -> bool {
+    let Some(TokenTree::Ident(ident)) = tokens.get(i) else {
+        return false;
+    };
+    macros.contains(&ident.to_string()) && !is_path_sep(tokens, i + 1)
+}
+
+fn apply(source: &str, mut edits: Vec<Edit>) -> String {
+    edits.sort_by_key(|(range, _)| (range.start, range.end));
+
+    let mut res = String::with_capacity(source.len());
+    let mut last = 0;
+    for (range, text) in edits {
+        res += &source[last..range.start];
+        res += &text;
+        last = range.end;
+    }
+    res += &source[last..];
+    res
+}
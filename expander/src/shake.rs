@@ -0,0 +1,822 @@
+//! 展開結果から，到達可能な要素だけを残す（tree shaking）
+//!
+//! 呼び出し元のファイルで指定された要素を起点に，
+//! 名前で参照されている構造体・トレイト・関数・マクロなどを推移的に辿る．
+//!
+//! コンパイル可能性を優先し，以下は保守的に残す．
+//! - `impl` ブロック：ヘッダに現れるライブラリ内の型・トレイトがすべて残る場合
+//! - トレイトの `use`：メソッド呼び出しのためにスコープに必要な場合がある
+//! - 外部クレートの `use`，アイテム位置でのマクロ呼び出し
+
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    fs,
+    ops::Range,
+    path::Path,
+};
+
+use anyhow::{bail, Context};
+use proc_macro2::{Spacing, TokenTree};
+use quote::ToTokens;
+use syn::{visit::Visit, Item, UseTree};
+
+use crate::{
+    module_path::ModulePath,
+    parser::{get_paths, UseVisitor},
+    span::{flatten_tokens, LineIndex},
+};
+
+/// `use` 文の末端
+struct UseLeaf {
+    /// 末端までのパス（グロブの場合は `*` を含まない）
+    path: Vec<String>,
+    /// スコープに導入される名前（グロブの場合は `*`）
+    name: String,
+}
+
+enum ItemKind {
+    Use {
+        leaves: Vec<UseLeaf>,
+        tree: UseTree,
+        leading_colon: bool,
+        /// `pub` などの可視性の範囲
+        vis: Range<usize>,
+    },
+    Impl {
+        /// トレイト名・型名に現れる識別子（ジェネリクスを除く）
+        header: BTreeSet<String>,
+    },
+    Trait,
+    /// インラインのモジュール（分割せずに扱う）
+    Module {
+        traits: BTreeSet<String>,
+    },
+    /// アイテム位置でのマクロ呼び出しなど，名前を持たない要素
+    Anonymous,
+    Other,
+}
+
+struct ItemInfo {
+    kind: ItemKind,
+    /// 定義される名前
+    names: BTreeSet<String>,
+    /// 元のソースコード上の範囲
+    range: Range<usize>,
+    /// `#[cfg(test)]` が付いているか
+    is_test: bool,
+    /// 参照している識別子
+    idents: BTreeSet<String>,
+    /// 呼び出しているマクロ
+    macro_calls: BTreeSet<String>,
+    /// `crate::` から始まるパス（`crate` を除く）
+    crate_paths: Vec<Vec<String>>,
+}
+
+struct ParsedModule {
+    source: String,
+    /// `#![...]` の範囲
+    inner_attrs: Vec<Range<usize>>,
+    items: Vec<ItemInfo>,
+}
+
+/// 名前解決の結果
+enum Target {
+    Item(ModulePath, usize),
+    Leaf(ModulePath, usize, usize),
+    Module(ModulePath),
+}
+
+/// `use` 文のパスの解決先
+enum UseTarget {
+    /// ライブラリ内の別ファイル（ファイル以降の最初の要素）
+    Library(ModulePath, Option<String>),
+    /// 同じファイル内のアイテム
+    Local(String),
+    External,
+}
+
+/// 到達可能性の解析器
+pub struct TreeShaker {
+    modules: BTreeMap<ModulePath, ParsedModule>,
+    kept: BTreeSet<(ModulePath, usize)>,
+    kept_leaves: BTreeSet<(ModulePath, usize, usize)>,
+    requested: BTreeSet<(ModulePath, String)>,
+    queue: VecDeque<(ModulePath, String)>,
+}
+
+impl TreeShaker {
+    /// 依存しているファイルを読み込み，解析器を初期化する
+    pub fn new(library_path: &Path, deps: &BTreeSet<ModulePath>) -> anyhow::Result<Self> {
+        let mut modules = BTreeMap::new();
+
+        for dep in deps {
+            let p = dep.to_pathbuf(library_path.to_path_buf());
+            let source = match fs::read_to_string(&p) {
+                Ok(source) => source,
+                Err(err) => bail!("{}: {:?}", err, p),
+            };
+            let module = parse_module(source).with_context(|| format!("{:?}", p))?;
+            modules.insert(dep.clone(), module);
+        }
+
+        Ok(Self {
+            modules,
+            kept: BTreeSet::new(),
+            kept_leaves: BTreeSet::new(),
+            requested: BTreeSet::new(),
+            queue: VecDeque::new(),
+        })
+    }
+
+    /// 呼び出し元のソースコードから到達可能な要素を求める
+    pub fn shake(&mut self, entry_source: &str, import_name: &str) {
+        for path in get_paths(entry_source, import_name) {
+            // カテゴリ全体（`category::*` など）
+            if path.len() == 1 || path.get(1).is_some_and(|seg| seg == "*") {
+                let modules: Vec<ModulePath> = self
+                    .modules
+                    .keys()
+                    .filter(|m| matches!(m, ModulePath::Module { category, .. } if *category == path[0]))
+                    .cloned()
+                    .collect();
+                if !modules.is_empty() {
+                    for module in modules {
+                        self.apply(Target::Module(module));
+                    }
+                    continue;
+                }
+            }
+            match self.library_target(&path) {
+                Some((module, Some(seg))) if seg != "*" => {
+                    let mut out = vec![];
+                    self.resolve(&module, &seg, &mut BTreeSet::new(), &mut out);
+                    if out.is_empty() {
+                        // 解決できない場合はファイル全体を残す
+                        self.apply(Target::Module(module));
+                    } else {
+                        self.request(&module, &seg);
+                    }
+                }
+                Some((module, _)) => self.apply(Target::Module(module)),
+                None => log::warn!("unresolved path: {import_name}::{}", path.join("::")),
+            }
+        }
+
+        loop {
+            while let Some((module, name)) = self.queue.pop_front() {
+                let mut out = vec![];
+                self.resolve(&module, &name, &mut BTreeSet::new(), &mut out);
+                for target in out {
+                    self.apply(target);
+                }
+            }
+
+            self.keep_conservative_items();
+
+            if self.queue.is_empty() {
+                break;
+            }
+        }
+    }
+
+    /// 残した要素だけからなるソースコードを返す（何も残らない場合は `None`）
+    pub fn render(&self, dep: &ModulePath) -> Option<String> {
+        if !self.is_emitted(dep) {
+            return None;
+        }
+        let module = &self.modules[dep];
+        let mut parts: Vec<String> = module
+            .inner_attrs
+            .iter()
+            .map(|r| module.source[r.clone()].to_string())
+            .collect();
+
+        for (i, item) in module.items.iter().enumerate() {
+            if item.is_test {
+                continue;
+            }
+            match &item.kind {
+                ItemKind::Use {
+                    leaves,
+                    tree,
+                    leading_colon,
+                    vis,
+                } => {
+                    let keep: Vec<bool> = (0..leaves.len())
+                        .map(|j| self.is_leaf_kept(dep, i, j))
+                        .collect();
+                    if keep.iter().all(|&k| k) {
+                        parts.push(module.source[item.range.clone()].to_string());
+                    } else if keep.iter().any(|&k| k) {
+                        let mut keep = keep.into_iter();
+                        let tree = render_use_tree(tree, &mut keep).unwrap();
+                        let vis = &module.source[vis.clone()];
+                        let vis = if vis.is_empty() {
+                            String::new()
+                        } else {
+                            format!("{vis} ")
+                        };
+                        let colon = if *leading_colon { "::" } else { "" };
+                        parts.push(format!("{vis}use {colon}{tree};"));
+                    }
+                }
+                _ => {
+                    if self.kept.contains(&(dep.clone(), i)) {
+                        parts.push(module.source[item.range.clone()].to_string());
+                    }
+                }
+            }
+        }
+
+        Some(parts.join("\n") + "\n")
+    }
+
+    /// いずれかの要素が残るか
+    fn is_emitted(&self, dep: &ModulePath) -> bool {
+        self.kept
+            .range((dep.clone(), 0)..=(dep.clone(), usize::MAX))
+            .next()
+            .is_some()
+            || self
+                .kept_leaves
+                .range((dep.clone(), 0, 0)..=(dep.clone(), usize::MAX, usize::MAX))
+                .next()
+                .is_some()
+    }
+
+    /// `use` 文の末端を残すか
+    fn is_leaf_kept(&self, dep: &ModulePath, i: usize, j: usize) -> bool {
+        let ItemKind::Use { leaves, .. } = &self.modules[dep].items[i].kind else {
+            return false;
+        };
+        let leaf = &leaves[j];
+        match self.use_target(dep, &leaf.path) {
+            UseTarget::External => true,
+            _ if self.kept_leaves.contains(&(dep.clone(), i, j)) => true,
+            UseTarget::Library(module, None) if leaf.name == "*" => self.is_emitted(&module),
+            UseTarget::Local(name) if leaf.name == "*" => {
+                self.modules[dep].items.iter().enumerate().any(|(k, item)| {
+                    item.names.contains(&name) && self.kept.contains(&(dep.clone(), k))
+                })
+            }
+            _ => false,
+        }
+    }
+
+    fn request(&mut self, module: &ModulePath, name: &str) {
+        let key = (module.clone(), name.to_string());
+        if self.requested.insert(key.clone()) {
+            self.queue.push_back(key);
+        }
+    }
+
+    fn apply(&mut self, target: Target) {
+        match target {
+            Target::Item(module, i) => self.keep_item(&module, i),
+            Target::Leaf(module, i, j) => {
+                self.kept_leaves.insert((module, i, j));
+            }
+            Target::Module(module) => {
+                let Some(parsed) = self.modules.get(&module) else {
+                    return;
+                };
+                let names: Vec<String> = parsed
+                    .items
+                    .iter()
+                    .filter(|item| !item.is_test)
+                    .flat_map(|item| item.names.iter().cloned())
+                    .collect();
+                let items: Vec<usize> = parsed
+                    .items
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, item)| !item.is_test && !matches!(item.kind, ItemKind::Use { .. }))
+                    .map(|(i, _)| i)
+                    .collect();
+                for i in items {
+                    self.keep_item(&module, i);
+                }
+                for name in names {
+                    self.request(&module, &name);
+                }
+            }
+        }
+    }
+
+    fn keep_item(&mut self, module: &ModulePath, i: usize) {
+        if !self.kept.insert((module.clone(), i)) {
+            return;
+        }
+        let item = &self.modules[module].items[i];
+        let idents = item.idents.clone();
+        let macro_calls = item.macro_calls.clone();
+        let crate_paths = item.crate_paths.clone();
+
+        for ident in &idents {
+            self.request(module, ident);
+        }
+        for name in &macro_calls {
+            self.request(module, name);
+            let macro_module = ModulePath::Macro { file: name.clone() };
+            if self.modules.contains_key(&macro_module) {
+                self.request(&macro_module, name);
+            }
+        }
+        for path in &crate_paths {
+            match self.library_target(path) {
+                Some((target, Some(seg))) if seg != "*" => self.request(&target, &seg),
+                Some((target, _)) => self.apply(Target::Module(target)),
+                None => (),
+            }
+        }
+    }
+
+    /// 名前だけでは到達を判定できない要素を残す
+    fn keep_conservative_items(&mut self) {
+        let modules: Vec<ModulePath> = self.modules.keys().cloned().collect();
+
+        for module in &modules {
+            let emitted = self.is_emitted(module);
+            let len = self.modules[module].items.len();
+
+            for i in 0..len {
+                let item = &self.modules[module].items[i];
+                if item.is_test || self.kept.contains(&(module.clone(), i)) {
+                    continue;
+                }
+                match &item.kind {
+                    ItemKind::Impl { header } => {
+                        let header = header.clone();
+                        if self.is_impl_reachable(module, &header) {
+                            self.keep_item(module, i);
+                        }
+                    }
+                    ItemKind::Anonymous if emitted => self.keep_item(module, i),
+                    _ => (),
+                }
+            }
+
+            // トレイトはメソッド呼び出しのためにスコープに必要
+            if emitted {
+                let names: Vec<String> = self.modules[module]
+                    .items
+                    .iter()
+                    .filter_map(|item| match &item.kind {
+                        ItemKind::Use { leaves, .. } => Some(leaves),
+                        _ => None,
+                    })
+                    .flatten()
+                    .filter(|leaf| leaf.name != "*")
+                    .map(|leaf| leaf.name.clone())
+                    .collect();
+                for name in names {
+                    if self.is_trait(module, &name) {
+                        self.request(module, &name);
+                    }
+                }
+            }
+        }
+    }
+
+    /// `impl` のヘッダに現れるライブラリ内の要素がすべて残っているか
+    fn is_impl_reachable(&self, module: &ModulePath, header: &BTreeSet<String>) -> bool {
+        let mut found = false;
+        for name in header {
+            let mut out = vec![];
+            self.resolve(module, name, &mut BTreeSet::new(), &mut out);
+            let defs: Vec<_> = out
+                .iter()
+                .filter_map(|t| match t {
+                    Target::Item(m, i) => Some((m.clone(), *i)),
+                    _ => None,
+                })
+                .collect();
+            if defs.is_empty() {
+                continue;
+            }
+            if !defs.iter().any(|def| self.kept.contains(def)) {
+                return false;
+            }
+            found = true;
+        }
+        found
+    }
+
+    /// 名前がトレイトを指しているか
+    fn is_trait(&self, module: &ModulePath, name: &str) -> bool {
+        let mut out = vec![];
+        self.resolve(module, name, &mut BTreeSet::new(), &mut out);
+        out.iter().any(|t| match t {
+            Target::Item(m, i) => match &self.modules[m].items[*i].kind {
+                ItemKind::Trait => true,
+                ItemKind::Module { traits } => traits.contains(name),
+                _ => false,
+            },
+            _ => false,
+        })
+    }
+
+    /// `[category, file, ..]` または `[macro]` の形のパスをファイルに解決する
+    fn library_target(&self, segs: &[String]) -> Option<(ModulePath, Option<String>)> {
+        if let [category, file, rest @ ..] = segs {
+            let module = ModulePath::Module {
+                category: category.clone(),
+                file: file.clone(),
+            };
+            if self.modules.contains_key(&module) {
+                return Some((module, rest.first().cloned()));
+            }
+        }
+        if let [file, ..] = segs {
+            let module = ModulePath::Macro { file: file.clone() };
+            if self.modules.contains_key(&module) {
+                return Some((module, Some(file.clone())));
+            }
+        }
+        None
+    }
+
+    fn use_target(&self, module: &ModulePath, path: &[String]) -> UseTarget {
+        let Some(first) = path.first() else {
+            return UseTarget::External;
+        };
+        let library = |segs: &[String]| match self.library_target(segs) {
+            Some((target, seg)) => UseTarget::Library(target, seg),
+            None => UseTarget::External,
+        };
+        match first.as_str() {
+            "crate" => library(&path[1..]),
+            "super" => match module {
+                ModulePath::Module { category, .. } => {
+                    let segs: Vec<String> = std::iter::once(category.clone())
+                        .chain(path[1..].iter().cloned())
+                        .collect();
+                    library(&segs)
+                }
+                ModulePath::Macro { .. } => library(&path[1..]),
+            },
+            "self" => match path.get(1) {
+                Some(name) => UseTarget::Local(name.clone()),
+                None => UseTarget::External,
+            },
+            _ => {
+                let is_local = self.modules[module].items.iter().any(|item| {
+                    !matches!(item.kind, ItemKind::Use { .. }) && item.names.contains(first)
+                });
+                if is_local {
+                    UseTarget::Local(first.clone())
+                } else {
+                    UseTarget::External
+                }
+            }
+        }
+    }
+
+    /// モジュール内の名前を，それを定義する要素に解決する
+    fn resolve(
+        &self,
+        module: &ModulePath,
+        name: &str,
+        visited: &mut BTreeSet<(ModulePath, String)>,
+        out: &mut Vec<Target>,
+    ) {
+        if !visited.insert((module.clone(), name.to_string())) {
+            return;
+        }
+        let Some(parsed) = self.modules.get(module) else {
+            return;
+        };
+
+        for (i, item) in parsed.items.iter().enumerate() {
+            if item.is_test {
+                continue;
+            }
+            let ItemKind::Use { leaves, .. } = &item.kind else {
+                if item.names.contains(name) {
+                    out.push(Target::Item(module.clone(), i));
+                }
+                continue;
+            };
+            for (j, leaf) in leaves.iter().enumerate() {
+                if leaf.name != "*" && leaf.name != name {
+                    continue;
+                }
+                let before = out.len();
+                match self.use_target(module, &leaf.path) {
+                    UseTarget::Library(target, Some(seg)) if leaf.name != "*" => {
+                        self.resolve(&target, &seg, visited, out);
+                    }
+                    UseTarget::Library(target, Some(seg)) => {
+                        // 別ファイルのインラインモジュールのグロブ
+                        let mut nested = vec![];
+                        self.resolve(&target, &seg, visited, &mut nested);
+                        out.extend(nested.into_iter().filter(|t| match t {
+                            Target::Item(m, k) => self.modules[m].items[*k].names.contains(name),
+                            _ => false,
+                        }));
+                    }
+                    UseTarget::Library(target, None) => {
+                        if leaf.name == "*" {
+                            self.resolve(&target, name, visited, out);
+                        } else {
+                            out.push(Target::Module(target));
+                        }
+                    }
+                    UseTarget::Local(local) => {
+                        let local_name = if leaf.name == "*" { name } else { &local };
+                        for (k, other) in parsed.items.iter().enumerate() {
+                            if !matches!(other.kind, ItemKind::Use { .. })
+                                && other.names.contains(&local)
+                                && other.names.contains(local_name)
+                            {
+                                out.push(Target::Item(module.clone(), k));
+                            }
+                        }
+                    }
+                    UseTarget::External => (),
+                }
+                if out.len() > before {
+                    out.push(Target::Leaf(module.clone(), i, j));
+                }
+            }
+        }
+    }
+}
+
+/// ファイルを要素ごとに分解する
+fn parse_module(source: String) -> anyhow::Result<ParsedModule> {
+    let file = syn::parse_file(&source)?;
+    let index = LineIndex::new(&source);
+
+    let inner_attrs = file
+        .attrs
+        .iter()
+        .filter(|attr| !attr.path().is_ident("doc"))
+        .filter_map(|attr| index.node_range(attr))
+        .collect();
+
+    let items = file
+        .items
+        .iter()
+        .filter_map(|item| {
+            let range = index.node_range(item)?;
+            Some(item_info(item, range, &index))
+        })
+        .collect();
+
+    Ok(ParsedModule {
+        source,
+        inner_attrs,
+        items,
+    })
+}
+
+fn item_info(item: &Item, range: Range<usize>, index: &LineIndex) -> ItemInfo {
+    let mut names = BTreeSet::new();
+    let kind = match item {
+        Item::Use(item_use) => {
+            let mut leaves = vec![];
+            collect_leaves(&item_use.tree, vec![], &mut leaves);
+            names.extend(leaves.iter().map(|l| l.name.clone()));
+            let vis = index
+                .node_range(&item_use.vis)
+                .unwrap_or(range.start..range.start);
+            ItemKind::Use {
+                leaves,
+                tree: item_use.tree.clone(),
+                leading_colon: item_use.leading_colon.is_some(),
+                vis,
+            }
+        }
+        Item::Impl(item_impl) => {
+            let generics: BTreeSet<String> = item_impl
+                .generics
+                .params
+                .iter()
+                .filter_map(|param| match param {
+                    syn::GenericParam::Type(t) => Some(t.ident.to_string()),
+                    syn::GenericParam::Const(c) => Some(c.ident.to_string()),
+                    syn::GenericParam::Lifetime(_) => None,
+                })
+                .collect();
+            let mut tokens = item_impl.self_ty.to_token_stream();
+            if let Some((_, path, _)) = &item_impl.trait_ {
+                tokens.extend(path.to_token_stream());
+            }
+            let mut flat = vec![];
+            flatten_tokens(tokens, &mut flat);
+            let header = flat
+                .iter()
+                .filter_map(|tt| match tt {
+                    TokenTree::Ident(ident) => Some(ident.to_string()),
+                    _ => None,
+                })
+                .filter(|ident| !generics.contains(ident))
+                .collect();
+            ItemKind::Impl { header }
+        }
+        Item::Trait(item_trait) => {
+            names.insert(item_trait.ident.to_string());
+            ItemKind::Trait
+        }
+        Item::Mod(item_mod) => {
+            names.insert(item_mod.ident.to_string());
+            let mut traits = BTreeSet::new();
+            for inner in item_mod.content.iter().flat_map(|(_, items)| items) {
+                names.extend(item_names(inner));
+                if let Item::Trait(t) = inner {
+                    traits.insert(t.ident.to_string());
+                }
+            }
+            ItemKind::Module { traits }
+        }
+        Item::Macro(item_macro) if item_macro.ident.is_none() => ItemKind::Anonymous,
+        Item::ForeignMod(_) | Item::Verbatim(_) => ItemKind::Anonymous,
+        _ => {
+            names.extend(item_names(item));
+            ItemKind::Other
+        }
+    };
+
+    let is_test = is_cfg_test(item_attrs(item));
+
+    let mut idents = BTreeSet::new();
+    let mut macro_calls = BTreeSet::new();
+    let mut crate_paths = vec![];
+
+    if !matches!(kind, ItemKind::Use { .. }) {
+        let mut flat = vec![];
+        flatten_tokens(item.to_token_stream(), &mut flat);
+
+        for (i, tt) in flat.iter().enumerate() {
+            let TokenTree::Ident(ident) = tt else {
+                continue;
+            };
+            let ident = ident.to_string();
+            if let Some(TokenTree::Punct(p)) = flat.get(i + 1) {
+                if p.as_char() == '!' && p.spacing() == Spacing::Alone && ident != "macro_rules" {
+                    macro_calls.insert(ident.clone());
+                }
+            }
+            if ident == "crate" {
+                crate_paths.push(path_after(&flat, i + 1));
+            }
+            idents.insert(ident);
+        }
+
+        // インラインモジュール内の `use crate::{..}` を展開して読む
+        let mut visitor = UseVisitor { uses: vec![] };
+        visitor.visit_item(item);
+        crate_paths.extend(
+            visitor
+                .uses
+                .into_iter()
+                .filter(|path| path.first().is_some_and(|p| p == "crate"))
+                .map(|path| path[1..].to_vec()),
+        );
+    }
+
+    ItemInfo {
+        kind,
+        names,
+        range,
+        is_test,
+        idents,
+        macro_calls,
+        crate_paths,
+    }
+}
+
+/// `::ident` の繰り返しを読み取る
+fn path_after(flat: &[TokenTree], mut i: usize) -> Vec<String> {
+    let mut path = vec![];
+    while let [TokenTree::Punct(p1), TokenTree::Punct(p2), TokenTree::Ident(ident), ..] =
+        &flat[i.min(flat.len())..]
+    {
+        if p1.as_char() != ':' || p2.as_char() != ':' {
+            break;
+        }
+        path.push(ident.to_string());
+        i += 3;
+    }
+    path
+}
+
+/// 要素が定義する名前
+fn item_names(item: &Item) -> Vec<String> {
+    let ident = match item {
+        Item::Const(i) => &i.ident,
+        Item::Enum(i) => &i.ident,
+        Item::ExternCrate(i) => match &i.rename {
+            Some((_, rename)) => rename,
+            None => &i.ident,
+        },
+        Item::Fn(i) => &i.sig.ident,
+        Item::Macro(i) => match &i.ident {
+            Some(ident) => ident,
+            None => return vec![],
+        },
+        Item::Mod(i) => &i.ident,
+        Item::Static(i) => &i.ident,
+        Item::Struct(i) => &i.ident,
+        Item::Trait(i) => &i.ident,
+        Item::TraitAlias(i) => &i.ident,
+        Item::Type(i) => &i.ident,
+        Item::Union(i) => &i.ident,
+        Item::Use(i) => {
+            let mut leaves = vec![];
+            collect_leaves(&i.tree, vec![], &mut leaves);
+            return leaves.into_iter().map(|l| l.name).collect();
+        }
+        _ => return vec![],
+    };
+    vec![ident.to_string()]
+}
+
+/// `#[cfg(test)]` が付いているか
+pub(crate) fn is_cfg_test(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path().is_ident("cfg") && attr.meta.to_token_stream().to_string() == "cfg (test)"
+    })
+}
+
+/// 要素に付いている属性
+pub(crate) fn item_attrs(item: &Item) -> &[syn::Attribute] {
+    match item {
+        Item::Const(i) => &i.attrs,
+        Item::Enum(i) => &i.attrs,
+        Item::ExternCrate(i) => &i.attrs,
+        Item::Fn(i) => &i.attrs,
+        Item::ForeignMod(i) => &i.attrs,
+        Item::Impl(i) => &i.attrs,
+        Item::Macro(i) => &i.attrs,
+        Item::Mod(i) => &i.attrs,
+        Item::Static(i) => &i.attrs,
+        Item::Struct(i) => &i.attrs,
+        Item::Trait(i) => &i.attrs,
+        Item::TraitAlias(i) => &i.attrs,
+        Item::Type(i) => &i.attrs,
+        Item::Union(i) => &i.attrs,
+        Item::Use(i) => &i.attrs,
+        _ => &[],
+    }
+}
+
+fn collect_leaves(tree: &UseTree, mut path: Vec<String>, leaves: &mut Vec<UseLeaf>) {
+    match tree {
+        UseTree::Path(p) => {
+            path.push(p.ident.to_string());
+            collect_leaves(&p.tree, path, leaves);
+        }
+        UseTree::Name(n) if n.ident == "self" => {
+            let name = path.last().cloned().unwrap_or_default();
+            leaves.push(UseLeaf { path, name });
+        }
+        UseTree::Name(n) => {
+            path.push(n.ident.to_string());
+            let name = n.ident.to_string();
+            leaves.push(UseLeaf { path, name });
+        }
+        UseTree::Rename(r) => {
+            path.push(r.ident.to_string());
+            let name = r.rename.to_string();
+            leaves.push(UseLeaf { path, name });
+        }
+        UseTree::Glob(_) => {
+            let name = "*".to_string();
+            leaves.push(UseLeaf { path, name });
+        }
+        UseTree::Group(g) => {
+            for item in &g.items {
+                collect_leaves(item, path.clone(), leaves);
+            }
+        }
+    }
+}
+
+/// 残す末端だけからなる `use` 文の木を文字列にする
+fn render_use_tree(tree: &UseTree, keep: &mut impl Iterator<Item = bool>) -> Option<String> {
+    match tree {
+        UseTree::Path(p) => {
+            render_use_tree(&p.tree, keep).map(|rest| format!("{}::{}", p.ident, rest))
+        }
+        UseTree::Name(n) => keep.next().unwrap().then(|| n.ident.to_string()),
+        UseTree::Rename(r) => keep
+            .next()
+            .unwrap()
+            .then(|| format!("{} as {}", r.ident, r.rename)),
+        UseTree::Glob(_) => keep.next().unwrap().then(|| "*".to_string()),
+        UseTree::Group(g) => {
+            let items: Vec<String> = g
+                .items
+                .iter()
+                .filter_map(|item| render_use_tree(item, keep))
+                .collect();
+            match items.len() {
+                0 => None,
+                1 if items[0] != "self" => items.into_iter().next(),
+                _ => Some(format!("{{{}}}", items.join(", "))),
+            }
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use std::ops::Range;
+
+use proc_macro2::{LineColumn, Span, TokenStream, TokenTree};
+use quote::ToTokens;
+
+/// ソースコード上の位置（行・列）をバイト位置に変換する
+pub struct LineIndex<'a> {
+    /// 元のソースコード
+    source: &'a str,
+    /// 各行の先頭のバイト位置
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    pub fn new(source: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self {
+            source,
+            line_starts,
+        }
+    }
+
+    /// 行・列（行は1始まり，列は文字単位）をバイト位置に変換する
+    pub fn offset(&self, pos: LineColumn) -> usize {
+        let Some(&start) = self.line_starts.get(pos.line.saturating_sub(1)) else {
+            return self.source.len();
+        };
+        self.source[start..]
+            .char_indices()
+            .nth(pos.column)
+            .map_or(self.source.len(), |(i, _)| start + i)
+    }
+
+    /// スパンの範囲をバイト位置の範囲に変換する
+    pub fn range(&self, span: Span) -> Range<usize> {
+        self.offset(span.start())..self.offset(span.end())
+    }
+
+    /// 構文要素全体（属性，ドキュメントコメントを含む）の範囲を返す
+    pub fn node_range<T: ToTokens>(&self, node: &T) -> Option<Range<usize>> {
+        let tokens: Vec<TokenTree> = node.to_token_stream().into_iter().collect();
+        let first = tokens.first()?.span();
+        let last = tokens.last()?.span();
+        Some(self.offset(first.start())..self.offset(last.end()))
+    }
+}
+
+/// トークン列を再帰的に平坦化する（グループは区切り文字を除いて展開する）
+pub fn flatten_tokens(tokens: TokenStream, out: &mut Vec<TokenTree>) {
+    for tt in tokens {
+        match tt {
+            TokenTree::Group(group) => flatten_tokens(group.stream(), out),
+            tt => out.push(tt),
+        }
+    }
+}
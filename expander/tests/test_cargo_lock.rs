@@ -0,0 +1,160 @@
+use std::{env, fs, path::PathBuf};
+
+use expander::{cargo_lock::resolve_library_path, expander::ModuleExpander};
+
+/// 一時ディレクトリにファイルを書き出す
+fn write_files(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let root = env::temp_dir().join(format!("expander_{}_{name}", std::process::id()));
+    for (path, contents) in files {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+    root
+}
+
+const LIBRARY_MANIFEST: &str = r#"[package]
+name = "my-library"
+version = "0.1.0"
+edition = "2021"
+"#;
+
+#[test]
+fn test_resolve_path_dependency() {
+    let root = write_files(
+        "path_dependency",
+        &[
+            (
+                "Cargo.toml",
+                "[workspace]\nmembers = [\n    \"contest/*\",\n]\n",
+            ),
+            (
+                "Cargo.lock",
+                "version = 3\n\n[[package]]\nname = \"abc001\"\nversion = \"0.1.0\"\ndependencies = [\n \"my-library\",\n]\n\n[[package]]\nname = \"my-library\"\nversion = \"0.1.0\"\n",
+            ),
+            (
+                "contest/abc001/Cargo.toml",
+                "[package]\nname = \"abc001\"\nversion = \"0.1.0\"\n\n[dependencies]\nlib = { package = \"my-library\", path = \"../../library/my-library\" }\n",
+            ),
+            ("contest/abc001/src/main.rs", "fn main() {}\n"),
+            ("library/my-library/Cargo.toml", LIBRARY_MANIFEST),
+            ("library/my-library/src/lib.rs", ""),
+        ],
+    );
+
+    let path =
+        resolve_library_path(&root.join("contest/abc001/src/main.rs"), "my-library").unwrap();
+    assert_eq!(
+        path,
+        root.join("library/my-library").canonicalize().unwrap()
+    );
+
+    fs::remove_dir_all(root).ok();
+}
+
+#[test]
+fn test_resolve_path_dependency_table() {
+    let root = write_files(
+        "path_dependency_table",
+        &[
+            (
+                "Cargo.toml",
+                "[package]\nname = \"solver\"\nversion = \"0.1.0\"\n\n[dependencies.my-library]\npath = \"my-library\"\n",
+            ),
+            (
+                "Cargo.lock",
+                "version = 3\n\n[[package]]\nname = \"my-library\"\nversion = \"0.1.0\"\n",
+            ),
+            ("src/main.rs", "fn main() {}\n"),
+            ("my-library/Cargo.toml", LIBRARY_MANIFEST),
+        ],
+    );
+
+    let path = resolve_library_path(&root.join("src/main.rs"), "my-library").unwrap();
+    assert_eq!(path, root.join("my-library").canonicalize().unwrap());
+
+    fs::remove_dir_all(root).ok();
+}
+
+#[test]
+fn test_expand_registry_dependency() {
+    // `cargo vendor` のディレクトリ名は `<crate>-<version>` になる
+    let root = write_files(
+        "registry_expand",
+        &[
+            (
+                "Cargo.lock",
+                "version = 3\n\n[[package]]\nname = \"my-library\"\nversion = \"0.1.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n",
+            ),
+            (
+                "src/main.rs",
+                "use my_library::math::add;\n\nfn main() {\n    println!(\"{}\", add(1, 2));\n}\n",
+            ),
+            ("vendor/my-library-0.1.0/Cargo.toml", LIBRARY_MANIFEST),
+            ("vendor/my-library-0.1.0/src/lib.rs", "pub mod math;\n"),
+            (
+                "vendor/my-library-0.1.0/src/math.rs",
+                "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n",
+            ),
+        ],
+    );
+
+    let entry = root.join("src/main.rs");
+    let path = resolve_library_path(&entry, "my-library").unwrap();
+    assert_eq!(path, root.join("vendor/my-library-0.1.0"));
+
+    // ディレクトリ名ではなく，パッケージ名からインポート時の名前を決める
+    let mut expander = ModuleExpander::new(entry, path).unwrap();
+    assert_eq!(expander.library_name, "my-library");
+    assert_eq!(expander.import_name, "my_library");
+
+    let expanded = expander.expand().unwrap();
+    assert!(expanded.starts_with("use crate::my_library::math::add;\n"));
+    assert!(expanded.contains("pub mod my_library {"));
+    assert!(expanded.contains("pub fn add(a: i32, b: i32) -> i32"));
+
+    fs::remove_dir_all(root).ok();
+}
+
+#[test]
+fn test_resolve_vendored_dependency() {
+    let root = write_files(
+        "vendored",
+        &[
+            (
+                "Cargo.lock",
+                "version = 3\n\n[[package]]\nname = \"my-library\"\nversion = \"0.1.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n",
+            ),
+            ("src/main.rs", "fn main() {}\n"),
+            ("vendor/my-library/Cargo.toml", LIBRARY_MANIFEST),
+        ],
+    );
+
+    let path = resolve_library_path(&root.join("src/main.rs"), "my-library").unwrap();
+    assert_eq!(path, root.join("vendor/my-library"));
+
+    fs::remove_dir_all(root).ok();
+}
+
+#[test]
+fn test_resolve_multiple_versions() {
+    let root = write_files(
+        "multiple_versions",
+        &[
+            (
+                "Cargo.lock",
+                "version = 3\n\n[[package]]\nname = \"my-library\"\nversion = \"0.1.0\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\n\n[[package]]\nname = \"my-library\"\nversion = \"0.2.0\"\n",
+            ),
+            ("src/main.rs", "fn main() {}\n"),
+        ],
+    );
+
+    let err = resolve_library_path(&root.join("src/main.rs"), "my-library")
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("multiple versions of my-library"));
+    assert!(err.contains("0.1.0 (registry+https://github.com/rust-lang/crates.io-index)"));
+    assert!(err.contains("0.2.0 (path)"));
+
+    fs::remove_dir_all(root).ok();
+}
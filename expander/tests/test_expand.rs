@@ -1,15 +1,20 @@
-use std::{collections::BTreeSet, env, path::PathBuf};
+use std::{collections::BTreeSet, env, fs, path::PathBuf};
 
 use expander::{
-    expander::ModuleExpander,
+    check::check,
+    expander::{resolve_path, ModuleExpander},
     module_path::ModulePath,
-    parser::{get_deps, UseVisitor},
+    parser::{get_deps, get_paths, UseVisitor},
+    profile::Profile,
+    rewrite::{prefix_crate_paths, qualify_library_paths, unqualify_library_paths},
 };
 use syn::visit::Visit;
 
 /// ライブラリのパスを取得する
 fn get_library_path() -> PathBuf {
-    PathBuf::from(env::var("KYOPURO_LIBRARY_DIR").unwrap())
+    env::var("KYOPURO_LIBRARY_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("../cp-library-rs"))
 }
 
 macro_rules! debug {
@@ -41,7 +46,7 @@ fn test_get_library_path() {
 
 #[test]
 fn test_enum_modules() {
-    let syntax_tree = syn::parse_file(&SOURCE).expect("Unable to parse file");
+    let syntax_tree = syn::parse_file(SOURCE).expect("Unable to parse file");
 
     let mut visitor = UseVisitor { uses: vec![] };
     visitor.visit_file(&syntax_tree);
@@ -52,7 +57,7 @@ fn test_enum_modules() {
 #[test]
 fn test_local_deps() {
     assert_eq!(
-        get_deps(&SOURCE, "cp_library_rs"),
+        get_deps(SOURCE, "cp_library_rs"),
         BTreeSet::from([
             ModulePath::Module {
                 category: "number_theory".to_string(),
@@ -69,7 +74,7 @@ fn test_local_deps() {
         ])
     );
 
-    assert_eq!(get_deps(&SOURCE, "crate"), BTreeSet::new());
+    assert_eq!(get_deps(SOURCE, "crate"), BTreeSet::new());
 }
 
 #[test]
@@ -83,17 +88,28 @@ fn test_parser() {
     assert_eq!(
         res.dependancies,
         Some(BTreeSet::from([
+            ModulePath::Module {
+                category: "algebraic_structure".to_string(),
+                file: "indexed_monoid".to_string()
+            },
             ModulePath::Module {
                 category: "algebraic_structure".to_string(),
                 file: "monoid".to_string()
             },
+            ModulePath::Module {
+                category: "algebraic_structure".to_string(),
+                file: "operation".to_string()
+            },
             ModulePath::Module {
                 category: "data_structure".to_string(),
                 file: "segment_tree".to_string()
             },
             ModulePath::Module {
-                category: "utils".to_string(),
-                file: "num_traits".to_string()
+                category: "tree".to_string(),
+                file: "show_binary_tree".to_string()
+            },
+            ModulePath::Macro {
+                file: "debug".to_string()
             },
         ]))
     );
@@ -107,6 +123,11 @@ fn test_parser() {
     assert_eq!(
         res.dependancies,
         Some(BTreeSet::from([
+            // `Modint` の `Writable` の実装
+            ModulePath::Module {
+                category: "io".to_string(),
+                file: "output".to_string(),
+            },
             ModulePath::Module {
                 category: "number_theory".to_string(),
                 file: "modint".to_string(),
@@ -115,10 +136,6 @@ fn test_parser() {
                 category: "number_theory".to_string(),
                 file: "comb".to_string(),
             },
-            ModulePath::Module {
-                category: "utils".to_string(),
-                file: "num_traits".to_string(),
-            }
         ]))
     );
 }
@@ -140,14 +157,610 @@ fn test_get_module() {
     );
 }
 
+/// ソースコードを一時ファイルに書き出す
+fn write_temp(name: &str, source: &str) -> PathBuf {
+    let p = env::temp_dir().join(format!("expander_{}_{name}", std::process::id()));
+    fs::write(&p, source).unwrap();
+    p
+}
+
 #[test]
 fn test_expand_restore() {
-    let p = PathBuf::from("../cp-library-rs/tests/test_modint_comb.rs");
+    let source = fs::read_to_string("../cp-library-rs/tests/test_modint_comb.rs").unwrap();
+    let p = write_temp("test_modint_comb.rs", &source);
     let mut res = ModuleExpander::new(p, get_library_path()).unwrap();
 
     // 展開
-    res.expand().ok();
+    res.expand_inplace().unwrap();
+    assert_ne!(fs::read_to_string(&res.entry_file).unwrap(), source);
 
     // 復元
-    res.restore().ok();
+    res.restore().unwrap();
+    assert_eq!(fs::read_to_string(&res.entry_file).unwrap(), source);
+}
+
+const SOURCE_WITH_LOOKALIKES: &str = r#"//! `cp_library_rs::number_theory` を使う
+use cp_library_rs::number_theory::modint::M998;
+
+/// ドキュメント中の cp_library_rs::number_theory::modint は書き換えない
+fn cp_library_rs_like(crate_name: &str) -> String {
+    // コメント中の cp_library_rs::get!() も書き換えない
+    format!("{crate_name} cp_library_rs::number_theory {}", r"crate::cp_library_rs")
+}
+
+fn main() {
+    let x = cp_library_rs::number_theory::modint::M107::new(2);
+    let n: usize = cp_library_rs::get!(usize);
+    println!("{} {} {}", M998::new(n), x, cp_library_rs_like("a"));
+}
+"#;
+
+#[test]
+fn test_expand_lookalikes() {
+    let p = write_temp("lookalikes.rs", SOURCE_WITH_LOOKALIKES);
+    let mut res = ModuleExpander::new(p, get_library_path()).unwrap();
+    let expanded = res.expand().unwrap();
+
+    let user_part = expanded
+        .split("// ==================== cp-library-rs")
+        .next()
+        .unwrap();
+
+    assert_eq!(
+        user_part,
+        r#"//! `cp_library_rs::number_theory` を使う
+use crate::cp_library_rs::number_theory::modint::M998;
+
+/// ドキュメント中の cp_library_rs::number_theory::modint は書き換えない
+fn cp_library_rs_like(crate_name: &str) -> String {
+    // コメント中の cp_library_rs::get!() も書き換えない
+    format!("{crate_name} cp_library_rs::number_theory {}", r"crate::cp_library_rs")
+}
+
+fn main() {
+    let x = crate::cp_library_rs::number_theory::modint::M107::new(2);
+    let n: usize = crate::get!(usize);
+    println!("{} {} {}", M998::new(n), x, cp_library_rs_like("a"));
+}
+
+"#
+    );
+
+    // 復元するとバイト単位で元に戻る
+    assert_eq!(
+        res.restore_source(&expanded).unwrap(),
+        SOURCE_WITH_LOOKALIKES
+    );
+}
+
+#[test]
+fn test_expand_macro_rename() {
+    let source = r#"use cp_library_rs::scan as get;
+
+fn main() {
+    let n = get!(usize);
+    println!("{n}");
+}
+"#;
+    let p = write_temp("macro_rename.rs", source);
+    let mut res = ModuleExpander::new(p, get_library_path()).unwrap();
+    let expanded = res.expand().unwrap();
+
+    // クレート直下のマクロを別名でインポートする
+    assert!(expanded.starts_with("use crate::scan as get;\n"));
+    assert!(expanded.contains("pub mod scanner {"));
+
+    assert_eq!(res.restore_source(&expanded).unwrap(), source);
+}
+
+#[test]
+fn test_expand_grouped_macro_rename() {
+    let source = r#"use cp_library_rs::{
+    number_theory::modint::M998,
+    scan as get,
+    utils::consts::Infinity,
+};
+
+fn main() {
+    let n = get!(usize);
+    println!("{} {}", M998::new(n), usize::infinity());
+}
+"#;
+    let p = write_temp("grouped_macro_rename.rs", source);
+    let mut res = ModuleExpander::new(p, get_library_path()).unwrap();
+    let expanded = res.expand().unwrap();
+
+    // マクロはクレート直下から，それ以外はライブラリのモジュールからインポートする
+    assert!(expanded.starts_with(
+        r#"use crate::{
+    cp_library_rs::number_theory::modint::M998,
+    scan as get,
+    cp_library_rs::utils::consts::Infinity,
+};
+"#
+    ));
+    assert!(expanded.contains("pub mod scanner {"));
+    assert!(expanded.contains("pub mod modint {"));
+
+    assert_eq!(res.restore_source(&expanded).unwrap(), source);
+}
+
+#[test]
+fn test_super_paths() {
+    let source = r#"use super::sibling::A;
+
+pub mod inner {
+    use super::super::other::B;
+    use super::Local;
+
+    mod deep {
+        use super::super::Local;
+        use super::super::super::super::root::C;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+}
+"#;
+    // ファイル内のモジュールを指すパスは含めない
+    assert_eq!(
+        get_paths(source, "super"),
+        BTreeSet::from([
+            vec!["sibling".to_string(), "A".to_string()],
+            vec!["other".to_string(), "B".to_string()],
+            vec!["super".to_string(), "root".to_string(), "C".to_string()],
+        ])
+    );
+}
+
+/// `src/cat/{a,b,c}.rs` からなるライブラリを作り，`cat::a` の依存関係を解析する
+fn solve_super_dependencies(name: &str, a: &str) -> anyhow::Result<BTreeSet<ModulePath>> {
+    let root = env::temp_dir().join(format!("expander_{}_{name}", std::process::id()));
+    let library = root.join("superlib");
+    fs::create_dir_all(library.join("src/cat")).unwrap();
+    fs::create_dir_all(library.join("src/top")).unwrap();
+    for (path, contents) in [
+        ("src/lib.rs", "pub mod cat;\npub mod top;\n"),
+        ("src/cat/mod.rs", "pub mod a;\npub mod b;\npub mod c;\n"),
+        ("src/cat/a.rs", a),
+        ("src/cat/b.rs", "pub fn g() {}\n"),
+        ("src/cat/c.rs", "pub fn h() {}\n"),
+        ("src/top/mod.rs", "pub mod t;\n"),
+        ("src/top/t.rs", "pub fn t() {}\n"),
+        (
+            "main.rs",
+            "use superlib::cat::a::f;\n\nfn main() {\n    f();\n}\n",
+        ),
+    ] {
+        fs::write(library.join(path), contents).unwrap();
+    }
+
+    let mut expander = ModuleExpander::new(library.join("main.rs"), library).unwrap();
+    let res = expander.solve_dependancies();
+    fs::remove_dir_all(root).ok();
+    res.map(|_| expander.dependancies.unwrap())
+}
+
+#[test]
+fn test_resolve_super() {
+    let module = |category: &str, file: &str| ModulePath::Module {
+        category: category.to_string(),
+        file: file.to_string(),
+    };
+
+    // テスト用のモジュールの `super::*` はファイル自身を指す
+    let deps = solve_super_dependencies(
+        "super_relative",
+        r#"use super::b::g;
+
+pub fn f() {
+    g();
+    super::super::top::t::t();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+}
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        deps,
+        BTreeSet::from([module("cat", "a"), module("cat", "b"), module("top", "t")])
+    );
+
+    // 親のモジュールのグロブはカテゴリ全体を取り込むためエラーにする
+    let err =
+        solve_super_dependencies("super_glob", "use super::*;\n\npub fn f() {}\n").unwrap_err();
+    assert!(format!("{err:#}").contains("super::*"));
+}
+
+#[test]
+fn test_prefix_crate_paths() {
+    let source = r#"use crate::algebraic_structure::monoid::Monoid;
+use crate::chmax;
+
+/// `crate::utils` は書き換えない
+pub(crate) fn crate_name() -> &'static str {
+    chmax!(a, b);
+    "crate::utils"
+}
+
+#[macro_export]
+macro_rules! my_macro {
+    ($x:expr) => {
+        $crate::utils::grid::Grid::new($x, $crate::get!(usize))
+    };
+}
+"#;
+    let macros = BTreeSet::from(["chmax".to_string(), "get".to_string()]);
+
+    assert_eq!(
+        prefix_crate_paths(source, "cp_library_rs", &macros).unwrap(),
+        r#"use crate::cp_library_rs::algebraic_structure::monoid::Monoid;
+use crate::chmax;
+
+/// `crate::utils` は書き換えない
+pub(crate) fn crate_name() -> &'static str {
+    chmax!(a, b);
+    "crate::utils"
+}
+
+#[macro_export]
+macro_rules! my_macro {
+    ($x:expr) => {
+        $crate::cp_library_rs::utils::grid::Grid::new($x, $crate::get!(usize))
+    };
+}
+"#
+    );
+}
+
+#[test]
+fn test_qualify_roundtrip() {
+    let macros = BTreeSet::from(["get".to_string()]);
+
+    for source in [
+        SOURCE,
+        SOURCE_WITH_LOOKALIKES,
+        "fn main() { let a = ::cp_library_rs::x(); let b = foo::cp_library_rs::y(); }",
+        "use cp_library_rs::get as input;\nfn main() { let n = input!(usize); }",
+    ] {
+        let qualified = qualify_library_paths(source, "cp_library_rs", &macros).unwrap();
+        assert_eq!(
+            unqualify_library_paths(&qualified, "cp_library_rs", &macros).unwrap(),
+            source
+        );
+    }
+}
+
+#[test]
+fn test_tree_shaking() {
+    let source = r#"use cp_library_rs::{
+    algebraic_structure::operation::Add, data_structure::segment_tree::SegmentTree,
+};
+
+fn main() {
+    let mut seg = SegmentTree::<Add<i64>>::new(5);
+    seg.update(0, 5);
+    println!("{}", seg.get_range(..));
+}
+"#;
+    let p = write_temp("tree_shaking.rs", source);
+    let mut res = ModuleExpander::new(p, get_library_path()).unwrap();
+    let full = res.expand().unwrap();
+
+    res.tree_shaking = true;
+    let shaken = res.expand().unwrap();
+
+    assert!(shaken.len() < full.len());
+
+    // 使用している要素は残る
+    assert!(shaken.contains("pub struct SegmentTree<M: Monoid>"));
+    assert!(shaken.contains("pub trait Monoid"));
+    assert!(shaken.contains("impl<T: Zero + Clone> Monoid for Add<T>"));
+
+    // 使用していない要素は削除される
+    assert!(!shaken.contains("pub struct Max<T>"));
+    assert!(!shaken.contains("Monoid for Max<T>"));
+    assert!(!shaken.contains("pub mod debug"));
+}
+
+#[test]
+fn test_deps_from_paths_and_macros() {
+    let source = r#"
+fn main() {
+    let n = cp_library_rs::get!(usize);
+    let g: cp_library_rs::graph::simple_graph::Graph = todo!();
+    let d = cp_library_rs::graph::dijkstra::dijkstra(&g, 0);
+    cp_library_rs::debug!(cp_library_rs::number_theory::powmod::powmod(2, n, 7));
+}
+"#;
+    let paths = get_paths(source, "cp_library_rs");
+    assert!(paths.contains(&vec!["get".to_string()]));
+    assert!(paths.contains(&vec![
+        "graph".to_string(),
+        "dijkstra".to_string(),
+        "dijkstra".to_string()
+    ]));
+    assert!(paths.contains(&vec![
+        "number_theory".to_string(),
+        "powmod".to_string(),
+        "powmod".to_string()
+    ]));
+
+    let deps = get_deps(source, "cp_library_rs");
+    for dep in [
+        ModulePath::Macro {
+            file: "get".to_string(),
+        },
+        ModulePath::Macro {
+            file: "debug".to_string(),
+        },
+        ModulePath::Module {
+            category: "graph".to_string(),
+            file: "simple_graph".to_string(),
+        },
+        ModulePath::Module {
+            category: "graph".to_string(),
+            file: "dijkstra".to_string(),
+        },
+        ModulePath::Module {
+            category: "number_theory".to_string(),
+            file: "powmod".to_string(),
+        },
+    ] {
+        assert!(deps.contains(&dep), "{dep:?} not found");
+    }
+}
+
+#[test]
+fn test_resolve_glob() {
+    let library_path = get_library_path().join("src");
+
+    // カテゴリのグロブは mod.rs に含まれるすべてのファイルに解決される
+    let deps = resolve_path(&library_path, &["graph".to_string(), "*".to_string()]).unwrap();
+    assert!(deps.contains(&ModulePath::Module {
+        category: "graph".to_string(),
+        file: "dijkstra".to_string()
+    }));
+    assert!(deps.contains(&ModulePath::Module {
+        category: "graph".to_string(),
+        file: "scc".to_string()
+    }));
+    assert!(deps
+        .iter()
+        .all(|dep| matches!(dep, ModulePath::Module { category, .. } if category == "graph")));
+
+    assert_eq!(
+        resolve_path(&library_path, &["get".to_string()]).unwrap(),
+        vec![ModulePath::Macro {
+            file: "get".to_string()
+        }]
+    );
+    assert!(resolve_path(&library_path, &["no_such_module".to_string()])
+        .unwrap()
+        .is_empty());
+
+    // 展開結果にカテゴリ内のすべてのファイルが含まれる
+    let p = write_temp(
+        "glob.rs",
+        "use cp_library_rs::convolution::*;\n\nfn main() {}\n",
+    );
+    let mut res = ModuleExpander::new(p, get_library_path()).unwrap();
+    let expanded = res.expand().unwrap();
+    assert!(expanded.contains("pub mod convolution {"));
+    assert!(expanded.contains("pub mod ntt {"));
+}
+
+#[test]
+fn test_minify_and_max_bytes() {
+    let source = fs::read_to_string("../cp-library-rs/tests/test_segment_tree.rs").unwrap();
+    let p = write_temp("minify.rs", &source);
+    let mut res = ModuleExpander::new(p, get_library_path()).unwrap();
+    let full = res.expand().unwrap();
+
+    res.minify = true;
+    let minified = res.expand().unwrap();
+    assert!(minified.len() < full.len());
+
+    // ユーザーのコードは変更しない
+    let user_part = |s: &str| {
+        s.split("// ==================== cp-library-rs")
+            .next()
+            .unwrap()
+            .to_string()
+    };
+    assert_eq!(user_part(&minified), user_part(&full));
+    assert!(!user_part(&minified).contains("/// "));
+
+    // 上限を超える場合は大きいモジュールを列挙する
+    res.max_bytes = Some(minified.len() - 1);
+    let err = res.expand().unwrap_err().to_string();
+    assert!(err.contains("exceeds the limit"));
+    assert!(err.contains("data_structure::segment_tree"));
+
+    res.max_bytes = Some(minified.len());
+    assert_eq!(res.expand().unwrap(), minified);
+}
+
+#[test]
+fn test_expand_inplace_twice() {
+    let source = fs::read_to_string("../cp-library-rs/tests/test_modint_comb.rs").unwrap();
+    let p = write_temp("reexpand.rs", &source);
+    let mut res = ModuleExpander::new(p.clone(), get_library_path()).unwrap();
+
+    res.expand_inplace().unwrap();
+    let once = fs::read_to_string(&p).unwrap();
+
+    // 2回展開しても結果は変わらない
+    res.expand_inplace().unwrap();
+    assert_eq!(fs::read_to_string(&p).unwrap(), once);
+    assert_eq!(
+        once.matches("// ==================== cp-library-rs ====================")
+            .count(),
+        1
+    );
+
+    // 展開後にユーザーのコードを編集して再展開する
+    let edited = once.replacen(
+        "\n// ==================== cp-library-rs",
+        "\nfn edited() -> usize {\n    cp_library_rs::number_theory::powmod::powmod(2, 10, 1000)\n}\n\n// ==================== cp-library-rs",
+        1,
+    );
+    fs::write(&p, edited).unwrap();
+
+    res.expand_inplace().unwrap();
+    let twice = fs::read_to_string(&p).unwrap();
+    assert!(twice.contains("pub mod powmod {"));
+    assert!(twice.contains("crate::cp_library_rs::number_theory::powmod::powmod(2, 10, 1000)"));
+    assert_eq!(
+        twice
+            .matches("// ==================== cp-library-rs ====================")
+            .count(),
+        1
+    );
+
+    // 復元すると編集後のコードに戻る
+    res.restore().unwrap();
+    assert_eq!(
+        fs::read_to_string(&p).unwrap(),
+        source + "\nfn edited() -> usize {\n    cp_library_rs::number_theory::powmod::powmod(2, 10, 1000)\n}\n"
+    );
+}
+
+/// 型エラーを含むライブラリを一時ディレクトリに作る
+fn write_broken_library() -> PathBuf {
+    let root = env::temp_dir()
+        .join(format!("expander_{}", std::process::id()))
+        .join("broken-lib");
+    fs::create_dir_all(root.join("src").join("utils")).unwrap();
+    fs::write(
+        root.join("Cargo.toml"),
+        "[package]\nname = \"broken-lib\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\n",
+    )
+    .unwrap();
+    fs::write(root.join("src").join("lib.rs"), "pub mod utils;\n").unwrap();
+    fs::write(
+        root.join("src").join("utils").join("mod.rs"),
+        "pub mod broken;\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("src").join("utils").join("broken.rs"),
+        "//! 型エラーを含む\n\n/// 2倍\npub fn double(x: usize) -> usize {\n    x * 2\n}\n\npub fn broken() -> usize {\n    \"broken\"\n}\n",
+    )
+    .unwrap();
+    root
+}
+
+#[test]
+fn test_check_maps_errors() {
+    let library = write_broken_library();
+    let p = write_temp(
+        "check.rs",
+        "use broken_lib::utils::broken::{broken, double};\n\nfn main() {\n    let x: String = double(1);\n    let y: String = broken_lib::utils::broken::double(2);\n    println!(\"{x} {y} {}\", broken());\n}\n",
+    );
+    let mut res = ModuleExpander::new(p.clone(), library.clone()).unwrap();
+    let broken = library.join("src").join("utils").join("broken.rs");
+
+    for minify in [false, true] {
+        res.minify = minify;
+        let diagnostics = check(&mut res).unwrap();
+        let errors: Vec<_> = diagnostics.iter().filter(|d| d.is_error()).collect();
+        debug!(errors);
+
+        // ユーザーのコードのエラー
+        assert!(errors
+            .iter()
+            .any(|d| d.location == Some((p.clone(), 4, Some(21)))));
+        // パスを書き換えた行は列を対応付けない
+        assert!(errors
+            .iter()
+            .any(|d| d.location == Some((p.clone(), 5, None))));
+
+        // ライブラリのエラーは元のファイルの位置に対応付ける
+        let error = errors
+            .iter()
+            .find(|d| matches!(&d.location, Some((file, _, _)) if *file == broken))
+            .unwrap();
+        assert_eq!(error.location, Some((broken.clone(), 9, Some(5))));
+        assert!(error
+            .to_string()
+            .ends_with(&format!("--> {}:9:5", broken.display())));
+    }
+
+    fs::remove_dir_all(library.parent().unwrap()).ok();
+}
+
+#[test]
+fn test_profile_disallowed_crates() {
+    let source = fs::read_to_string("../cp-library-rs/tests/test_segment_tree.rs").unwrap();
+    let p = write_temp("profile.rs", &source);
+    let mut res = ModuleExpander::new(p, get_library_path()).unwrap();
+
+    res.profile = Profile::builtin("atcoder");
+    let full = res.expand().unwrap();
+
+    // 外部クレートを使えないジャッジではモジュールとパスを示してエラーにする
+    res.profile = Profile::builtin("codeforces");
+    let err = res.expand().unwrap_err().to_string();
+    debug!(err);
+    assert!(err.contains("crates unavailable on codeforces"));
+    assert!(err.contains("algebraic_structure::operation: use of `num_traits::"));
+
+    res.profile = None;
+    assert_eq!(res.expand().unwrap(), full);
+}
+
+#[test]
+fn test_dependency_graph() {
+    let source = fs::read_to_string("../cp-library-rs/tests/test_segment_tree.rs").unwrap();
+    let p = write_temp("graph.rs", &source);
+    let mut res = ModuleExpander::new(p, get_library_path()).unwrap();
+    res.solve_dependancies().unwrap();
+
+    // 依存しているモジュールとグラフの頂点は一致する
+    let nodes: BTreeSet<ModulePath> = res.graph.parents.keys().cloned().collect();
+    assert_eq!(Some(nodes), res.dependancies);
+
+    // 直接使っているモジュールは入力ファイルから取り込まれる
+    let segment_tree = ModulePath::Module {
+        category: "data_structure".to_string(),
+        file: "segment_tree".to_string(),
+    };
+    assert_eq!(
+        res.graph.parents[&segment_tree],
+        (
+            None,
+            "cp_library_rs::data_structure::segment_tree::*".to_string()
+        )
+    );
+
+    // ライブラリ内の依存
+    let monoid = ModulePath::Module {
+        category: "algebraic_structure".to_string(),
+        file: "monoid".to_string(),
+    };
+    assert!(res.graph.edges.contains_key(&(Some(segment_tree), monoid)));
+
+    let list = res.graph.to_list("main.rs");
+    debug!(list);
+    assert!(list.contains(
+        "data_structure::segment_tree <- main.rs (cp_library_rs::data_structure::segment_tree::*)"
+    ));
+
+    let dot = res.graph.to_dot("main.rs");
+    assert!(dot.starts_with("digraph dependencies {"));
+    assert!(dot.contains(r#""data_structure::segment_tree" -> "algebraic_structure::monoid";"#));
+
+    let json: serde_json::Value = serde_json::from_str(&res.graph.to_json("main.rs")).unwrap();
+    assert_eq!(json["entry"], "main.rs");
+    assert_eq!(
+        json["nodes"].as_array().unwrap().len(),
+        res.graph.parents.len()
+    );
 }
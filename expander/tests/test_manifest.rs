@@ -0,0 +1,86 @@
+use std::{env, fs, path::PathBuf};
+
+use expander::manifest::{bin_targets, find_bin};
+
+#[test]
+fn test_cargo_compete_bins() {
+    let manifest = PathBuf::from("../library-checker/Cargo.toml");
+
+    let bin = find_bin(&manifest, "tree_diameter").unwrap();
+    assert_eq!(bin.name, "tree_diameter");
+    assert_eq!(
+        bin.path,
+        PathBuf::from("../library-checker/src/bin/tree_diameter.rs")
+    );
+    assert!(bin.path.exists());
+
+    // メタデータに書かれていない src/bin 以下のファイルも含む
+    let bins = bin_targets(&manifest).unwrap();
+    assert!(bins.iter().any(|bin| bin.name == "aplusb"));
+    assert!(bins.windows(2).all(|w| w[0].name < w[1].name));
+
+    let err = find_bin(&manifest, "no_such_problem")
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("no_such_problem"));
+    assert!(err.contains("tree_diameter"));
+}
+
+const MANIFEST: &str = r#"[package]
+name = "abc001"
+version = "0.1.0"
+edition = "2021"
+
+[package.metadata.cargo-compete.bin]
+abc001-a = { alias = "a", problem = "https://atcoder.jp/contests/abc001/tasks/abc001_1" }
+
+[package.metadata.cargo-compete.bin.abc001-b]
+alias = "b"
+problem = "https://atcoder.jp/contests/abc001/tasks/abc001_2"
+
+[[bin]]
+name = "abc001-a"
+path = "src/bin/a.rs"
+
+[[bin]]
+name = "abc001-b"
+path = "src/bin/b.rs"
+
+[[bin]]
+name = "abc001-c"
+
+[dependencies]
+"#;
+
+#[test]
+fn test_bin_tables_and_aliases() {
+    let root = env::temp_dir().join(format!("expander_{}_manifest", std::process::id()));
+    fs::create_dir_all(root.join("src/bin/abc001-c")).unwrap();
+    fs::write(root.join("Cargo.toml"), MANIFEST).unwrap();
+    for file in ["src/bin/a.rs", "src/bin/b.rs", "src/bin/abc001-c/main.rs"] {
+        fs::write(root.join(file), "fn main() {}\n").unwrap();
+    }
+    let manifest = root.join("Cargo.toml");
+
+    let a = find_bin(&manifest, "a").unwrap();
+    assert_eq!(a.name, "abc001-a");
+    assert_eq!(a.path, root.join("src/bin/a.rs"));
+    assert_eq!(find_bin(&manifest, "abc001-a").unwrap(), a);
+
+    let b = find_bin(&manifest, "b").unwrap();
+    assert_eq!(b.name, "abc001-b");
+    assert_eq!(b.alias.as_deref(), Some("b"));
+
+    let c = find_bin(&manifest, "abc001-c").unwrap();
+    assert_eq!(c.path, root.join("src/bin/abc001-c/main.rs"));
+
+    // src/bin 以下のファイルは [[bin]] と重複しない
+    let names: Vec<String> = bin_targets(&manifest)
+        .unwrap()
+        .into_iter()
+        .map(|bin| bin.name)
+        .collect();
+    assert_eq!(names, ["abc001-a", "abc001-b", "abc001-c"]);
+
+    fs::remove_dir_all(root).ok();
+}
@@ -0,0 +1,56 @@
+use expander::minify::minify;
+
+const SOURCE: &str = r#"//! モジュールのドキュメント
+
+use std::fmt::Debug;
+
+/// 構造体のドキュメント
+/** ブロック形式のドキュメント */
+pub struct A {
+    /// フィールドのドキュメント
+    pub x: usize,
+}
+
+impl A {
+    // 通常のコメントは残す
+    pub fn text() -> &'static str {
+        "line1
+    line2 /// not a comment
+
+  line4"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_a() {}
+}
+"#;
+
+#[test]
+fn test_minify() {
+    assert_eq!(
+        minify(SOURCE).unwrap(),
+        r#"use std::fmt::Debug;
+pub struct A {
+pub x: usize,
+}
+impl A {
+// 通常のコメントは残す
+pub fn text() -> &'static str {
+"line1
+    line2 /// not a comment
+
+  line4"
+}
+}
+"#
+    );
+}
+
+#[test]
+fn test_minify_idempotent() {
+    let once = minify(SOURCE).unwrap();
+    assert_eq!(minify(&once).unwrap(), once);
+}
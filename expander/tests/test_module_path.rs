@@ -32,3 +32,17 @@ fn test_display() {
     };
     assert_eq!(p2.to_string(), "crt");
 }
+
+#[test]
+fn test_qualified_name() {
+    let p1 = ModulePath::Macro {
+        file: "get".to_string(),
+    };
+    assert_eq!(&p1.qualified_name(), "get");
+
+    let p2 = ModulePath::Module {
+        category: "number_theory".to_string(),
+        file: "crt".to_string(),
+    };
+    assert_eq!(&p2.qualified_name(), "number_theory::crt");
+}
@@ -0,0 +1,144 @@
+use std::{env, fs, path::Path};
+
+use expander::profile::{external_crates, Profile, CONFIG_FILE};
+
+#[test]
+fn test_builtin() {
+    let atcoder = Profile::builtin("atcoder").unwrap();
+    for krate in [
+        "num_traits",
+        "num",
+        "itertools",
+        "superslice",
+        "rustc_hash",
+        "rand",
+    ] {
+        assert!(atcoder.allows(krate), "{krate}");
+    }
+    assert!(atcoder.allows("num-traits"));
+
+    let codeforces = Profile::builtin("codeforces").unwrap();
+    assert!(!codeforces.allows("num_traits"));
+    assert_eq!(codeforces.rustc.as_deref(), Some("1.75.0"));
+
+    assert!(Profile::builtin("yosupo").unwrap().crates.is_empty());
+    assert!(Profile::builtin("unknown").is_none());
+    assert!(Profile::load("unknown").is_err());
+}
+
+const CONFIG: &str = r#"
+# Codeforces に itertools を追加した環境
+profile = "codeforces"
+name = "my-judge"
+crates = [
+    "itertools",
+    "num-traits",
+]
+"#;
+
+#[test]
+fn test_parse() {
+    let profile = Profile::parse(CONFIG).unwrap();
+    assert_eq!(profile.name, "my-judge");
+    assert_eq!(profile.rustc.as_deref(), Some("1.75.0"));
+    assert_eq!(
+        profile.crates.iter().collect::<Vec<_>>(),
+        ["itertools", "num_traits"]
+    );
+
+    let profile = Profile::parse("rustc = \"1.70.0\"\ncrates = []\n").unwrap();
+    assert_eq!(profile.name, "custom");
+    assert_eq!(profile.rustc.as_deref(), Some("1.70.0"));
+    assert!(profile.crates.is_empty());
+
+    assert!(Profile::parse("profile = \"unknown\"").is_err());
+    assert!(Profile::parse("unknown = \"value\"").is_err());
+    assert!(Profile::parse("crates = [\"itertools\",").is_err());
+}
+
+#[test]
+fn test_find_config() {
+    let dir = env::temp_dir().join(format!("expander_{}_profile", std::process::id()));
+    fs::create_dir_all(dir.join("src")).unwrap();
+    fs::write(dir.join("Cargo.lock"), "").unwrap();
+    let entry = dir.join("src").join("main.rs");
+    fs::write(&entry, "fn main() {}\n").unwrap();
+
+    assert_eq!(Profile::find(&entry).unwrap(), None);
+
+    fs::write(dir.join(CONFIG_FILE), CONFIG).unwrap();
+    assert_eq!(Profile::find(&entry).unwrap().unwrap().name, "my-judge");
+
+    fs::remove_dir_all(dir).ok();
+}
+
+#[test]
+fn test_external_crates() {
+    let crates = external_crates(Path::new("../cp-library-rs")).unwrap();
+    for krate in [
+        "num_traits",
+        "num",
+        "itertools",
+        "superslice",
+        "rustc_hash",
+        "rand",
+    ] {
+        assert!(crates.contains(krate), "{krate}");
+    }
+    assert!(!crates.contains("rstest"));
+}
+
+/// `Cargo.toml` だけを持つ一時的なライブラリ
+fn external_crates_of(name: &str, manifest: &str) -> Vec<String> {
+    let dir = env::temp_dir().join(format!("expander_{}_{name}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("Cargo.toml"), manifest).unwrap();
+    let crates = external_crates(&dir).unwrap();
+    fs::remove_dir_all(dir).ok();
+    crates.into_iter().collect()
+}
+
+#[test]
+fn test_external_crates_inline() {
+    let manifest = r#"
+[package]
+name = "library"
+
+[dependencies]
+num-traits = "0.2"
+rand = { version = "0.9", features = ["small_rng"] }
+itertools.workspace = true
+
+[dev-dependencies]
+rstest = "0.18"
+"#;
+    assert_eq!(
+        external_crates_of("inline", manifest),
+        ["itertools", "num_traits", "rand"]
+    );
+}
+
+#[test]
+fn test_external_crates_subtable() {
+    let manifest = r#"
+[package]
+name = "library"
+
+[dependencies.num-traits]
+version = "0.2"
+
+[dependencies."rustc-hash"]
+version = "2"
+default-features = false
+
+[dev-dependencies.rstest]
+version = "0.18"
+
+[dependencies]
+rand = "0.9"
+"#;
+    assert_eq!(
+        external_crates_of("subtable", manifest),
+        ["num_traits", "rand", "rustc_hash"]
+    );
+}
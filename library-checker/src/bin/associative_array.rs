@@ -1,15 +1,14 @@
 #![allow(non_snake_case)]
 
 use cp_library_rs::{
-    algebraic_structure::monoid::Monoid, data_structure::balanced_binary_tree::BalancedBinaryTree,
-    get,
+    algebraic_structure::monoid::Monoid, data_structure::bbt_aa::BalancedBinaryTree, get,
 };
 
 struct Set;
 
 impl Monoid for Set {
     type Val = usize;
-    fn id() -> Self::Val {
+    fn e() -> Self::Val {
         0
     }
     fn op(_left: &Self::Val, _right: &Self::Val) -> Self::Val {
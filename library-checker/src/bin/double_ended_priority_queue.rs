@@ -1,12 +1,18 @@
 #![allow(non_snake_case)]
 
-use cp_library_rs::{data_structure::multiset_splay_tree::Multiset, get, utils::consts::IINF};
+use cp_library_rs::{
+    data_structure::multiset_splay_tree::{MultiSet, NodeOps},
+    get,
+};
 
 fn main() {
     let (_N, Q) = get!(usize, usize);
     let S = get!(isize;;);
 
-    let mut mset = Multiset::from_iter(S);
+    let mut mset = MultiSet::new();
+    for x in S {
+        mset.insert(x);
+    }
 
     for _ in 0..Q {
         let q = get!(isize;;);
@@ -16,14 +22,14 @@ fn main() {
                 mset.insert(x);
             }
             [1] => {
-                let mini = *mset.lower_bound(&-IINF).unwrap();
+                let mini = *mset.range(..).next().unwrap().key();
                 println!("{}", mini);
-                mset.delete(&mini);
+                mset.remove(&mini);
             }
             [2] => {
-                let maxi = *mset.lower_bound_rev(&IINF).unwrap();
+                let maxi = *mset.range(..).next_back().unwrap().key();
                 println!("{}", maxi);
-                mset.delete(&maxi);
+                mset.remove(&maxi);
             }
             _ => (),
         }
@@ -2,8 +2,8 @@
 
 use cp_library_rs::{
     debug, get, linear_algrebra::dynamic_matrix_exp::Matrix, number_theory::modint::M998,
-    utils::iterutil::IterUtil,
 };
+use itertools::Itertools;
 
 fn main() {
     let (N, M, _K) = get!(usize, usize, usize);
@@ -2,13 +2,16 @@
 
 use cp_library_rs::{
     algebraic_structure::operation::Add, data_structure::segment_tree::SegmentTree, get,
+    io::output::Output, put,
 };
 
 fn main() {
+    let mut out = Output::stdout();
+
     let (_N, Q) = get!(usize, usize);
     let A = get!(isize;;);
 
-    let mut seg = SegmentTree::<Add<isize>>::from(A);
+    let mut seg = SegmentTree::<Add<isize>>::from_vec(A);
 
     for _ in 0..Q {
         let q = get!(usize;;);
@@ -20,7 +23,7 @@ fn main() {
         if let &[1, l, r] = &q[..] {
             let ans = seg.get_range(l..r);
 
-            println!("{ans}");
+            put!(out; ans);
         }
     }
 }
@@ -2,8 +2,8 @@
 
 use cp_library_rs::{
     get, linear_algrebra::dynamic_matrix_exp::Matrix, number_theory::modint::M998,
-    utils::iterutil::IterUtil,
 };
+use itertools::Itertools;
 
 fn main() {
     let (N, K) = get!(usize, usize);
@@ -1,18 +1,18 @@
 #![allow(non_snake_case)]
 
 use cp_library_rs::{
-    algebraic_structure::operation::Add,
-    data_structure::multiset_splay_tree::{Multiset, NodeOps},
-    debug, get,
+    data_structure::multiset_splay_tree::{MultiSet, NodeOps},
+    get,
 };
+
 fn main() {
-    let (N, Q) = get!(usize, usize);
+    let (_N, Q) = get!(usize, usize);
     let T = get!(String);
 
     let mut set = T
         .chars()
         .enumerate()
-        .fold(Multiset::new(), |mut set, (x, e)| {
+        .fold(MultiSet::new(), |mut set, (x, e)| {
             if e == '1' {
                 set.insert(x);
             }
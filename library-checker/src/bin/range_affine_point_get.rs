@@ -1,15 +1,18 @@
 #![allow(non_snake_case)]
 
 use cp_library_rs::{
-    algebraic_structure::extmonoid_mod::AffineSum,
-    data_structure::lazy_segment_tree::LazySegmentTree, get, number_theory::modint::M998,
+    algebraic_structure::actedmonoid_mod::AffineSum,
+    data_structure::lazy_segment_tree::LazySegmentTree, get, io::output::Output,
+    number_theory::modint::M998, put,
 };
 
 fn main() {
+    let mut out = Output::stdout();
+
     let (_N, Q) = get!(usize, usize);
     let A = get!(M998;;);
 
-    let mut seg = LazySegmentTree::<AffineSum<998244353>>::from(&A);
+    let mut seg: LazySegmentTree<AffineSum<M998>> = A.iter().map(|&a| (a, 1)).collect();
 
     for _ in 0..Q {
         let q = get!(usize;;);
@@ -19,7 +22,7 @@ fn main() {
         }
 
         if let &[1, i] = &q[..] {
-            println!("{}", seg.get(i..=i));
+            put!(out; seg.get_at(i).0);
         }
     }
 }
@@ -1,22 +1,18 @@
-#![allow(unused_imports)]
-#![allow(unused_variables)]
 #![allow(non_snake_case)]
-#![allow(dead_code)]
-#![allow(unused_macros)]
 
 use cp_library_rs::{
-    algebraic_structure::{extmonoid::ExtMonoid, extmonoid_mod::AffineSum},
-    data_structure::lazy_segment_tree::LazySegmentTree,
-    get,
-    number_theory::modint::{M998, MOD998},
-    utils::show_binary_tree::ShowBinaryTree,
+    algebraic_structure::actedmonoid_mod::AffineSum,
+    data_structure::lazy_segment_tree::LazySegmentTree, get, io::output::Output,
+    number_theory::modint::M998, put,
 };
 
 fn main() {
-    let (N, Q) = get!(usize, usize);
+    let mut out = Output::stdout();
+
+    let (_N, Q) = get!(usize, usize);
     let A = get!(M998;;);
 
-    let mut seg = LazySegmentTree::<AffineSum<MOD998>>::from(&A);
+    let mut seg: LazySegmentTree<AffineSum<M998>> = A.iter().map(|&a| (a, 1)).collect();
 
     for _ in 0..Q {
         let q = get!(usize;;);
@@ -26,9 +22,7 @@ fn main() {
         }
 
         if let &[1, l, r] = &q[..] {
-            println!("{}", seg.get(l..r));
+            put!(out; seg.get(l..r).0);
         }
-
-        seg.print_as_binary_tree();
     }
 }
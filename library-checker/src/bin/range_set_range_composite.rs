@@ -1,28 +1,60 @@
 #![allow(non_snake_case)]
 
 use cp_library_rs::{
-    algebraic_structure::extmonoid_mod::AffineUpdateComposite,
-    data_structure::lazy_segment_tree::LazySegmentTree, get, number_theory::modint::M998,
-    number_theory::modint::MOD998,
+    algebraic_structure::{
+        actedmonoid::ActedMonoid,
+        affine1d::{Affine, AffineTransform},
+    },
+    data_structure::lazy_segment_tree::LazySegmentTree,
+    get,
+    number_theory::modint::M998,
 };
 
 fn main() {
     let (N, Q) = get!(usize, usize);
-    let AB = get!(M998, M998; N);
+    let AB = get!(usize, usize; N);
 
-    let mut seg = LazySegmentTree::<AffineUpdateComposite<MOD998>>::from(&AB);
+    let mut seg: LazySegmentTree<UpdateComposite> =
+        AB.iter().map(|&(a, b)| ((a.into(), b.into()), 1)).collect();
 
     for _ in 0..Q {
         let q = get!(usize;;);
 
         if let &[0, l, r, c, d] = &q[..] {
-            seg.apply(l..r, (c.into(), d.into()));
+            seg.apply(l..r, Some((c.into(), d.into())));
         }
 
         if let &[1, l, r, x] = &q[..] {
-            let (a, b) = seg.get(l..r);
-            let ans = a * x + b;
-            println!("{ans}");
+            let (f, _) = seg.get(l..r);
+            println!("{}", f.apply(x.into()));
         }
     }
 }
+
+/// 区間を一次関数で更新 + 区間の関数合成
+///
+/// 長さ `size` の区間を `f` で更新すると，合成は `f` の `size` 乗になる
+struct UpdateComposite;
+
+impl ActedMonoid for UpdateComposite {
+    type Val = (Affine<M998>, usize);
+    type Act = Option<Affine<M998>>;
+    fn e() -> Self::Val {
+        (Affine::id_(), 0)
+    }
+    fn id() -> Self::Act {
+        None
+    }
+    fn op(x: &Self::Val, y: &Self::Val) -> Self::Val {
+        (y.0.compose(&x.0), x.1 + y.1)
+    }
+    fn mapping(x: &Self::Val, y: &Self::Act) -> Self::Val {
+        match y {
+            Some(f) => (f.pow(x.1), x.1),
+            None => *x,
+        }
+    }
+    fn compose(x: &Self::Act, y: &Self::Act) -> Self::Act {
+        y.or(*x)
+    }
+}
@@ -1,8 +1,11 @@
 #![allow(non_snake_case)]
 
-use cp_library_rs::get;
+use cp_library_rs::scan as get;
+use cp_library_rs::{io::output::Output, put};
 
 fn main() {
+    let mut out = Output::stdout();
+
     let (N, Q) = get!(usize, usize);
     let A = get!(usize;;);
     let LR = get!(usize, usize; Q);
@@ -14,6 +17,6 @@ fn main() {
     }
 
     for &(l, r) in &LR {
-        println!("{}", S[r] - S[l]);
+        put!(out; S[r] - S[l]);
     }
 }
@@ -2,9 +2,12 @@
 
 use cp_library_rs::{
     algebraic_structure::operation::Min, data_structure::sparse_table::SparseTable, get,
+    io::output::Output, put,
 };
 
 fn main() {
+    let mut out = Output::stdout();
+
     let (_N, Q) = get!(usize, usize);
     let A = get!(isize;;);
     let LR = get!(usize, usize; Q);
@@ -12,6 +15,6 @@ fn main() {
     let tb = SparseTable::<Min<_>>::build(&A);
 
     for &(l, r) in &LR {
-        println!("{}", tb.get_range(l..r));
+        put!(out; tb.get_range(l..r));
     }
 }
@@ -1,17 +1,13 @@
 #![allow(non_snake_case)]
 
-use cp_library_rs::{get, string::suffix_array::SuffixArray};
+use cp_library_rs::{get, io::output::Output, put, string::suffix_array::SuffixArray};
 
 fn main() {
+    let mut out = Output::stdout();
+
     let S = get!(String);
 
     let SA = SuffixArray::build(&S);
 
-    for i in 1..SA.len() {
-        if i == SA.len() - 1 {
-            println!("{}", SA[i]);
-        } else {
-            print!("{} ", SA[i]);
-        }
-    }
+    put!(out; join &SA[1..]);
 }
@@ -1,72 +1,75 @@
 #![allow(non_snake_case)]
 
-use std::{cmp::max, collections::VecDeque};
-
 use cp_library_rs::{
+    algebraic_structure::monoid_with_context::MonoidCtx,
     debug, get,
     graph::{
         dijkstra::{dijkstra, path_reconstruction},
-        dynamic_rerooting::Rerooting,
+        rerooting::{RerootingDP, TreeMonoid},
     },
-    utils::{consts::INF, iterutil::IterUtil},
 };
+use itertools::Itertools;
 
-fn main() {
-    let N = get!(usize);
-    let edges = get!(usize, usize, isize; N - 1);
+/// 各頂点から最も遠い頂点までの距離
+struct Farthest {
+    w: Vec<usize>,
+}
 
-    let mut rerooting = Rerooting::new(N, 0, |a, b| *a.max(b), |a, i| a + edges[i].2, |a, _| *a);
+impl MonoidCtx for Farthest {
+    type Val = usize;
+    fn e(&self) -> Self::Val {
+        0
+    }
+    fn op(&self, left: &Self::Val, right: &Self::Val) -> Self::Val {
+        *left.max(right)
+    }
+}
 
-    for &(u, v, w) in &edges {
-        rerooting.add_edge2(u, v);
+impl TreeMonoid for Farthest {
+    type T = usize;
+    fn put_edge(&self, x: &Self::T, i: usize) -> Self::Val {
+        *x + self.w[i]
+    }
+    fn put_vertex(&self, x: &Self::Val, _v: usize) -> Self::T {
+        *x
     }
+}
 
-    rerooting.build();
+fn main() {
+    let N = get!(usize);
+    let edges = get!(usize, usize, usize; N - 1);
+
+    // 全方位木DP
+    let mut dp = RerootingDP::new(
+        N,
+        Farthest {
+            w: edges.iter().map(|&(_, _, w)| w).collect(),
+        },
+    );
+    let mut G = vec![vec![]; N];
+
+    for (i, &(u, v, w)) in edges.iter().enumerate() {
+        dp.add_edge(u, v, i, i);
+        G[u].push((v, w));
+        G[v].push((u, w));
+    }
 
-    debug!(rerooting.dp);
-    debug!(rerooting.ans);
+    let ans = dp.build(0);
 
-    let diameter = rerooting.ans.iter().max().unwrap();
+    debug!(ans);
 
-    let (mut u, mut v) = (INF, INF);
+    // 直径の端点の1つ
+    let u = (0..N).max_by_key(|&v| ans[v]).unwrap();
+    let diameter = ans[u];
 
-    for i in 0..N {
-        if rerooting.ans[i] == *diameter {
-            if u == INF {
-                u = i;
-            } else {
-                v = i;
-            }
-        }
-    }
+    // u から最も遠い頂点がもう一方の端点
+    let (prev, dist) = dijkstra(&G, u);
+    let v = (0..N).max_by_key(|&v| dist[v]).unwrap();
 
-    assert_ne!(u, INF, "ans: {:?}", rerooting.ans);
-    assert_ne!(v, INF, "ans: {:?}", rerooting.ans);
+    assert_eq!(dist[v], diameter);
 
     debug!(u, v);
 
-    // u → v へのパスを構築
-    let prev = {
-        let mut prev = vec![None; N];
-        let mut seen = vec![false; N];
-        seen[u] = true;
-        let mut q = VecDeque::from([u]);
-        while let Some(u) = q.pop_front() {
-            seen[u] = true;
-            for e in &rerooting.G[u] {
-                let v = *e;
-                if seen[v] {
-                    continue;
-                }
-                prev[v] = Some(u);
-                q.push_back(v);
-            }
-        }
-        prev
-    };
-
-    debug!(prev, prev[u], prev[v]);
-
     let path = path_reconstruction(u, v, &prev).unwrap();
 
     // 出力
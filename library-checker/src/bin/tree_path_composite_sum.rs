@@ -1,37 +1,63 @@
 #![allow(non_snake_case)]
 
 use cp_library_rs::{
-    algebraic_structure::affine1d::{Affine, AffineTransform},
+    algebraic_structure::{affine1d::Affine, monoid_with_context::MonoidCtx},
     get,
-    graph::rerooting::{Rerooting, TreeMonoid},
+    graph::rerooting::{RerootingDP, TreeMonoid},
     number_theory::modint::M998,
 };
+use itertools::Itertools;
 
 fn main() {
     let N = get!(usize);
-    let _A = get!(M998;;);
+    let A = get!(M998;;);
     let UVBC = get!(usize, usize, M998, M998; N - 1);
 
     // 全方位木DP
-    let mut tree = Rerooting::<Composite>::new(N);
+    let mut dp = RerootingDP::new(
+        N,
+        Composite {
+            A,
+            F: UVBC.iter().map(|&(_, _, b, c)| (b, c)).collect(),
+        },
+    );
 
-    for &(u, v, b, c) in &UVBC {
-        tree.add_edge2(u, v, (b, c));
+    for (i, &(u, v, _, _)) in UVBC.iter().enumerate() {
+        dp.add_edge(u, v, i, i);
     }
+
+    let ans = dp.build(0);
+
+    println!("{}", ans.iter().map(|&(sum, _)| sum).join(" "));
 }
 
-struct Composite;
+/// 部分木の各頂点へのパスの合成の和
+struct Composite {
+    /// 頂点の値
+    A: Vec<M998>,
+    /// 辺の一次関数
+    F: Vec<Affine<M998>>,
+}
 
-impl TreeMonoid for Composite {
-    type T = Affine<M998>;
-    type W = Affine<M998>;
-    fn id() -> Self::T {
-        AffineTransform::id_()
+impl MonoidCtx for Composite {
+    /// (和, 頂点数)
+    type Val = (M998, usize);
+    fn e(&self) -> Self::Val {
+        (M998::new(0), 0)
     }
-    fn merge(x: &Self::T, y: &Self::T) -> Self::T {
-        x.compose(y)
+    fn op(&self, left: &Self::Val, right: &Self::Val) -> Self::Val {
+        (left.0 + right.0, left.1 + right.1)
+    }
+}
+
+impl TreeMonoid for Composite {
+    type T = (M998, usize);
+    fn put_edge(&self, x: &Self::T, i: usize) -> Self::Val {
+        // 部分木の各頂点について f(x) = bx + c を適用する
+        let (b, c) = self.F[i];
+        (b * x.0 + c * x.1, x.1)
     }
-    fn put_edge(x: &Self::T, weight: &Self::W) -> Self::T {
-        x.compose(weight)
+    fn put_vertex(&self, x: &Self::Val, v: usize) -> Self::T {
+        (x.0 + self.A[v], x.1 + 1)
     }
 }
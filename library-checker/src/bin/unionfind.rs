@@ -1,6 +1,10 @@
-use cp_library_rs::{data_structure::union_find::UnionFind, get};
+use cp_library_rs::data_structure::union_find::UnionFind;
+use cp_library_rs::scan as get;
+use cp_library_rs::{io::output::Output, put};
 
 fn main() {
+    let mut out = Output::stdout();
+
     let (n, q) = get!(usize, usize);
 
     let mut uf = UnionFind::new(n);
@@ -13,7 +17,7 @@ fn main() {
         } else {
             let res = uf.is_same(u, v);
 
-            println!("{}", res as usize);
+            put!(out; res as usize);
         }
     }
 }
@@ -2,7 +2,7 @@
 
 use cp_library_rs::{
     algebraic_structure::operation::Add, data_structure::segment_tree::SegmentTree, debug, debug2D,
-    get, graph::hld::HLD, utils::iterutil::IterUtil,
+    get, graph::hld::HLD,
 };
 
 fn main() {
@@ -32,7 +32,7 @@ fn main() {
         W[hld.get_id(i)] = A[i];
     }
 
-    let mut seg = SegmentTree::<Add<usize>>::from(W);
+    let mut seg = SegmentTree::<Add<usize>>::from_vec(W);
 
     for _ in 0..Q {
         let q = get!(usize;;);
@@ -45,8 +45,7 @@ fn main() {
         if let &[1, u, v] = &q[..] {
             let mut ans = 0;
 
-            #[cfg(debug_assertions)]
-            eprintln!("{}", hld.get_path(u, v).join_debug(" -> "));
+            debug!(hld.get_path(u, v).collect::<Vec<_>>());
 
             for (a, b, _, _) in hld.get_path(u, v) {
                 let l = hld.get_id(a);
@@ -9,7 +9,6 @@ use cp_library_rs::{
     debug, debug2D, get,
     graph::hld::HLD,
     number_theory::modint::M998,
-    utils::iterutil::IterUtil,
 };
 
 fn main() {
@@ -28,8 +27,8 @@ fn main() {
     debug2D!(hld.G);
     debug!(hld.in_);
 
-    let mut Wl = vec![Affine::id(); N];
-    let mut Wr = vec![Affine::id(); N];
+    let mut Wl = vec![Affine::<M998>::e(); N];
+    let mut Wr = vec![Affine::<M998>::e(); N];
 
     let rev = |i| N - i - 1;
 
@@ -41,8 +40,8 @@ fn main() {
     debug!(Wl);
     debug!(Wr);
 
-    let mut segl = SegmentTree::<Affine<M998>>::from(Wl);
-    let mut segr = SegmentTree::<Affine<M998>>::from(Wr);
+    let mut segl = SegmentTree::<Affine<M998>>::from_vec(Wl);
+    let mut segr = SegmentTree::<Affine<M998>>::from_vec(Wr);
 
     for _ in 0..Q {
         let q = get!(usize, usize, usize, usize);
@@ -55,12 +54,11 @@ fn main() {
                 *segr.get_mut(b).unwrap() = (c.into(), d.into());
             }
             (1, u, v, x) => {
-                let mut resl = Affine::id();
-                let mut resr = Affine::id();
+                let mut resl = Affine::<M998>::e();
+                let mut resr = Affine::<M998>::e();
 
                 debug!(u, v);
-                #[cfg(debug_assertions)]
-                eprintln!("{}", hld.get_path(u, v).join_debug(" -> "));
+                debug!(hld.get_path(u, v).collect::<Vec<_>>());
 
                 for (a, b, _, r) in hld.get_path(u, v) {
                     if r {
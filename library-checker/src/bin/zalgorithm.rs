@@ -1,3 +1,13 @@
 #![allow(non_snake_case)]
 
-fn main() {}
+use cp_library_rs::{get, io::output::Output, put, string::z::z_algorithm};
+
+fn main() {
+    let mut out = Output::stdout();
+
+    let S = get!(chars);
+
+    let Z = z_algorithm(&S);
+
+    put!(out; join &Z);
+}
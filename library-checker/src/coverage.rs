@@ -0,0 +1,219 @@
+//! ライブラリの各モジュールを検証している bin の一覧
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write,
+    fs,
+    path::Path,
+};
+
+use anyhow::{anyhow, Context, Result};
+use expander::{
+    expander::ModuleExpander, manifest::BinTarget, module_path::ModulePath, parser::get_deps,
+};
+
+/// モジュールの検証状況
+#[derive(Debug, Default)]
+pub struct Coverage {
+    /// モジュール -> それを直接 `use` している bin
+    pub modules: BTreeMap<ModulePath, BTreeSet<String>>,
+    /// モジュール -> それを他のモジュールを経由して取り込んでいる bin
+    pub reached: BTreeMap<ModulePath, BTreeSet<String>>,
+}
+
+impl Coverage {
+    /// `library` 以下のモジュールと，それぞれを使っている bin を求める
+    ///
+    /// - bin が直接 `use` するモジュールだけを検証済みとみなす
+    /// - それらが依存するモジュール（マクロの展開先を含む）は `reached` に分けて記録する
+    pub fn new(library: &Path, bins: &[BinTarget]) -> Result<Self> {
+        let modules: BTreeMap<ModulePath, BTreeSet<String>> =
+            library_modules(&library.join("src"))?
+                .into_iter()
+                .map(|module| (module, BTreeSet::new()))
+                .collect();
+        let mut coverage = Self {
+            reached: modules.clone(),
+            modules,
+        };
+
+        for bin in bins {
+            let (direct, reached) = dependencies(library, bin)?;
+            for dep in reached {
+                let map = if direct.contains(&dep) {
+                    &mut coverage.modules
+                } else {
+                    &mut coverage.reached
+                };
+                map.entry(dep).or_default().insert(bin.name.clone());
+            }
+        }
+
+        Ok(coverage)
+    }
+
+    /// 検証済み・失敗・間接的に使用・未検証のモジュールの一覧
+    ///
+    /// `results` は bin ごとの実行結果（すべて AC なら `true`）
+    pub fn report(&self, results: &BTreeMap<String, bool>) -> String {
+        let mut verified = vec![];
+        let mut failing = vec![];
+        let mut reached = vec![];
+        let mut unverified = vec![];
+
+        for (module, bins) in &self.modules {
+            let name = module.qualified_name();
+            let failed: Vec<&str> = bins
+                .iter()
+                .filter(|bin| results.get(*bin) == Some(&false))
+                .map(String::as_str)
+                .collect();
+            if bins.is_empty() {
+                match self.reached.get(module) {
+                    Some(bins) if !bins.is_empty() => {
+                        let bins: Vec<&str> = bins.iter().map(String::as_str).collect();
+                        reached.push(format!("{name}: {}", bins.join(", ")));
+                    }
+                    _ => unverified.push(name),
+                }
+            } else if !failed.is_empty() {
+                failing.push(format!("{name}: {}", failed.join(", ")));
+            } else {
+                let bins: Vec<&str> = bins.iter().map(String::as_str).collect();
+                verified.push(format!("{name}: {}", bins.join(", ")));
+            }
+        }
+
+        let mut res = String::new();
+        for (title, items) in [
+            ("Verified", verified),
+            ("Failing", failing),
+            ("Reached", reached),
+            ("Unverified", unverified),
+        ] {
+            writeln!(&mut res, "## {title} ({})", items.len()).unwrap();
+            writeln!(&mut res).unwrap();
+            for item in items {
+                writeln!(&mut res, "- {item}").unwrap();
+            }
+            writeln!(&mut res).unwrap();
+        }
+        res
+    }
+}
+
+/// bin が直接 `use` するモジュールと，推移的に依存するモジュール
+fn dependencies(
+    library: &Path,
+    bin: &BinTarget,
+) -> Result<(BTreeSet<ModulePath>, BTreeSet<ModulePath>)> {
+    let source =
+        fs::read_to_string(&bin.path).with_context(|| format!("failed to read {:?}", bin.path))?;
+    let mut expander =
+        ModuleExpander::new(bin.path.clone(), library.to_path_buf()).map_err(|err| anyhow!(err))?;
+    expander
+        .solve_dependancies()
+        .with_context(|| format!("failed to solve dependencies of {}", bin.name))?;
+    Ok((
+        get_deps(&source, &expander.import_name),
+        expander.dependancies.unwrap_or_default(),
+    ))
+}
+
+/// ライブラリのモジュールの一覧
+///
+/// - `<category>/<file>.rs`（`mod.rs` を除く）
+/// - ライブラリ直下のマクロ（`lib.rs` を除く）
+fn library_modules(library_src: &Path) -> Result<BTreeSet<ModulePath>> {
+    let mut modules = BTreeSet::new();
+
+    for entry in
+        fs::read_dir(library_src).with_context(|| format!("failed to read {:?}", library_src))?
+    {
+        let path = entry?.path();
+        let name = path.file_stem().unwrap().to_string_lossy().to_string();
+
+        if path.is_dir() {
+            for entry in fs::read_dir(&path)? {
+                let file = entry?.path();
+                if file.extension().is_none_or(|ext| ext != "rs") {
+                    continue;
+                }
+                let file = file.file_stem().unwrap().to_string_lossy().to_string();
+                if file != "mod" {
+                    modules.insert(ModulePath::Module {
+                        category: name.clone(),
+                        file,
+                    });
+                }
+            }
+        } else if path.extension().is_some_and(|ext| ext == "rs") && name != "lib" {
+            modules.insert(ModulePath::Macro { file: name });
+        }
+    }
+
+    Ok(modules)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, path::PathBuf};
+
+    use super::*;
+
+    fn module(category: &str, file: &str) -> ModulePath {
+        ModulePath::Module {
+            category: category.to_string(),
+            file: file.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_direct_dependencies() {
+        let dir = env::temp_dir().join(format!("coverage_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("scan_bin.rs");
+        fs::write(
+            &path,
+            "use cp_library_rs::scan;\n\nfn main() {\n    let n = scan!(usize);\n    println!(\"{n}\");\n}\n",
+        )
+        .unwrap();
+        let bin = BinTarget {
+            name: "scan_bin".to_string(),
+            alias: None,
+            path,
+        };
+
+        let coverage = Coverage::new(&PathBuf::from("../cp-library-rs"), &[bin]).unwrap();
+        let bins =
+            |module: &ModulePath| coverage.modules[module].iter().cloned().collect::<Vec<_>>();
+
+        // 直接 `use` しているマクロ
+        assert_eq!(
+            bins(&ModulePath::Macro {
+                file: "scan".to_string()
+            }),
+            vec!["scan_bin"]
+        );
+        // マクロの展開先は検証済みとみなさない
+        assert!(bins(&module("io", "scanner")).is_empty());
+        assert_eq!(
+            coverage.reached[&module("io", "scanner")]
+                .iter()
+                .collect::<Vec<_>>(),
+            vec!["scan_bin"]
+        );
+        // 使っていないモジュール
+        assert!(bins(&module("io", "interactive")).is_empty());
+        assert!(coverage.reached[&module("io", "interactive")].is_empty());
+
+        let report = coverage.report(&BTreeMap::from([("scan_bin".to_string(), true)]));
+        let (verified, rest) = report.split_once("## Reached").unwrap();
+        let (reached, unverified) = rest.split_once("## Unverified").unwrap();
+        assert!(verified.contains("- scan: scan_bin\n"));
+        assert!(reached.contains("- io::scanner: scan_bin\n"));
+        assert!(unverified.contains("- io::interactive\n"));
+
+        fs::remove_dir_all(dir).ok();
+    }
+}
@@ -0,0 +1,274 @@
+//! bin の実行と出力の判定
+
+use std::{
+    fmt,
+    io::{Read, Write},
+    path::Path,
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+
+/// 出力の比較方法
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Match {
+    /// 完全一致
+    Exact,
+    /// 空白で区切ったトークンごとに一致
+    Token,
+    /// トークンごとに，数値なら絶対誤差または相対誤差が許容範囲内
+    Float { abs_error: f64, rel_error: f64 },
+}
+
+/// コマンドライン引数での比較方法
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum MatchKind {
+    Exact,
+    Token,
+    Float,
+}
+
+impl Match {
+    /// cargo-compete の `match` の値を読む
+    ///
+    /// - `Exact`
+    /// - `SplitWhitespace`，`Lines`
+    /// - `Float: relative_error: 1e-6 absolute_error: 1e-6`
+    pub fn parse(value: &str) -> Result<Self> {
+        let mut tokens = value.split_whitespace();
+        let Some(kind) = tokens.next() else {
+            bail!("empty match");
+        };
+        match kind.trim_end_matches(':') {
+            "Exact" => Ok(Match::Exact),
+            "SplitWhitespace" | "Lines" => Ok(Match::Token),
+            "Float" => {
+                let (mut abs_error, mut rel_error) = (0.0, 0.0);
+                let tokens: Vec<&str> = tokens.collect();
+                for pair in tokens.chunks(2) {
+                    let [key, value] = pair else {
+                        bail!("invalid float match: {value}");
+                    };
+                    let value: f64 = value.parse()?;
+                    match key.trim_end_matches(':') {
+                        "absolute_error" => abs_error = value,
+                        "relative_error" => rel_error = value,
+                        key => bail!("unknown key: {key}"),
+                    }
+                }
+                Ok(Match::Float {
+                    abs_error,
+                    rel_error,
+                })
+            }
+            kind => bail!("unsupported match: {kind}"),
+        }
+    }
+
+    /// 出力 `actual` が期待する出力 `expected` と一致するか
+    pub fn judge(&self, actual: &str, expected: &str) -> bool {
+        match *self {
+            Match::Exact => actual == expected,
+            Match::Token => actual.split_whitespace().eq(expected.split_whitespace()),
+            Match::Float {
+                abs_error,
+                rel_error,
+            } => {
+                let actual: Vec<&str> = actual.split_whitespace().collect();
+                let expected: Vec<&str> = expected.split_whitespace().collect();
+                actual.len() == expected.len()
+                    && actual.iter().zip(&expected).all(|(a, b)| {
+                        match (a.parse::<f64>(), b.parse::<f64>()) {
+                            (Ok(a), Ok(b)) => {
+                                let diff = (a - b).abs();
+                                diff <= abs_error || diff <= rel_error * b.abs()
+                            }
+                            _ => a == b,
+                        }
+                    })
+            }
+        }
+    }
+}
+
+/// 判定結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Accepted,
+    WrongAnswer,
+    RuntimeError,
+    TimeLimitExceeded,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Verdict::Accepted => "AC",
+            Verdict::WrongAnswer => "WA",
+            Verdict::RuntimeError => "RE",
+            Verdict::TimeLimitExceeded => "TLE",
+        };
+        f.pad(s)
+    }
+}
+
+/// 実行結果
+#[derive(Debug)]
+pub struct Execution {
+    pub status: Verdict,
+    pub stdout: String,
+    pub stderr: String,
+    pub time: Duration,
+}
+
+/// `input` を標準入力に与えて `program` を実行する（`timelimit` を超えたら kill する）
+pub fn run(program: &Path, input: &str, timelimit: Duration) -> Result<Execution> {
+    let start = Instant::now();
+    let mut child = Command::new(program)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("failed to run {:?}", program))?;
+
+    // パイプが詰まらないよう，入出力は別スレッドで行う
+    let mut stdin = child.stdin.take().unwrap();
+    let input = input.to_string();
+    let writer = thread::spawn(move || {
+        // 入力を読み切らずに終了した場合の BrokenPipe は無視する
+        let _ = stdin.write_all(input.as_bytes());
+    });
+    let stdout = read_to_end(child.stdout.take().unwrap());
+    let stderr = read_to_end(child.stderr.take().unwrap());
+
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break Some(status);
+        }
+        if start.elapsed() > timelimit {
+            child.kill()?;
+            child.wait()?;
+            break None;
+        }
+        thread::sleep(Duration::from_millis(1));
+    };
+    let time = start.elapsed();
+
+    let _ = writer.join();
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+
+    let status = match status {
+        None => Verdict::TimeLimitExceeded,
+        Some(status) if !status.success() => Verdict::RuntimeError,
+        Some(_) => Verdict::Accepted,
+    };
+
+    Ok(Execution {
+        status,
+        stdout,
+        stderr,
+        time,
+    })
+}
+
+fn read_to_end(mut reader: impl Read + Send + 'static) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut buf = vec![];
+        let _ = reader.read_to_end(&mut buf);
+        String::from_utf8_lossy(&buf).to_string()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Match::parse("Exact").unwrap(), Match::Exact);
+        assert_eq!(Match::parse("SplitWhitespace").unwrap(), Match::Token);
+        assert_eq!(Match::parse("Lines").unwrap(), Match::Token);
+        assert_eq!(
+            Match::parse("Float: relative_error: 1e-6 absolute_error: 1e-9").unwrap(),
+            Match::Float {
+                abs_error: 1e-9,
+                rel_error: 1e-6,
+            }
+        );
+        assert_eq!(
+            Match::parse("Float absolute_error: 0.5").unwrap(),
+            Match::Float {
+                abs_error: 0.5,
+                rel_error: 0.0,
+            }
+        );
+
+        assert!(Match::parse("").is_err());
+        assert!(Match::parse("Regex").is_err());
+        assert!(Match::parse("Float: relative_error:").is_err());
+        assert!(Match::parse("Float: relative_error: abc").is_err());
+        assert!(Match::parse("Float: tolerance: 1e-6").is_err());
+    }
+
+    #[test]
+    fn test_judge_exact() {
+        let m = Match::Exact;
+        assert!(m.judge("1 2\n3\n", "1 2\n3\n"));
+        assert!(!m.judge("1 2\n3", "1 2\n3\n"));
+        assert!(!m.judge("1 2 \n3\n", "1 2\n3\n"));
+        assert!(!m.judge("1 2\r\n3\r\n", "1 2\n3\n"));
+    }
+
+    #[test]
+    fn test_judge_token() {
+        let m = Match::Token;
+        assert!(m.judge("1 2\n3\n", "1 2\n3\n"));
+        assert!(m.judge("1  2 \n3", "1 2\n3\n"));
+        assert!(m.judge("1 2\r\n3\r\n", "1 2\n3\n"));
+        assert!(m.judge("1\n2\n3\n", "1 2 3\n"));
+        assert!(!m.judge("1 2\n", "1 2\n3\n"));
+        assert!(!m.judge("12\n3\n", "1 2\n3\n"));
+    }
+
+    #[test]
+    fn test_judge_float_absolute() {
+        let m = Match::Float {
+            abs_error: 1e-6,
+            rel_error: 0.0,
+        };
+        assert!(m.judge("0.3333333\n", "0.33333333333\n"));
+        assert!(m.judge("1000000.0000005\n", "1000000\n"));
+        assert!(!m.judge("0.333\n", "0.33333333333\n"));
+        assert!(!m.judge("1000000.1\n", "1000000\n"));
+    }
+
+    #[test]
+    fn test_judge_float_relative() {
+        let m = Match::Float {
+            abs_error: 0.0,
+            rel_error: 1e-6,
+        };
+        assert!(m.judge("1000000.1\n", "1000000\n"));
+        assert!(m.judge("-1000000.1\n", "-1000000\n"));
+        assert!(!m.judge("1000002\n", "1000000\n"));
+        assert!(!m.judge("0.0000001\n", "0\n"));
+    }
+
+    #[test]
+    fn test_judge_float_tokens() {
+        let m = Match::Float {
+            abs_error: 1e-6,
+            rel_error: 1e-6,
+        };
+        // 数値でないトークンは完全一致
+        assert!(m.judge("Yes 1.0000000001\r\n", "Yes 1\n"));
+        assert!(!m.judge("yes 1\n", "Yes 1\n"));
+        // トークンの個数が違う
+        assert!(!m.judge("1 2\n", "1\n"));
+        assert!(!m.judge("1\n", "1 2\n"));
+    }
+}
@@ -0,0 +1,212 @@
+//! ローカルのテストケースで bin を検証する
+//!
+//! `[package.metadata.cargo-compete.bin]` の各 bin をビルドし，`testcases` 以下のテストケースを与えて
+//! 出力を比較する
+//!
+//! `--coverage` を指定すると，ライブラリの各モジュールを検証している bin の一覧も出力する
+
+mod coverage;
+mod judge;
+mod testcase;
+
+use std::{
+    collections::BTreeMap,
+    env,
+    path::{Path, PathBuf},
+    process::{exit, Command},
+    time::Duration,
+};
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use expander::{
+    cargo_lock::resolve_library_path,
+    manifest::{bin_targets, BinTarget},
+};
+
+use coverage::Coverage;
+use judge::{run, Match, MatchKind, Verdict};
+
+#[derive(Parser, Debug)]
+#[command(about, long_about = None)]
+struct Args {
+    /// bins to verify (all bins if empty)
+    bins: Vec<String>,
+    /// directory containing `<bin>.yml` and `<bin>/*.in`, `<bin>/*.out`
+    #[arg(long)]
+    testcases: Option<PathBuf>,
+    /// how to compare outputs (defaults to `match` in `<bin>.yml`, or exact)
+    #[arg(long = "match", value_enum)]
+    matching: Option<MatchKind>,
+    /// absolute error allowed with `--match float`
+    #[arg(long, default_value_t = 1e-9)]
+    abs_error: f64,
+    /// relative error allowed with `--match float`
+    #[arg(long, default_value_t = 1e-9)]
+    rel_error: f64,
+    /// time limit per case in seconds (overrides `timelimit` in `<bin>.yml`)
+    #[arg(long)]
+    timeout: Option<f64>,
+    /// print verified, failing and unverified library modules after running all bins
+    #[arg(long, conflicts_with = "bins")]
+    coverage: bool,
+}
+
+/// 検証するライブラリのクレート名
+const LIBRARY_NAME: &str = "cp-library-rs";
+
+/// `timelimit` がない場合の制限時間
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn main() {
+    let args = Args::parse();
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let testcases = args
+        .testcases
+        .clone()
+        .unwrap_or_else(|| root.join("testcases"));
+
+    let bins = match select_bins(root, &args.bins) {
+        Ok(bins) => bins,
+        Err(err) => {
+            eprintln!("error: {err}");
+            exit(1);
+        }
+    };
+
+    let mut results = BTreeMap::new();
+    for bin in &bins {
+        let passed = verify(&args, root, &testcases, bin).unwrap_or_else(|err| {
+            eprintln!("{}: error: {err:#}", bin.name);
+            false
+        });
+        results.insert(bin.name.clone(), passed);
+    }
+    let failed: Vec<&str> = results
+        .iter()
+        .filter(|(_, &passed)| !passed)
+        .map(|(name, _)| name.as_str())
+        .collect();
+
+    if args.coverage {
+        match coverage(root, &bins) {
+            Ok(coverage) => {
+                println!();
+                print!("{}", coverage.report(&results));
+            }
+            Err(err) => {
+                eprintln!("error: {err:#}");
+                exit(1);
+            }
+        }
+    }
+
+    println!();
+    println!("{}/{} bins passed", bins.len() - failed.len(), bins.len());
+    if !failed.is_empty() {
+        println!("failed: {}", failed.join(", "));
+        exit(1);
+    }
+}
+
+/// `[package.metadata.cargo-compete.bin]` の bin のうち，検証するもの
+fn select_bins(root: &Path, names: &[String]) -> Result<Vec<BinTarget>> {
+    let bins = bin_targets(&root.join("Cargo.toml"))?;
+    if names.is_empty() {
+        return Ok(bins);
+    }
+    names
+        .iter()
+        .map(|name| match bins.iter().find(|bin| bin.matches(name)) {
+            Some(bin) => Ok(bin.clone()),
+            None => bail!("bin not found: {name}"),
+        })
+        .collect()
+}
+
+/// `Cargo.lock` からライブラリを探し，各モジュールを使っている bin を求める
+fn coverage(root: &Path, bins: &[BinTarget]) -> Result<Coverage> {
+    let library = resolve_library_path(&root.join("Cargo.toml"), LIBRARY_NAME)?;
+    Coverage::new(&library, bins)
+}
+
+/// bin をビルドし，すべてのテストケースで AC なら `true` を返す
+fn verify(args: &Args, root: &Path, testcases: &Path, bin: &BinTarget) -> Result<bool> {
+    let suite = testcase::load(testcases, &bin.name)?;
+    if suite.cases.is_empty() {
+        bail!("no test cases in {:?}", testcases);
+    }
+
+    println!("{}:", bin.name);
+
+    let program = match build(root, &bin.name)? {
+        Some(program) => program,
+        None => {
+            println!("  CE");
+            return Ok(false);
+        }
+    };
+
+    let matching = match args.matching {
+        Some(MatchKind::Exact) => Match::Exact,
+        Some(MatchKind::Token) => Match::Token,
+        Some(MatchKind::Float) => Match::Float {
+            abs_error: args.abs_error,
+            rel_error: args.rel_error,
+        },
+        None => suite.matching.unwrap_or(Match::Exact),
+    };
+    let timelimit = args
+        .timeout
+        .map(Duration::from_secs_f64)
+        .or(suite.timelimit)
+        .unwrap_or(DEFAULT_TIMEOUT);
+
+    let mut passed = 0;
+    for case in &suite.cases {
+        let execution = run(&program, &case.input, timelimit)?;
+        let verdict = match execution.status {
+            Verdict::Accepted if !matching.judge(&execution.stdout, &case.output) => {
+                Verdict::WrongAnswer
+            }
+            status => status,
+        };
+        println!(
+            "  {:<3} {:>6} ms  {}",
+            verdict,
+            execution.time.as_millis(),
+            case.name
+        );
+        if verdict == Verdict::Accepted {
+            passed += 1;
+        } else if verdict == Verdict::RuntimeError && !execution.stderr.is_empty() {
+            for line in execution.stderr.lines() {
+                println!("        {line}");
+            }
+        }
+    }
+
+    println!("  {passed}/{} passed", suite.cases.len());
+    Ok(passed == suite.cases.len())
+}
+
+/// `cargo build --release --bin <name>` を実行し，実行ファイルのパスを返す（失敗したら `None`）
+fn build(root: &Path, name: &str) -> Result<Option<PathBuf>> {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let status = Command::new(cargo)
+        .args(["build", "--quiet", "--release", "--bin", name])
+        .current_dir(root)
+        .status()?;
+    if !status.success() {
+        return Ok(None);
+    }
+
+    let target_dir = env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| root.join("target"));
+    let program = target_dir
+        .join("release")
+        .join(format!("{name}{}", env::consts::EXE_SUFFIX));
+    Ok(Some(program))
+}
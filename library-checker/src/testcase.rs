@@ -0,0 +1,412 @@
+//! テストケースの読み込み
+//!
+//! - `testcases/<bin>.yml` の `cases`（cargo-compete の形式）
+//! - `testcases/<bin>.yml` の `extend` にある `Text` 形式のディレクトリ（`in/*.txt`，`out/*.txt`）
+//! - `testcases/<bin>/*.in` と同名の `*.out`
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use anyhow::{bail, Context, Result};
+
+use crate::judge::Match;
+
+/// テストケース
+#[derive(Debug, Clone)]
+pub struct TestCase {
+    pub name: String,
+    pub input: String,
+    pub output: String,
+}
+
+/// 1つの bin のテストケースと判定方法
+#[derive(Debug, Default)]
+pub struct TestSuite {
+    pub cases: Vec<TestCase>,
+    /// 出力の比較方法（`match`）
+    pub matching: Option<Match>,
+    /// 実行時間制限（`timelimit`）
+    pub timelimit: Option<Duration>,
+}
+
+/// `dir` から bin `name` のテストケースを読み込む
+pub fn load(dir: &Path, name: &str) -> Result<TestSuite> {
+    let mut suite = TestSuite::default();
+
+    let yml = dir.join(format!("{name}.yml"));
+    if yml.exists() {
+        let contents =
+            fs::read_to_string(&yml).with_context(|| format!("failed to read {:?}", yml))?;
+        parse_yml(&contents, dir, &mut suite)
+            .with_context(|| format!("failed to parse {:?}", yml))?;
+    }
+
+    suite
+        .cases
+        .extend(load_pairs(&dir.join(name), "in", "out")?);
+
+    Ok(suite)
+}
+
+/// `dir` にある `*.{input_ext}` と，同名の `*.{output_ext}` の組
+fn load_pairs(dir: &Path, input_ext: &str, output_ext: &str) -> Result<Vec<TestCase>> {
+    load_dirs(dir, dir, input_ext, output_ext)
+}
+
+/// `input_dir` にある `*.{input_ext}` と，`output_dir` にある同名の `*.{output_ext}` の組（名前順）
+fn load_dirs(
+    input_dir: &Path,
+    output_dir: &Path,
+    input_ext: &str,
+    output_ext: &str,
+) -> Result<Vec<TestCase>> {
+    if !input_dir.is_dir() {
+        return Ok(vec![]);
+    }
+
+    let mut inputs: Vec<PathBuf> = fs::read_dir(input_dir)
+        .with_context(|| format!("failed to read {:?}", input_dir))?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<_, _>>()?;
+    inputs.retain(|path| path.extension().is_some_and(|ext| ext == input_ext));
+    inputs.sort();
+
+    let mut cases = vec![];
+    for input in inputs {
+        let stem = input.file_stem().unwrap().to_string_lossy().to_string();
+        let output = output_dir.join(format!("{stem}.{output_ext}"));
+        if !output.exists() {
+            bail!("expected output not found: {:?}", output);
+        }
+        cases.push(TestCase {
+            name: stem,
+            input: read_text(&input)?,
+            output: read_text(&output)?,
+        });
+    }
+
+    Ok(cases)
+}
+
+/// ファイルを読む（改行は `\n` にそろえる）
+fn read_text(path: &Path) -> Result<String> {
+    let text = fs::read_to_string(path).with_context(|| format!("failed to read {:?}", path))?;
+    Ok(text.replace("\r\n", "\n"))
+}
+
+/// cargo-compete のテストケースファイルを読む
+///
+/// ```yaml
+/// timelimit: 5s
+/// match: Exact
+///
+/// cases:
+///   - in: |
+///       1 2
+///     out: |
+///       3
+///
+/// extend:
+///   - type: Text
+///     path: "./aplusb"
+///     in: /in/*.txt
+///     out: /out/*.txt
+/// ```
+fn parse_yml(contents: &str, dir: &Path, suite: &mut TestSuite) -> Result<()> {
+    let lines: Vec<&str> = contents.lines().collect();
+    // 現在のトップレベルのキー
+    let mut section = "";
+    // `extend` の要素（path, in, out）
+    let mut extend: Vec<[Option<String>; 3]> = vec![];
+
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        let trimmed = line.trim();
+        i += 1;
+
+        if trimmed.is_empty() || trimmed == "---" || trimmed.starts_with('#') {
+            continue;
+        }
+
+        // トップレベルのキー
+        if !line.starts_with(' ') {
+            let Some((key, value)) = trimmed.split_once(':') else {
+                bail!("invalid line: {line}");
+            };
+            section = key;
+            let value = value.trim();
+            match key {
+                "timelimit" => suite.timelimit = parse_duration(value)?,
+                "match" if !value.is_empty() => suite.matching = Some(Match::parse(value)?),
+                "match" => {
+                    // `Float:` の下に誤差が書かれる
+                    let mut body = vec![];
+                    while i < lines.len() && lines[i].starts_with(' ') {
+                        body.push(lines[i].trim());
+                        i += 1;
+                    }
+                    suite.matching = Some(Match::parse(&body.join(" "))?);
+                }
+                _ => (),
+            }
+            continue;
+        }
+
+        // リストの要素
+        let (item_start, trimmed) = match trimmed.strip_prefix("- ") {
+            Some(rest) => (true, rest.trim()),
+            None => (false, trimmed),
+        };
+        let Some((key, value)) = trimmed.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+
+        match section {
+            "cases" => {
+                if item_start {
+                    suite.cases.push(TestCase {
+                        name: format!("sample_{}", suite.cases.len() + 1),
+                        input: String::new(),
+                        output: String::new(),
+                    });
+                }
+                let Some(case) = suite.cases.last_mut() else {
+                    bail!("invalid case: {line}");
+                };
+                let text = if value == "|" {
+                    read_block(&lines, &mut i)
+                } else {
+                    unquote(value).to_string()
+                };
+                match key {
+                    "name" => case.name = text,
+                    "in" => case.input = text,
+                    "out" => case.output = text,
+                    _ => (),
+                }
+            }
+            "extend" => {
+                if item_start {
+                    extend.push([None, None, None]);
+                }
+                let Some(item) = extend.last_mut() else {
+                    bail!("invalid extend: {line}");
+                };
+                let index = match key {
+                    "path" => 0,
+                    "in" => 1,
+                    "out" => 2,
+                    _ => continue,
+                };
+                item[index] = Some(unquote(value).to_string());
+            }
+            _ => (),
+        }
+    }
+
+    for [path, input, output] in extend {
+        let (Some(path), Some(input), Some(output)) = (path, input, output) else {
+            continue;
+        };
+        let base = dir.join(path);
+        let (input_dir, input_ext) = split_glob(&input)?;
+        let (output_dir, output_ext) = split_glob(&output)?;
+        suite.cases.extend(load_dirs(
+            &base.join(input_dir),
+            &base.join(output_dir),
+            input_ext,
+            output_ext,
+        )?);
+    }
+
+    Ok(())
+}
+
+/// `|` で始まるブロックの文字列を読む（末尾の改行は1つだけ残す）
+fn read_block(lines: &[&str], i: &mut usize) -> String {
+    let mut indent = None;
+    let mut res = String::new();
+
+    while *i < lines.len() {
+        let line = lines[*i];
+        let width = line.len() - line.trim_start().len();
+        if !line.trim().is_empty() {
+            if width < indent.unwrap_or(0) || indent.is_none() && line.trim_start().contains(": ") {
+                break;
+            }
+            indent.get_or_insert(width);
+        }
+        res += line.get(indent.unwrap_or(0)..).unwrap_or("");
+        res += "\n";
+        *i += 1;
+    }
+
+    let len = res.trim_end_matches('\n').len();
+    res.truncate(len);
+    res + "\n"
+}
+
+/// `/in/*.txt` -> (`in`, `txt`)
+fn split_glob(glob: &str) -> Result<(&str, &str)> {
+    let glob = glob.trim_start_matches('/');
+    match glob.rsplit_once("/*.") {
+        Some((dir, ext)) => Ok((dir, ext)),
+        None => bail!("unsupported glob: {glob}"),
+    }
+}
+
+fn unquote(value: &str) -> &str {
+    value.trim_matches(|c| c == '"' || c == '\'')
+}
+
+/// `~`，`2s`，`500ms`
+fn parse_duration(value: &str) -> Result<Option<Duration>> {
+    let value = unquote(value);
+    if value == "~" || value.is_empty() {
+        return Ok(None);
+    }
+    let duration = if let Some(ms) = value.strip_suffix("ms") {
+        Duration::from_millis(ms.trim().parse()?)
+    } else if let Some(s) = value.strip_suffix('s') {
+        Duration::from_secs_f64(s.trim().parse()?)
+    } else {
+        bail!("invalid timelimit: {value}");
+    };
+    Ok(Some(duration))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    /// テスト用の一時ディレクトリ
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("testcase_{}_{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_parse_yml() {
+        let dir = temp_dir("yml");
+        fs::create_dir_all(dir.join("aplusb/in")).unwrap();
+        fs::create_dir_all(dir.join("aplusb/out")).unwrap();
+        fs::write(dir.join("aplusb/in/large.txt"), "100 200\n").unwrap();
+        fs::write(dir.join("aplusb/out/large.txt"), "300\n").unwrap();
+
+        let yml = r#"---
+timelimit: 2s
+match:
+  Float:
+    relative_error: 1e-6
+    absolute_error: 1e-6
+
+cases:
+  - name: sample
+    in: |
+      1 2
+      3 4
+    out: |
+      3
+
+  - in: "5 6"
+    out: "11"
+
+extend:
+  - type: Text
+    path: "./aplusb"
+    in: /in/*.txt
+    out: /out/*.txt
+"#;
+        let mut suite = TestSuite::default();
+        parse_yml(yml, &dir, &mut suite).unwrap();
+
+        assert_eq!(suite.timelimit, Some(Duration::from_secs(2)));
+        assert_eq!(
+            suite.matching,
+            Some(Match::Float {
+                abs_error: 1e-6,
+                rel_error: 1e-6,
+            })
+        );
+        let cases: Vec<_> = suite
+            .cases
+            .iter()
+            .map(|case| (&case.name[..], &case.input[..], &case.output[..]))
+            .collect();
+        assert_eq!(
+            cases,
+            vec![
+                ("sample", "1 2\n3 4\n", "3\n"),
+                ("sample_2", "5 6", "11"),
+                ("large", "100 200\n", "300\n"),
+            ]
+        );
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_parse_yml_inline() {
+        let yml = "timelimit: 500ms\nmatch: SplitWhitespace\r\n\r\ncases:\r\n  - in: |\r\n      1 2  \r\n    out: |\r\n      3\r\n";
+        let mut suite = TestSuite::default();
+        parse_yml(yml, Path::new("."), &mut suite).unwrap();
+
+        assert_eq!(suite.timelimit, Some(Duration::from_millis(500)));
+        assert_eq!(suite.matching, Some(Match::Token));
+        assert_eq!(suite.cases.len(), 1);
+        // 行末の空白は残し，改行は `\n` にそろえる
+        assert_eq!(suite.cases[0].input, "1 2  \n");
+        assert_eq!(suite.cases[0].output, "3\n");
+
+        let mut suite = TestSuite::default();
+        assert!(parse_yml("timelimit: 2 seconds\n", Path::new("."), &mut suite).is_err());
+        assert!(parse_yml("match: Regex\n", Path::new("."), &mut suite).is_err());
+    }
+
+    #[test]
+    fn test_load_pairs() {
+        let dir = temp_dir("pairs");
+        let cases = dir.join("aplusb");
+        fs::create_dir_all(&cases).unwrap();
+        fs::write(cases.join("b.in"), "1 2\r\n").unwrap();
+        fs::write(cases.join("b.out"), "3\r\n").unwrap();
+        fs::write(cases.join("a.in"), "1 1 \n\n").unwrap();
+        fs::write(cases.join("a.out"), "2 \n").unwrap();
+        fs::write(cases.join("memo.txt"), "").unwrap();
+
+        let suite = load(&dir, "aplusb").unwrap();
+        assert_eq!(suite.matching, None);
+        assert_eq!(suite.timelimit, None);
+        let cases: Vec<_> = suite
+            .cases
+            .iter()
+            .map(|case| (&case.name[..], &case.input[..], &case.output[..]))
+            .collect();
+        // 名前順，行末の空白はそのまま，CRLF は LF にそろえる
+        assert_eq!(
+            cases,
+            vec![("a", "1 1 \n\n", "2 \n"), ("b", "1 2\n", "3\n")]
+        );
+        assert!(Match::Exact.judge("3\n", &suite.cases[1].output));
+        assert!(!Match::Exact.judge("2\n", &suite.cases[0].output));
+        assert!(Match::Token.judge("2\n", &suite.cases[0].output));
+
+        // 存在しない bin
+        assert!(load(&dir, "unknown").unwrap().cases.is_empty());
+
+        // 対応する出力がない
+        fs::write(dir.join("aplusb/c.in"), "").unwrap();
+        assert!(load(&dir, "aplusb").is_err());
+
+        fs::remove_dir_all(dir).ok();
+    }
+}